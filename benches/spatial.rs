@@ -0,0 +1,140 @@
+// Building and querying the spatial indexes at sizes a sketch might reach,
+// run with `cargo bench --bench spatial`. One frame at 60fps is about 16ms.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use nannou::prelude::*;
+
+use sketches::rng;
+use sketches::spatial::{HashGrid, Quadtree};
+
+const SIZE: f32 = 1000.0;
+const QUERIES: usize = 1000;
+
+fn random_points(n: usize) -> Vec<Point2> {
+    rng::seed(1);
+    (0..n)
+        .map(|_| {
+            pt2(
+                rng::random_range(-SIZE / 2.0, SIZE / 2.0),
+                rng::random_range(-SIZE / 2.0, SIZE / 2.0),
+            )
+        })
+        .collect()
+}
+
+fn grid(points: &[Point2]) -> HashGrid {
+    let mut grid = HashGrid::new(5.0);
+    for &p in points {
+        grid.insert(p);
+    }
+    grid
+}
+
+fn quadtree(points: &[Point2]) -> Quadtree {
+    let mut tree = Quadtree::new(Rect::from_w_h(SIZE, SIZE));
+    for &p in points {
+        tree.insert(p);
+    }
+    tree
+}
+
+fn build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build");
+    group.sample_size(10);
+    for &n in &[100_000, 300_000] {
+        let points = random_points(n);
+        group.bench_with_input(BenchmarkId::new("grid", n), &points, |b, ps| {
+            b.iter(|| grid(ps))
+        });
+        group.bench_with_input(BenchmarkId::new("quadtree", n), &points, |b, ps| {
+            b.iter(|| quadtree(ps))
+        });
+    }
+    group.finish();
+}
+
+// A thousand of each query against 300,000 points.
+fn query(c: &mut Criterion) {
+    let points = random_points(300_000);
+    let centers = &points[..QUERIES];
+    let grid = grid(&points);
+    let tree = quadtree(&points);
+    let mut group = c.benchmark_group("query");
+    group.bench_function("grid within", |b| {
+        b.iter(|| {
+            centers
+                .iter()
+                .map(|&p| grid.within(p, 5.0).len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("quadtree within", |b| {
+        b.iter(|| {
+            centers
+                .iter()
+                .map(|&p| tree.within(p, 5.0).len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("grid nearest", |b| {
+        b.iter(|| {
+            centers
+                .iter()
+                .map(|&p| grid.nearest(p, 8).len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("quadtree nearest", |b| {
+        b.iter(|| {
+            centers
+                .iter()
+                .map(|&p| tree.nearest(p, 8).len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("grid in_rect", |b| {
+        b.iter(|| {
+            centers
+                .iter()
+                .map(|&p| grid.in_rect(Rect::from_xy_wh(p, vec2(20.0, 20.0))).len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("quadtree in_rect", |b| {
+        b.iter(|| {
+            centers
+                .iter()
+                .map(|&p| tree.in_rect(Rect::from_xy_wh(p, vec2(20.0, 20.0))).len())
+                .sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+// Moving points, as a flocking sketch would each frame.
+fn update(c: &mut Criterion) {
+    let points = random_points(300_000);
+    let mut group = c.benchmark_group("update");
+    group.sample_size(10);
+    group.bench_function("grid move 10,000", |b| {
+        let mut grid = grid(&points);
+        b.iter(|| {
+            for id in 0..10_000 {
+                grid.update(id, grid.get(id).unwrap() + vec2(0.1, 0.0));
+            }
+        })
+    });
+    group.bench_function("quadtree move 10,000", |b| {
+        let mut tree = quadtree(&points);
+        b.iter(|| {
+            for id in 0..10_000 {
+                let p = tree.get(id).unwrap();
+                tree.update(id, pt2(p.y, p.x));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, build, query, update);
+criterion_main!(benches);
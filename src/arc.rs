@@ -0,0 +1,204 @@
+// Circular and elliptical arcs. An arc is a sweep of the angle around an
+// ellipse, which can be rotated, from a start angle through a sweep that is
+// anticlockwise when positive and clockwise when negative. Angles are radians
+// throughout, with `Arc::degrees` for arcs given in degrees.
+//
+// For the raster an arc becomes points no further from it than a tolerance,
+// so small arcs stay smooth and large ones don't waste vertices. For SVG and
+// plotters it becomes cubic Béziers, one per quarter turn or less, which
+// stay within three ten-thousandths of the radius.
+
+use nannou::prelude::*;
+
+use crate::canvas::Segment;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Arc {
+    pub center: Point2,
+    /// The radius along the ellipse's own x and y axes.
+    pub radii: Vector2,
+    /// The turn of the ellipse's x axis from the canvas x axis.
+    pub rotation: f32,
+    pub start: f32,
+    pub sweep: f32,
+}
+
+impl Arc {
+    /// A circular arc.
+    pub fn new(center: Point2, radius: f32, start: f32, sweep: f32) -> Self {
+        Arc {
+            center,
+            radii: vec2(radius, radius),
+            rotation: 0.0,
+            start,
+            sweep,
+        }
+    }
+
+    /// A circular arc with the angles in degrees.
+    pub fn degrees(center: Point2, radius: f32, start: f32, sweep: f32) -> Self {
+        Arc::new(center, radius, start.to_radians(), sweep.to_radians())
+    }
+
+    /// The whole ellipse, anticlockwise from the end of the x radius.
+    pub fn ellipse(center: Point2, rx: f32, ry: f32) -> Self {
+        Arc::new(center, 1.0, 0.0, TAU).radii(rx, ry)
+    }
+
+    pub fn radii(mut self, rx: f32, ry: f32) -> Self {
+        self.radii = vec2(rx, ry);
+        self
+    }
+
+    pub fn rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// The same arc traced from the other end.
+    pub fn reversed(mut self) -> Self {
+        self.start += self.sweep;
+        self.sweep = -self.sweep;
+        self
+    }
+
+    pub fn end(&self) -> f32 {
+        self.start + self.sweep
+    }
+
+    /// The point at `angle` round the ellipse.
+    pub fn point(&self, angle: f32) -> Point2 {
+        let (s, c) = angle.sin_cos();
+        self.center + self.turn(vec2(self.radii.x * c, self.radii.y * s))
+    }
+
+    // The rate the point moves with the angle.
+    fn tangent(&self, angle: f32) -> Vector2 {
+        let (s, c) = angle.sin_cos();
+        self.turn(vec2(-self.radii.x * s, self.radii.y * c))
+    }
+
+    fn turn(&self, v: Vector2) -> Vector2 {
+        let (s, c) = self.rotation.sin_cos();
+        vec2(v.x * c - v.y * s, v.x * s + v.y * c)
+    }
+
+    /// Points along the arc, both ends included, with the lines between
+    /// them within `tolerance` of it.
+    pub fn points(&self, tolerance: f32) -> Vec<Point2> {
+        let r = self.radii.x.abs().max(self.radii.y.abs());
+        let steps = if r > tolerance {
+            let step = 2.0 * (1.0 - tolerance / r).acos();
+            (self.sweep.abs() / step).ceil().max(1.0) as usize
+        } else {
+            1
+        };
+        (0..=steps)
+            .map(|i| self.point(self.start + self.sweep * i as f32 / steps as f32))
+            .collect()
+    }
+
+    /// The arc as cubic Béziers after a move to its start. Drop the move to
+    /// carry on a path that already ends there.
+    pub fn segments(&self) -> Vec<Segment> {
+        let pieces = (self.sweep.abs() / (PI / 2.0) - 1e-4).ceil().max(1.0) as usize;
+        let step = self.sweep / pieces as f32;
+        // Handles for a quarter turn or less of a circle, stretched with it.
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+        let mut segments = vec![Segment::MoveTo(self.point(self.start))];
+        for i in 0..pieces {
+            let (a, b) = (
+                self.start + step * i as f32,
+                self.start + step * (i + 1) as f32,
+            );
+            let (p, q) = (self.point(a), self.point(b));
+            segments.push(Segment::CubicTo(
+                p + self.tangent(a) * k,
+                q - self.tangent(b) * k,
+                q,
+            ));
+        }
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polyline::segment_distance;
+
+    // The cubic at `t`.
+    fn bezier(p: Point2, c1: Point2, c2: Point2, q: Point2, t: f32) -> Point2 {
+        let s = 1.0 - t;
+        p * (s * s * s) + c1 * (3.0 * s * s * t) + c2 * (3.0 * s * t * t) + q * (t * t * t)
+    }
+
+    #[test]
+    fn degrees_and_directions() {
+        let a = Arc::degrees(pt2(1.0, 1.0), 2.0, 90.0, -45.5);
+        assert!(a.point(a.start).distance(pt2(1.0, 3.0)) < 1e-5);
+        let end = a.point(a.end());
+        let expected =
+            pt2(1.0, 1.0) + vec2(44.5f32.to_radians().cos(), 44.5f32.to_radians().sin()) * 2.0;
+        assert!(end.distance(expected) < 1e-5);
+        let b = a.reversed();
+        assert!(b.point(b.start).distance(end) < 1e-5);
+        // Clockwise, so the centre is right of the chord.
+        let points = a.points(0.01);
+        let (p, q) = (points[0], points[points.len() - 1]);
+        assert!((q - p).perp_dot(a.center - p) < 0.0);
+    }
+
+    #[test]
+    fn points_stay_within_tolerance() {
+        let a = Arc::ellipse(pt2(0.0, 0.0), 100.0, 40.0).rotation(0.3);
+        let coarse = a.points(1.0);
+        let fine = a.points(0.01);
+        assert!(coarse.len() < 30 && fine.len() > 5 * coarse.len());
+        for w in coarse.windows(2) {
+            let mid = (w[0] + w[1]) / 2.0;
+            let nearest = fine
+                .windows(2)
+                .map(|f| segment_distance(mid, f[0], f[1]))
+                .fold(f32::MAX, f32::min);
+            assert!(nearest < 1.01);
+        }
+        // A tiny arc still gets more than one line.
+        assert!(
+            Arc::degrees(pt2(0.0, 0.0), 10.0, 0.0, 30.0)
+                .points(0.01)
+                .len()
+                > 5
+        );
+    }
+
+    #[test]
+    fn beziers_follow_the_arc() {
+        let a = Arc::new(pt2(5.0, -2.0), 1.0, 0.4, -4.0)
+            .radii(30.0, 12.0)
+            .rotation(-0.7);
+        let segments = a.segments();
+        assert_eq!(segments.len(), 4);
+        let mut p = match segments[0] {
+            Segment::MoveTo(p) => p,
+            _ => panic!("no move"),
+        };
+        for (i, s) in segments[1..].iter().enumerate() {
+            if let Segment::CubicTo(c1, c2, q) = *s {
+                for k in 0..=8 {
+                    let t = k as f32 / 8.0;
+                    let angle = a.start + a.sweep * (i as f32 + t) / 3.0;
+                    // Bézier and ellipse parameters differ a little, so
+                    // check the distance to the ellipse.
+                    let b = bezier(p, c1, c2, q, t);
+                    let d = (0..=400)
+                        .map(|j| a.point(angle + (j as f32 - 200.0) / 2000.0).distance(b))
+                        .fold(f32::MAX, f32::min);
+                    assert!(d < 0.01, "{}", d);
+                }
+                p = q;
+            }
+        }
+        assert!(p.distance(a.point(a.end())) < 1e-4);
+    }
+}
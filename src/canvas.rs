@@ -0,0 +1,1047 @@
+// A canvas records what a sketch draws as plain geometry so that the same
+// scene can be shown in a nannou window, rasterized without a window or
+// exported as svg.
+//
+// Masks clip everything drawn inside a scope. The rasterizer and svg export
+// apply them exactly, with soft edges and image masks, `Canvas::clip_masks`
+// turns them into explicit geometry for nannou's window and plotters.
+
+use nannou::color::{IntoLinSrgba, LinSrgba};
+use nannou::geom::path::Builder;
+use nannou::image::RgbaImage;
+use nannou::lyon::tessellation::{LineCap, LineJoin, StrokeOptions};
+use nannou::prelude::*;
+use std::sync::Arc;
+
+use crate::geometry::Polygon;
+
+// Control point distance for approximating a quarter circle with a cubic.
+const KAPPA: f32 = 0.552_284_8;
+
+// 2d affine transformations --------------------------------------------------
+
+/// The affine map `(x, y) -> (a * x + c * y + e, b * x + d * y + f)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Affine {
+    pub const IDENTITY: Affine = Affine {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        e: 0.0,
+        f: 0.0,
+    };
+
+    pub fn translate(x: f32, y: f32) -> Self {
+        Affine {
+            e: x,
+            f: y,
+            ..Affine::IDENTITY
+        }
+    }
+
+    /// Counter-clockwise rotation by `theta` radians about the origin.
+    pub fn rotate(theta: f32) -> Self {
+        let (s, c) = theta.sin_cos();
+        Affine {
+            a: c,
+            b: s,
+            c: -s,
+            d: c,
+            ..Affine::IDENTITY
+        }
+    }
+
+    pub fn scale(sx: f32, sy: f32) -> Self {
+        Affine {
+            a: sx,
+            d: sy,
+            ..Affine::IDENTITY
+        }
+    }
+
+    /// Apply `self` and then `other`.
+    pub fn then(&self, other: &Affine) -> Self {
+        Affine {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f,
+        }
+    }
+
+    pub fn apply(&self, p: Point2) -> Point2 {
+        pt2(
+            self.a * p.x + self.c * p.y + self.e,
+            self.b * p.x + self.d * p.y + self.f,
+        )
+    }
+
+    /// How much lengths are scaled on average, used for stroke widths.
+    pub fn scale_factor(&self) -> f32 {
+        (self.a * self.d - self.b * self.c).abs().sqrt()
+    }
+
+    /// The transform that undoes `self`, which must not collapse the plane.
+    pub fn invert(&self) -> Self {
+        let det = self.a * self.d - self.b * self.c;
+        assert!(det != 0.0, "transform can't be inverted");
+        Affine {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            e: (self.c * self.f - self.d * self.e) / det,
+            f: (self.b * self.e - self.a * self.f) / det,
+        }
+    }
+}
+
+impl Default for Affine {
+    fn default() -> Self {
+        Affine::IDENTITY
+    }
+}
+
+// Shapes ----------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    MoveTo(Point2),
+    LineTo(Point2),
+    CubicTo(Point2, Point2, Point2),
+    Close,
+}
+
+impl Segment {
+    pub fn map(&self, f: impl Fn(Point2) -> Point2) -> Segment {
+        match *self {
+            Segment::MoveTo(p) => Segment::MoveTo(f(p)),
+            Segment::LineTo(p) => Segment::LineTo(f(p)),
+            Segment::CubicTo(c1, c2, p) => Segment::CubicTo(f(c1), f(c2), f(p)),
+            Segment::Close => Segment::Close,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cap {
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Join {
+    Miter,
+    Round,
+    Bevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stroke {
+    pub color: LinSrgba,
+    pub weight: f32,
+    pub cap: Cap,
+    pub join: Join,
+}
+
+/// A path with its fill and stroke. Coordinates are in nannou's space, origin
+/// at the center and y pointing up, with the canvas transform already applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    pub segments: Vec<Segment>,
+    pub fill: Option<LinSrgba>,
+    pub stroke: Option<Stroke>,
+    pub rule: FillRule,
+    /// The innermost mask scope the shape was drawn in, an index into
+    /// `Canvas::masks`.
+    pub mask: Option<usize>,
+    // Scale of the transform the shape was drawn with, applied to the stroke.
+    scale: f32,
+}
+
+impl Shape {
+    fn new(segments: Vec<Segment>) -> Self {
+        Shape {
+            segments,
+            fill: None,
+            stroke: None,
+            rule: FillRule::EvenOdd,
+            mask: None,
+            scale: 1.0,
+        }
+    }
+
+    /// An open path through `points`, stroked in white.
+    pub fn polyline<I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = Point2>,
+    {
+        let mut shape = Shape::new(polyline_segments(points, false));
+        shape.stroke(WHITE);
+        shape
+    }
+
+    /// A closed path through `points`, filled in white.
+    pub fn polygon<I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = Point2>,
+    {
+        let mut shape = Shape::new(polyline_segments(points, true));
+        shape.fill(WHITE);
+        shape
+    }
+
+    pub fn ellipse(center: Point2, w: f32, h: f32) -> Self {
+        let mut shape = Shape::new(ellipse_segments(center, w / 2.0, h / 2.0));
+        shape.fill(WHITE);
+        shape
+    }
+
+    pub fn rect(center: Point2, w: f32, h: f32) -> Self {
+        let (x, y) = (w / 2.0, h / 2.0);
+        let corners = vec![
+            center + vec2(-x, y),
+            center + vec2(x, y),
+            center + vec2(x, -y),
+            center + vec2(-x, -y),
+        ];
+        Shape::polygon(corners)
+    }
+
+    /// A path filled in white.
+    pub fn path(segments: Vec<Segment>) -> Self {
+        let mut shape = Shape::new(segments);
+        shape.fill(WHITE);
+        shape
+    }
+
+    /// Set the fill color of filled shapes and the stroke color otherwise.
+    pub fn color<C: IntoLinSrgba<f32>>(&mut self, color: C) -> &mut Self {
+        if self.fill.is_some() {
+            self.fill(color)
+        } else {
+            self.stroke(color)
+        }
+    }
+
+    pub fn fill<C: IntoLinSrgba<f32>>(&mut self, color: C) -> &mut Self {
+        self.fill = Some(color.into_lin_srgba());
+        self
+    }
+
+    pub fn no_fill(&mut self) -> &mut Self {
+        self.fill = None;
+        self
+    }
+
+    /// Stroke with `color`, keeping any weight, cap and join already set.
+    pub fn stroke<C: IntoLinSrgba<f32>>(&mut self, color: C) -> &mut Self {
+        let color = color.into_lin_srgba();
+        match self.stroke.as_mut() {
+            Some(s) => s.color = color,
+            None => {
+                self.stroke = Some(Stroke {
+                    color,
+                    weight: 1.0,
+                    cap: Cap::Butt,
+                    join: Join::Miter,
+                })
+            }
+        }
+        self
+    }
+
+    pub fn no_stroke(&mut self) -> &mut Self {
+        self.stroke = None;
+        self
+    }
+
+    fn stroke_mut(&mut self) -> &mut Stroke {
+        if self.stroke.is_none() {
+            self.stroke(WHITE);
+        }
+        self.stroke.as_mut().unwrap()
+    }
+
+    /// Stroke weight before the canvas transform is applied.
+    pub fn weight(&mut self, weight: f32) -> &mut Self {
+        self.stroke_mut().weight = weight;
+        self
+    }
+
+    pub fn cap(&mut self, cap: Cap) -> &mut Self {
+        self.stroke_mut().cap = cap;
+        self
+    }
+
+    pub fn join(&mut self, join: Join) -> &mut Self {
+        self.stroke_mut().join = join;
+        self
+    }
+
+    pub fn caps_round(&mut self) -> &mut Self {
+        self.cap(Cap::Round)
+    }
+
+    pub fn join_round(&mut self) -> &mut Self {
+        self.join(Join::Round)
+    }
+
+    pub fn rule(&mut self, rule: FillRule) -> &mut Self {
+        self.rule = rule;
+        self
+    }
+
+    /// The region the fill covers as explicit geometry, filled or not.
+    pub fn region(&self) -> Polygon {
+        fill_region(&self.segments, self.rule)
+    }
+
+    /// The stroke with its weight scaled by the canvas transform.
+    pub fn scaled_stroke(&self) -> Option<Stroke> {
+        self.stroke.map(|s| Stroke {
+            weight: s.weight * self.scale,
+            ..s
+        })
+    }
+
+    pub fn transform(&mut self, t: &Affine) -> &mut Self {
+        for s in self.segments.iter_mut() {
+            *s = s.map(|p| t.apply(p));
+        }
+        self.scale *= t.scale_factor();
+        self
+    }
+
+    fn lyon_path(&self) -> nannou::geom::path::Path {
+        let mut builder = Builder::new();
+        for s in &self.segments {
+            builder = match *s {
+                Segment::MoveTo(p) => builder.move_to(p),
+                Segment::LineTo(p) => builder.line_to(p),
+                Segment::CubicTo(c1, c2, p) => builder.cubic_bezier_to(c1, c2, p),
+                Segment::Close => builder.close(),
+            }
+        }
+        builder.build()
+    }
+}
+
+pub fn polyline_segments<I>(points: I, closed: bool) -> Vec<Segment>
+where
+    I: IntoIterator<Item = Point2>,
+{
+    let mut segments: Vec<Segment> = points
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| {
+            if i == 0 {
+                Segment::MoveTo(p)
+            } else {
+                Segment::LineTo(p)
+            }
+        })
+        .collect();
+    if closed && !segments.is_empty() {
+        segments.push(Segment::Close);
+    }
+    segments
+}
+
+/// The subpaths of a path as polylines, cubics split into lines no more than
+/// `tolerance` away from the curve. Closed subpaths end on their first point.
+pub fn flatten(segments: &[Segment], tolerance: f32) -> Vec<Vec<Point2>> {
+    let mut lines: Vec<Vec<Point2>> = vec![];
+    for s in segments {
+        match *s {
+            Segment::MoveTo(p) => lines.push(vec![p]),
+            Segment::LineTo(p) => lines
+                .last_mut()
+                .expect("path must start with MoveTo")
+                .push(p),
+            Segment::CubicTo(c1, c2, p) => {
+                let line = lines.last_mut().expect("path must start with MoveTo");
+                let p0 = *line.last().unwrap();
+                // How far the curve strays from its chords bounds the steps.
+                let dd = (p0 - c1 * 2.0 + c2)
+                    .magnitude()
+                    .max((c1 - c2 * 2.0 + p).magnitude());
+                let n = ((0.75 * dd / tolerance).sqrt().ceil() as usize).clamp(1, 256);
+                for i in 1..=n {
+                    let t = i as f32 / n as f32;
+                    let u = 1.0 - t;
+                    line.push(
+                        p0 * (u * u * u)
+                            + c1 * (3.0 * u * u * t)
+                            + c2 * (3.0 * u * t * t)
+                            + p * (t * t * t),
+                    );
+                }
+            }
+            Segment::Close => {
+                if let Some(line) = lines.last_mut() {
+                    if line.len() > 1 && line[0] != line[line.len() - 1] {
+                        line.push(line[0]);
+                    }
+                    // Drawing carries on from the start of a closed subpath.
+                    let start = line[0];
+                    lines.push(vec![start]);
+                }
+            }
+        }
+    }
+    lines.retain(|l| l.len() > 1);
+    lines
+}
+
+// Each subpath is closed for filling, as renderers do.
+fn fill_region(segments: &[Segment], rule: FillRule) -> Polygon {
+    let rings = flatten(segments, 0.1)
+        .into_iter()
+        .map(|mut l| {
+            if l[0] == l[l.len() - 1] {
+                l.pop();
+            }
+            l
+        })
+        .filter(|l| l.len() > 2)
+        .collect();
+    match rule {
+        FillRule::NonZero => Polygon::nonzero(rings),
+        FillRule::EvenOdd => Polygon::even_odd(rings),
+    }
+}
+
+pub fn ellipse_segments(center: Point2, rx: f32, ry: f32) -> Vec<Segment> {
+    let (kx, ky) = (KAPPA * rx, KAPPA * ry);
+    let c = center;
+    vec![
+        Segment::MoveTo(c + vec2(rx, 0.0)),
+        Segment::CubicTo(c + vec2(rx, ky), c + vec2(kx, ry), c + vec2(0.0, ry)),
+        Segment::CubicTo(c + vec2(-kx, ry), c + vec2(-rx, ky), c + vec2(-rx, 0.0)),
+        Segment::CubicTo(c + vec2(-rx, -ky), c + vec2(-kx, -ry), c + vec2(0.0, -ry)),
+        Segment::CubicTo(c + vec2(kx, -ry), c + vec2(rx, -ky), c + vec2(rx, 0.0)),
+        Segment::Close,
+    ]
+}
+
+// Masks -----------------------------------------------------------------------
+
+/// The part of an image that decides how much a mask lets through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Alpha,
+    /// Brightness times alpha, so white lets everything through.
+    Luminance,
+}
+
+#[derive(Debug, Clone)]
+pub enum MaskSource {
+    /// Lets through what is inside the path. Made by `MaskSource::path`,
+    /// which works out the region.
+    Path {
+        segments: Vec<Segment>,
+        rule: FillRule,
+        region: Region,
+    },
+    /// Lets through as much as the image's channel says and nothing outside
+    /// the image. `transform` maps the unit square, y up, onto the image.
+    Image {
+        image: Arc<RgbaImage>,
+        transform: Affine,
+        channel: Channel,
+    },
+}
+
+/// The inside of a path mask, worked out once by `MaskSource::path` and moved
+/// with the path after that.
+#[derive(Debug, Clone)]
+pub struct Region(Polygon);
+
+impl Region {
+    pub fn polygon(&self) -> &Polygon {
+        &self.0
+    }
+
+    // Reflections reverse the rings, so they are turned back to keep outer
+    // rings anti-clockwise.
+    fn transformed(&self, t: &Affine) -> Region {
+        let flip = t.a * t.d - t.b * t.c < 0.0;
+        let rings = (self.0)
+            .rings
+            .iter()
+            .map(|ring| {
+                let mut ring: Vec<Point2> = ring.iter().map(|&p| t.apply(p)).collect();
+                if flip {
+                    ring.reverse();
+                }
+                ring
+            })
+            .collect();
+        Region(Polygon { rings })
+    }
+}
+
+impl MaskSource {
+    pub fn path(segments: Vec<Segment>, rule: FillRule) -> Self {
+        let region = Region(fill_region(&segments, rule));
+        MaskSource::Path {
+            segments,
+            rule,
+            region,
+        }
+    }
+
+    /// How much is let through at `p`, from 0 to 1, with hard path edges.
+    pub fn value(&self, p: Point2) -> f32 {
+        match self {
+            MaskSource::Path { region, .. } => {
+                if region.polygon().contains(p) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            MaskSource::Image {
+                image,
+                transform,
+                channel,
+            } => {
+                let u = transform.invert().apply(p);
+                if !(0.0..=1.0).contains(&u.x) || !(0.0..=1.0).contains(&u.y) {
+                    return 0.0;
+                }
+                // Bilinear between pixel centers.
+                let (w, h) = image.dimensions();
+                let x = (u.x * w as f32 - 0.5).clamp(0.0, (w - 1) as f32);
+                let y = ((1.0 - u.y) * h as f32 - 0.5).clamp(0.0, (h - 1) as f32);
+                let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+                let (x1, y1) = ((x0 + 1).min(w - 1), (y0 + 1).min(h - 1));
+                let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+                let v = |x, y| {
+                    let [r, g, b, a] = image.get_pixel(x, y).0;
+                    let a = a as f32 / 255.0;
+                    match channel {
+                        Channel::Alpha => a,
+                        Channel::Luminance => {
+                            (0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32) / 255.0 * a
+                        }
+                    }
+                };
+                let top = v(x0, y0) * (1.0 - fx) + v(x1, y0) * fx;
+                let bottom = v(x0, y1) * (1.0 - fx) + v(x1, y1) * fx;
+                top * (1.0 - fy) + bottom * fy
+            }
+        }
+    }
+
+    /// The region a path lets through, `None` for images.
+    pub fn to_polygon(&self) -> Option<Polygon> {
+        match self {
+            MaskSource::Path { region, .. } => Some(region.polygon().clone()),
+            MaskSource::Image { .. } => None,
+        }
+    }
+}
+
+/// What `Canvas::with_mask` clips to. Masks are given in canvas coordinates
+/// and drawn with the canvas transform like any shape.
+#[derive(Debug, Clone)]
+pub struct Mask {
+    pub source: MaskSource,
+    /// Let through what the source hides and hide what it lets through.
+    pub inverse: bool,
+    /// The width over which the edge fades out, 0 for a sharp edge.
+    pub feather: f32,
+}
+
+impl Mask {
+    pub fn path(segments: Vec<Segment>, rule: FillRule) -> Self {
+        Mask {
+            source: MaskSource::path(segments, rule),
+            inverse: false,
+            feather: 0.0,
+        }
+    }
+
+    pub fn polygon<I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = Point2>,
+    {
+        Mask::path(polyline_segments(points, true), FillRule::NonZero)
+    }
+
+    pub fn circle(center: Point2, radius: f32) -> Self {
+        Mask::path(ellipse_segments(center, radius, radius), FillRule::NonZero)
+    }
+
+    pub fn rect(center: Point2, w: f32, h: f32) -> Self {
+        Mask::region(&Polygon::rect(Rect::from_xy_wh(center, vec2(w, h))))
+    }
+
+    pub fn region(polygon: &Polygon) -> Self {
+        Mask::path(polygon.segments(), FillRule::NonZero)
+    }
+
+    /// Stretch `image` over `rect` and let through as much as `channel` says.
+    pub fn image(image: RgbaImage, rect: Rect, channel: Channel) -> Self {
+        let transform =
+            Affine::scale(rect.w(), rect.h()).then(&Affine::translate(rect.left(), rect.bottom()));
+        Mask {
+            source: MaskSource::Image {
+                image: Arc::new(image),
+                transform,
+                channel,
+            },
+            inverse: false,
+            feather: 0.0,
+        }
+    }
+
+    pub fn inverse(mut self) -> Self {
+        self.inverse = !self.inverse;
+        self
+    }
+
+    pub fn feather(mut self, width: f32) -> Self {
+        self.feather = width;
+        self
+    }
+
+    /// The standard deviation of the gaussian blur that softens the edge, so
+    /// that it fades from 90% to 10% over about `feather`.
+    pub fn sigma(&self) -> f32 {
+        self.feather / 2.5
+    }
+
+    /// How much is let through at `p`, from 0 to 1, ignoring the feather.
+    pub fn value(&self, p: Point2) -> f32 {
+        let v = self.source.value(p);
+        if self.inverse {
+            1.0 - v
+        } else {
+            v
+        }
+    }
+
+    fn transformed(&self, t: &Affine) -> Mask {
+        let source = match &self.source {
+            MaskSource::Path {
+                segments,
+                rule,
+                region,
+            } => MaskSource::Path {
+                segments: segments.iter().map(|s| s.map(|p| t.apply(p))).collect(),
+                rule: *rule,
+                region: region.transformed(t),
+            },
+            MaskSource::Image {
+                image,
+                transform,
+                channel,
+            } => MaskSource::Image {
+                image: image.clone(),
+                transform: transform.then(t),
+                channel: *channel,
+            },
+        };
+        Mask {
+            source,
+            inverse: self.inverse,
+            feather: self.feather * t.scale_factor(),
+        }
+    }
+
+    // The parts of `line` the mask lets through. Image masks are sampled
+    // every unit along the line and let through where they are over half.
+    fn clip(&self, region: Option<&Polygon>, line: &[Point2]) -> Vec<Vec<Point2>> {
+        match region {
+            Some(r) if self.inverse => r.clip_outside(line),
+            Some(r) => r.clip(line),
+            None => {
+                let mut runs: Vec<Vec<Point2>> = vec![];
+                let mut open = false;
+                for (i, w) in line.windows(2).enumerate() {
+                    let n = ((w[1] - w[0]).magnitude().ceil() as usize).max(1);
+                    let start = if i == 0 { 0 } else { 1 };
+                    for j in start..=n {
+                        let p = w[0] + (w[1] - w[0]) * (j as f32 / n as f32);
+                        if self.value(p) >= 0.5 {
+                            if !open {
+                                runs.push(vec![]);
+                                open = true;
+                            }
+                            runs.last_mut().unwrap().push(p);
+                        } else {
+                            open = false;
+                        }
+                    }
+                }
+                runs.retain(|r| r.len() > 1);
+                runs
+            }
+        }
+    }
+}
+
+/// A mask and the scope it was opened in.
+#[derive(Debug, Clone)]
+pub struct MaskScope {
+    pub mask: Mask,
+    pub parent: Option<usize>,
+}
+
+// Canvas ----------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    pub width: f32,
+    pub height: f32,
+    background: Option<LinSrgba>,
+    shapes: Vec<Shape>,
+    transform: Affine,
+    masks: Vec<MaskScope>,
+    scope: Option<usize>,
+}
+
+impl Canvas {
+    pub fn new(width: f32, height: f32) -> Self {
+        Canvas {
+            width,
+            height,
+            background: None,
+            shapes: vec![],
+            transform: Affine::IDENTITY,
+            masks: vec![],
+            scope: None,
+        }
+    }
+
+    /// The area of the canvas, centered on the origin.
+    pub fn bounds(&self) -> Rect {
+        Rect::from_w_h(self.width, self.height)
+    }
+
+    /// Clear the canvas to `color`. A canvas without a background is drawn
+    /// over whatever was drawn before it.
+    pub fn background<C: IntoLinSrgba<f32>>(&mut self, color: C) {
+        self.background = Some(color.into_lin_srgba());
+        self.shapes.clear();
+    }
+
+    pub fn background_color(&self) -> Option<LinSrgba> {
+        self.background
+    }
+
+    pub fn shapes(&self) -> &[Shape] {
+        &self.shapes
+    }
+
+    /// Add `shape` transformed by the current transform.
+    pub fn add(&mut self, mut shape: Shape) -> &mut Shape {
+        shape.transform(&self.transform);
+        shape.mask = self.scope;
+        self.shapes.push(shape);
+        self.shapes.last_mut().unwrap()
+    }
+
+    pub fn line(&mut self, a: Point2, b: Point2) -> &mut Shape {
+        self.add(Shape::polyline(vec![a, b]))
+    }
+
+    pub fn polyline<I>(&mut self, points: I) -> &mut Shape
+    where
+        I: IntoIterator<Item = Point2>,
+    {
+        self.add(Shape::polyline(points))
+    }
+
+    pub fn polygon<I>(&mut self, points: I) -> &mut Shape
+    where
+        I: IntoIterator<Item = Point2>,
+    {
+        self.add(Shape::polygon(points))
+    }
+
+    pub fn ellipse(&mut self, center: Point2, w: f32, h: f32) -> &mut Shape {
+        self.add(Shape::ellipse(center, w, h))
+    }
+
+    pub fn rect(&mut self, center: Point2, w: f32, h: f32) -> &mut Shape {
+        self.add(Shape::rect(center, w, h))
+    }
+
+    pub fn path(&mut self, segments: Vec<Segment>) -> &mut Shape {
+        self.add(Shape::path(segments))
+    }
+
+    /// Draw with `t` applied before the current transform.
+    pub fn with_transform(&mut self, t: Affine, f: impl FnOnce(&mut Canvas)) {
+        let saved = self.transform;
+        self.transform = t.then(&saved);
+        f(self);
+        self.transform = saved;
+    }
+
+    /// Clip everything `f` draws to `mask`, which is drawn with the current
+    /// transform. Masks nest, a shape drawn inside two is clipped by both.
+    pub fn with_mask(&mut self, mask: Mask, f: impl FnOnce(&mut Canvas)) {
+        let mask = mask.transformed(&self.transform);
+        self.masks.push(MaskScope {
+            mask,
+            parent: self.scope,
+        });
+        let saved = self.scope;
+        self.scope = Some(self.masks.len() - 1);
+        f(self);
+        self.scope = saved;
+    }
+
+    pub fn masks(&self) -> &[MaskScope] {
+        &self.masks
+    }
+
+    /// The mask scopes that `scope` lies inside, outermost first.
+    pub fn mask_chain(&self, scope: Option<usize>) -> Vec<usize> {
+        let mut chain = vec![];
+        let mut scope = scope;
+        while let Some(i) = scope {
+            chain.push(i);
+            scope = self.masks[i].parent;
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// The canvas with its masks applied to the geometry, for nannou's window
+    /// and plotters. Fills are cut to the mask region and strokes split where
+    /// they leave it, so edges are sharp and feathers are ignored. Image masks
+    /// only clip strokes.
+    pub fn clip_masks(&self) -> Canvas {
+        let regions: Vec<Option<Polygon>> = self
+            .masks
+            .iter()
+            .map(|m| m.mask.source.to_polygon())
+            .collect();
+        let mut canvas = Canvas::new(self.width, self.height);
+        canvas.background = self.background;
+        for shape in &self.shapes {
+            let chain = self.mask_chain(shape.mask);
+            if chain.is_empty() {
+                canvas.shapes.push(shape.clone());
+                continue;
+            }
+            let lines = flatten(&shape.segments, 0.1);
+            if let Some(fill) = shape.fill {
+                let mut region = shape.region();
+                for &i in &chain {
+                    match &regions[i] {
+                        Some(r) if self.masks[i].mask.inverse => region = region.difference(r),
+                        Some(r) => region = region.intersection(r),
+                        None => {}
+                    }
+                }
+                let mut clipped = Shape::new(region.segments());
+                clipped.fill(fill).rule(FillRule::NonZero);
+                canvas.shapes.push(clipped);
+            }
+            if let Some(stroke) = shape.stroke {
+                let mut lines = lines;
+                for &i in &chain {
+                    let mask = &self.masks[i].mask;
+                    lines = lines
+                        .iter()
+                        .flat_map(|l| mask.clip(regions[i].as_ref(), l))
+                        .collect();
+                }
+                for line in lines {
+                    let mut clipped = Shape::new(polyline_segments(line, false));
+                    clipped.stroke = Some(stroke);
+                    clipped.scale = shape.scale;
+                    canvas.shapes.push(clipped);
+                }
+            }
+        }
+        canvas
+    }
+
+    /// Replay the canvas with nannou's `Draw`, with masks applied by
+    /// `clip_masks`.
+    pub fn draw(&self, draw: &Draw) {
+        if !self.masks.is_empty() {
+            return self.clip_masks().draw(draw);
+        }
+        if let Some(bg) = self.background {
+            draw.background().color(bg);
+        }
+        for shape in &self.shapes {
+            if shape.segments.is_empty() {
+                continue;
+            }
+            let path = shape.lyon_path();
+            if let Some(fill) = shape.fill {
+                draw.path().fill().color(fill).events(path.iter());
+            }
+            if let Some(stroke) = shape.scaled_stroke() {
+                let opts = StrokeOptions::default()
+                    .with_line_width(stroke.weight)
+                    .with_line_cap(match stroke.cap {
+                        Cap::Butt => LineCap::Butt,
+                        Cap::Round => LineCap::Round,
+                        Cap::Square => LineCap::Square,
+                    })
+                    .with_line_join(match stroke.join {
+                        Join::Miter => LineJoin::Miter,
+                        Join::Round => LineJoin::Round,
+                        Join::Bevel => LineJoin::Bevel,
+                    });
+                draw.path()
+                    .stroke()
+                    .stroke_opts(opts)
+                    .color(stroke.color)
+                    .events(path.iter());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(p: Point2, q: Point2) -> bool {
+        (p - q).magnitude() < 1e-4
+    }
+
+    #[test]
+    fn affine_composition() {
+        let t = Affine::rotate(PI / 2.0).then(&Affine::translate(10.0, 0.0));
+        assert!(close(t.apply(pt2(1.0, 0.0)), pt2(10.0, 1.0)));
+        let s = Affine::scale(2.0, 2.0);
+        assert!((s.scale_factor() - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn transforms_apply_to_shapes_and_weights() {
+        let mut canvas = Canvas::new(100.0, 100.0);
+        canvas.with_transform(Affine::scale(3.0, 3.0), |c| {
+            c.with_transform(Affine::translate(1.0, 1.0), |c| {
+                c.line(pt2(0.0, 0.0), pt2(1.0, 0.0)).weight(2.0);
+            });
+        });
+        canvas.line(pt2(0.0, 0.0), pt2(1.0, 0.0));
+        let shapes = canvas.shapes();
+        assert_eq!(shapes[0].segments[0], Segment::MoveTo(pt2(3.0, 3.0)));
+        assert_eq!(shapes[0].scaled_stroke().unwrap().weight, 6.0);
+        assert_eq!(shapes[1].segments[0], Segment::MoveTo(pt2(0.0, 0.0)));
+    }
+
+    #[test]
+    fn color_targets_fill_or_stroke() {
+        let mut canvas = Canvas::new(10.0, 10.0);
+        canvas.ellipse(pt2(0.0, 0.0), 4.0, 2.0).color(RED);
+        canvas.line(pt2(0.0, 0.0), pt2(1.0, 1.0)).color(RED);
+        let shapes = canvas.shapes();
+        assert!(shapes[0].fill.is_some() && shapes[0].stroke.is_none());
+        assert!(shapes[1].fill.is_none() && shapes[1].stroke.is_some());
+    }
+
+    #[test]
+    fn affine_inverse_undoes_it() {
+        let t = Affine::rotate(0.3)
+            .then(&Affine::scale(2.0, 0.5))
+            .then(&Affine::translate(4.0, -1.0));
+        let p = pt2(3.0, 7.0);
+        assert!(close(t.invert().apply(t.apply(p)), p));
+    }
+
+    #[test]
+    fn masks_nest_and_follow_the_transform() {
+        let mut canvas = Canvas::new(100.0, 100.0);
+        canvas.with_transform(Affine::translate(10.0, 0.0), |c| {
+            c.with_mask(Mask::circle(pt2(0.0, 0.0), 20.0), |c| {
+                c.rect(pt2(0.0, 0.0), 5.0, 5.0);
+                c.with_mask(Mask::rect(pt2(0.0, 0.0), 10.0, 10.0).inverse(), |c| {
+                    c.rect(pt2(0.0, 0.0), 5.0, 5.0);
+                });
+            });
+        });
+        canvas.rect(pt2(0.0, 0.0), 5.0, 5.0);
+        let masks: Vec<_> = canvas.shapes().iter().map(|s| s.mask).collect();
+        assert_eq!(masks, vec![Some(0), Some(1), None]);
+        assert_eq!(canvas.mask_chain(Some(1)), vec![0, 1]);
+        let circle = &canvas.masks()[0].mask;
+        assert_eq!(circle.value(pt2(25.0, 0.0)), 1.0);
+        assert_eq!(circle.value(pt2(-15.0, 0.0)), 0.0);
+        // A mirrored mask moves its region rather than working it out again,
+        // and keeps the outer ring anti-clockwise.
+        let mirror = Affine::scale(-1.0, 1.0).then(&Affine::translate(30.0, 0.0));
+        let triangle = Mask::polygon(vec![pt2(0.0, 0.0), pt2(10.0, 0.0), pt2(0.0, 10.0)]);
+        let moved = triangle.transformed(&mirror);
+        let region = moved.source.to_polygon().unwrap();
+        assert!(region.area() > 0.0);
+        assert_eq!(moved.value(pt2(28.0, 2.0)), 1.0);
+        assert_eq!(moved.value(pt2(32.0, 2.0)), 0.0);
+    }
+
+    #[test]
+    fn clip_masks_cuts_fills_and_strokes() {
+        let mut canvas = Canvas::new(100.0, 100.0);
+        canvas.with_mask(Mask::rect(pt2(0.0, 0.0), 20.0, 20.0), |c| {
+            c.rect(pt2(10.0, 0.0), 20.0, 20.0);
+            c.line(pt2(-50.0, 0.0), pt2(50.0, 0.0));
+        });
+        canvas.with_mask(Mask::rect(pt2(0.0, 0.0), 20.0, 20.0).inverse(), |c| {
+            c.line(pt2(-50.0, 0.0), pt2(50.0, 0.0));
+        });
+        let clipped = canvas.clip_masks();
+        assert!(clipped.masks().is_empty());
+        let shapes = clipped.shapes();
+        let rings = flatten(&shapes[0].segments, 0.1);
+        let ring = &rings[0][..rings[0].len() - 1];
+        assert!((crate::geometry::signed_area(ring) - 200.0).abs() < 1e-3);
+        assert_eq!(
+            shapes[1].segments,
+            vec![
+                Segment::MoveTo(pt2(-10.0, 0.0)),
+                Segment::LineTo(pt2(10.0, 0.0))
+            ]
+        );
+        assert_eq!(shapes.len(), 4);
+    }
+
+    #[test]
+    fn image_masks_sample_the_channel() {
+        let mut image = RgbaImage::new(2, 1);
+        image.put_pixel(0, 0, nannou::image::Rgba([255, 255, 255, 255]));
+        image.put_pixel(1, 0, nannou::image::Rgba([0, 0, 0, 255]));
+        let rect = Rect::from_w_h(40.0, 20.0);
+        let luma = Mask::image(image.clone(), rect, Channel::Luminance);
+        assert_eq!(luma.value(pt2(-15.0, 0.0)), 1.0);
+        assert_eq!(luma.value(pt2(15.0, 0.0)), 0.0);
+        assert!((luma.value(pt2(0.0, 0.0)) - 0.5).abs() < 1e-3);
+        assert_eq!(luma.value(pt2(30.0, 0.0)), 0.0);
+        let alpha = Mask::image(image, rect, Channel::Alpha).inverse();
+        assert_eq!(alpha.value(pt2(15.0, 0.0)), 0.0);
+        assert_eq!(alpha.value(pt2(30.0, 0.0)), 1.0);
+    }
+}
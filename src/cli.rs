@@ -0,0 +1,69 @@
+use getopts::{Matches, Options};
+use lazy_static::lazy_static;
+use std::sync::RwLock;
+
+// The sketch being run by the launcher and the arguments that were passed to
+// it. Sketches read their options from here instead of `env::args` so that
+// `sketches run <name> [args]` behaves like running the sketch on its own.
+struct Invocation {
+    name: Option<&'static str>,
+    args: Vec<String>,
+}
+
+lazy_static! {
+    static ref INVOCATION: RwLock<Invocation> = RwLock::new(Invocation {
+        name: None,
+        args: vec![],
+    });
+}
+
+pub fn set_invocation(name: &'static str, args: Vec<String>) {
+    let mut invocation = INVOCATION.write().unwrap();
+    invocation.name = Some(name);
+    invocation.args = args;
+}
+
+/// The name of the running sketch, if it was started through the launcher.
+pub fn sketch_name() -> Option<&'static str> {
+    INVOCATION.read().unwrap().name
+}
+
+/// The arguments passed to the running sketch, not including its name.
+pub fn args() -> Vec<String> {
+    INVOCATION.read().unwrap().args.clone()
+}
+
+/// Parse the sketch arguments with `opts`. Every sketch accepts `-p/--png`
+/// and `-s/--seed`.
+pub fn parse(mut opts: Options) -> Matches {
+    opts.optflag("p", "png", "save frames to file as png.");
+    opts.optopt("s", "seed", "seed for the random number generator.", "SEED");
+    match opts.parse(args()) {
+        Ok(m) => m,
+        Err(f) => panic!("{}", f),
+    }
+}
+
+/// Was the sketch asked to save its output with `-p`?
+pub fn png() -> bool {
+    parse(Options::new()).opt_present("p")
+}
+
+/// The seed given with `-s/--seed`, read without the sketch's own options so
+/// that the launcher can seed the generator before the sketch starts.
+pub fn seed() -> Option<u64> {
+    let args = args();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = match arg.as_str() {
+            "-s" | "--seed" => iter.next().cloned(),
+            a if a.starts_with("--seed=") => Some(a["--seed=".len()..].to_string()),
+            a if a.starts_with("-s") && a.len() > 2 => Some(a[2..].to_string()),
+            _ => None,
+        };
+        if let Some(v) = value {
+            return Some(v.parse().unwrap_or_else(|_| panic!("invalid seed `{}`", v)));
+        }
+    }
+    None
+}
@@ -0,0 +1,461 @@
+// Space filling curves. Every curve is laid out on its own lattice, a point can
+// be asked for by its index along the curve, or the whole curve generated in
+// order, scaled into the unit square or fitted to a rectangle.
+
+use nannou::geom::Rect;
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Curve {
+    Hilbert,
+    /// Four Hilbert curves joined into a loop.
+    Moore,
+    /// Fills a 3x3 grid at each order.
+    Peano,
+    /// The flowsnake, fills a hexagonal island rather than a square.
+    Gosper,
+    /// Sierpiński–Knopp, a loop through the centres of halved triangles.
+    Sierpinski,
+    /// Morton order, jumps rather than stepping to a neighbour.
+    ZOrder,
+}
+
+impl Curve {
+    pub const ALL: [Curve; 6] = [
+        Curve::Hilbert,
+        Curve::Moore,
+        Curve::Peano,
+        Curve::Gosper,
+        Curve::Sierpinski,
+        Curve::ZOrder,
+    ];
+
+    /// The number of points in the curve at `order`.
+    pub fn len(self, order: usize) -> usize {
+        match self {
+            Curve::Hilbert | Curve::Moore | Curve::ZOrder => 4usize.pow(order as u32),
+            Curve::Peano => 9usize.pow(order as u32),
+            Curve::Gosper => 7usize.pow(order as u32) + 1,
+            Curve::Sierpinski => 2 * 4usize.pow(order as u32),
+        }
+    }
+
+    /// Does the last point join back up with the first?
+    pub fn is_closed(self) -> bool {
+        matches!(self, Curve::Moore | Curve::Sierpinski)
+    }
+
+    /// The `index`th point in the curve's own coordinates, grid cells for the
+    /// square curves and unit steps for Gosper.
+    pub fn lattice_point(self, index: usize, order: usize) -> Point2 {
+        assert!(index < self.len(order), "index past the end of the curve");
+        match self {
+            Curve::Hilbert => hilbert(index, order),
+            Curve::Moore => moore(index, order),
+            Curve::Peano => peano(index, order),
+            Curve::Gosper => gosper(index, order),
+            Curve::Sierpinski => sierpinski(index, order),
+            Curve::ZOrder => z_order(index, order),
+        }
+    }
+
+    /// The area covered by the curve's lattice.
+    pub fn lattice_bounds(self, order: usize) -> Rect {
+        let side = |base: usize| base.pow(order as u32) as f32;
+        let square = |n: f32| Rect::from_corners(pt2(-0.5, -0.5), pt2(n - 0.5, n - 0.5));
+        match self {
+            Curve::Hilbert | Curve::Moore | Curve::ZOrder => square(side(2)),
+            Curve::Peano => square(side(3)),
+            Curve::Gosper => gosper_bounds(order),
+            Curve::Sierpinski => Rect::from_corners(pt2(0.0, 0.0), pt2(1.0, 1.0)),
+        }
+    }
+
+    /// The `index`th point scaled into the unit square.
+    pub fn point(self, index: usize, order: usize) -> Point2 {
+        to_unit(self.lattice_bounds(order), self.lattice_point(index, order))
+    }
+
+    /// Every point of the curve in order, in the unit square.
+    pub fn points(self, order: usize) -> Vec<Point2> {
+        let bounds = self.lattice_bounds(order);
+        (0..self.len(order))
+            .map(|i| to_unit(bounds, self.lattice_point(i, order)))
+            .collect()
+    }
+
+    /// Every point of the curve in order, scaled to the largest square that
+    /// fits in `rect` and centred in it.
+    pub fn fit(self, order: usize, rect: Rect) -> Vec<Point2> {
+        let size = rect.w().min(rect.h());
+        self.points(order)
+            .into_iter()
+            .map(|p| rect.xy() + (p - vec2(0.5, 0.5)) * size)
+            .collect()
+    }
+}
+
+// Scale `p` so that the longer side of `bounds` becomes the unit square, with
+// the shorter side centred.
+fn to_unit(bounds: Rect, p: Point2) -> Point2 {
+    let scale = 1.0 / bounds.w().max(bounds.h());
+    (p - bounds.xy()) * scale + vec2(0.5, 0.5)
+}
+
+/// Displace every point by a noise field, `amount` is the largest offset and
+/// `frequency` scales the points' coordinates before the noise is sampled.
+/// This is what twists the curve in Hilbert Swerve.
+pub fn swerve<N>(points: &[Point2], noise: &N, frequency: f64, amount: f32) -> Vec<Point2>
+where
+    N: NoiseFn<[f64; 3]>,
+{
+    points
+        .iter()
+        .map(|p| {
+            let x = frequency * p.x as f64;
+            let y = frequency * p.y as f64;
+            let dx = amount * noise.get([x, y, 0.0]) as f32;
+            let dy = amount * noise.get([x, y, 0.1]) as f32;
+            pt2(p.x + dx, p.y + dy)
+        })
+        .collect()
+}
+
+/// The `k`th cell of a Hilbert curve on a `2^order` square grid, starting at
+/// the bottom left and ending at the bottom right.
+pub fn hilbert(k: usize, order: usize) -> Point2 {
+    if order == 0 {
+        return pt2(0.0, 0.0);
+    }
+    let points = [pt2(0.0, 0.0), pt2(0.0, 1.0), pt2(1.0, 1.0), pt2(1.0, 0.0)];
+    let mut v = points[k & 3];
+    let mut i = k;
+
+    for j in 1..order {
+        i >>= 2;
+        let index = i & 3;
+        let n = pow(2, j) as f32;
+        match index {
+            0 => {
+                std::mem::swap(&mut v.x, &mut v.y);
+            }
+            1 => {
+                v.y += n;
+            }
+            2 => {
+                v.x += n;
+                v.y += n;
+            }
+            3 => {
+                let temp = n - 1.0 - v.x;
+                v.x = n - 1.0 - v.y;
+                v.y = temp;
+                v.x += n;
+            }
+            _ => {}
+        }
+    }
+    v
+}
+
+// Hilbert curves of one order less in each quadrant, up the left half and
+// down the right, turned so that the ends meet at the bottom middle.
+fn moore(k: usize, order: usize) -> Point2 {
+    if order == 0 {
+        return pt2(0.0, 0.0);
+    }
+    let m = pow(2, order - 1) as usize;
+    let p = hilbert(k % (m * m), order - 1);
+    let m = m as f32;
+    match k / (m * m) as usize {
+        0 => pt2(m - 1.0 - p.y, p.x),
+        1 => pt2(m - 1.0 - p.y, p.x + m),
+        2 => pt2(p.y + m, 2.0 * m - 1.0 - p.x),
+        _ => pt2(p.y + m, m - 1.0 - p.x),
+    }
+}
+
+// Peano's own construction: the base 3 digits of `k` alternate between x and
+// y, and each digit is reflected when the digits of the other coordinate
+// before it add up to an odd number.
+fn peano(k: usize, order: usize) -> Point2 {
+    let mut digits = vec![0; 2 * order];
+    let mut i = k;
+    for d in digits.iter_mut().rev() {
+        *d = i % 3;
+        i /= 3;
+    }
+    let (mut x, mut y) = (0, 0);
+    let (mut x_sum, mut y_sum) = (0, 0);
+    for j in 0..order {
+        let a = digits[2 * j];
+        let a = if y_sum % 2 == 1 { 2 - a } else { a };
+        x_sum += digits[2 * j];
+        let b = digits[2 * j + 1];
+        let b = if x_sum % 2 == 1 { 2 - b } else { b };
+        y_sum += digits[2 * j + 1];
+        x = 3 * x + a;
+        y = 3 * y + b;
+    }
+    pt2(x as f32, y as f32)
+}
+
+fn z_order(k: usize, order: usize) -> Point2 {
+    let (mut x, mut y) = (0, 0);
+    for bit in 0..order {
+        x |= ((k >> (2 * bit)) & 1) << bit;
+        y |= ((k >> (2 * bit + 1)) & 1) << bit;
+    }
+    pt2(x as f32, y as f32)
+}
+
+// The unit square is cut into two right triangles along its diagonal, and each
+// triangle is halved `2 * order` times. The curve visits the centroids of the
+// smallest triangles, passing through the right angle between the halves.
+fn sierpinski(k: usize, order: usize) -> Point2 {
+    let levels = 2 * order;
+    let (mut a, mut r, mut c) = if k >> levels == 0 {
+        (pt2(0.0, 0.0), pt2(1.0, 0.0), pt2(1.0, 1.0))
+    } else {
+        (pt2(1.0, 1.0), pt2(0.0, 1.0), pt2(0.0, 0.0))
+    };
+    for level in (0..levels).rev() {
+        let m = (a + c) / 2.0;
+        if (k >> level) & 1 == 0 {
+            c = r;
+            r = m;
+        } else {
+            a = r;
+            r = m;
+        }
+    }
+    (a + r + c) / 3.0
+}
+
+// The flowsnake's two rules, `+` and `-` turn by 60 degrees. Both rules make
+// seven segments that end where seven segments of the other would.
+const GOSPER_A: &str = "A-B--B+A++AA+B-";
+const GOSPER_B: &str = "+A-AA--B-A++A+B";
+
+fn gosper_rule(symbol: char) -> &'static str {
+    if symbol == 'A' {
+        GOSPER_A
+    } else {
+        GOSPER_B
+    }
+}
+
+fn heading(v: Vector2, turns: i32) -> Vector2 {
+    v.rotate(turns as f32 * PI / 3.0)
+}
+
+// From start to end of either rule expanded `depth` times, facing along x.
+fn gosper_steps(order: usize) -> Vec<Vector2> {
+    let mut steps = vec![vec2(1.0, 0.0)];
+    for d in 1..=order {
+        let mut step = vec2(0.0, 0.0);
+        let mut turns = 0;
+        for c in GOSPER_A.chars() {
+            match c {
+                '+' => turns += 1,
+                '-' => turns -= 1,
+                _ => step += heading(steps[d - 1], turns),
+            }
+        }
+        steps.push(step);
+    }
+    steps
+}
+
+// Walk down the rules, skipping whole sub-curves by their end to end step
+// until reaching the one that contains vertex `k`.
+fn gosper(k: usize, order: usize) -> Point2 {
+    let steps = gosper_steps(order);
+    let mut pos = pt2(0.0, 0.0);
+    let mut turns = 0;
+    let mut symbol = 'A';
+    let mut depth = order;
+    let mut k = k;
+    'descend: while depth > 0 {
+        for c in gosper_rule(symbol).chars() {
+            match c {
+                '+' => turns += 1,
+                '-' => turns -= 1,
+                _ => {
+                    let size = 7usize.pow(depth as u32 - 1);
+                    if k < size {
+                        symbol = c;
+                        depth -= 1;
+                        continue 'descend;
+                    }
+                    pos += heading(steps[depth - 1], turns);
+                    k -= size;
+                }
+            }
+        }
+        break;
+    }
+    if depth == 0 && k == 1 {
+        pos += heading(steps[0], turns);
+    }
+    pos
+}
+
+// The bounds of a rule expanded `depth` times starting at the origin and
+// facing `turns` * 60 degrees, memoised as there are only 12 per depth.
+fn rule_bounds(
+    symbol: char,
+    depth: usize,
+    turns: i32,
+    steps: &[Vector2],
+    memo: &mut Vec<Option<Rect>>,
+) -> Rect {
+    let key = depth * 12 + (symbol == 'B') as usize * 6 + turns.rem_euclid(6) as usize;
+    if let Some(rect) = memo[key] {
+        return rect;
+    }
+    let origin = pt2(0.0, 0.0);
+    let rect = if depth == 0 {
+        Rect::from_corners(origin, heading(steps[0], turns))
+    } else {
+        let mut rect = Rect::from_corners(origin, origin);
+        let mut pos = origin;
+        let mut t = turns;
+        for c in gosper_rule(symbol).chars() {
+            match c {
+                '+' => t += 1,
+                '-' => t -= 1,
+                _ => {
+                    let child = rule_bounds(c, depth - 1, t, steps, memo).shift(pos);
+                    rect = rect
+                        .stretch_to_point(child.bottom_left())
+                        .stretch_to_point(child.top_right());
+                    pos += heading(steps[depth - 1], t);
+                }
+            }
+        }
+        rect
+    };
+    memo[key] = Some(rect);
+    rect
+}
+
+fn gosper_bounds(order: usize) -> Rect {
+    let steps = gosper_steps(order);
+    let mut memo = vec![None; (order + 1) * 12];
+    rule_bounds('A', order, 0, &steps, &mut memo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(a: Point2, b: Point2) -> f32 {
+        (a - b).magnitude()
+    }
+
+    #[test]
+    fn grid_curves_step_to_neighbours() {
+        for &(curve, order) in &[(Curve::Hilbert, 4), (Curve::Moore, 4), (Curve::Peano, 3)] {
+            let n = curve.len(order);
+            let pts: Vec<Point2> = (0..n).map(|i| curve.lattice_point(i, order)).collect();
+            for w in pts.windows(2) {
+                assert!((distance(w[0], w[1]) - 1.0).abs() < 1e-4, "{:?}", curve);
+            }
+            let mut cells: Vec<(i32, i32)> = pts.iter().map(|p| (p.x as i32, p.y as i32)).collect();
+            cells.sort();
+            cells.dedup();
+            assert_eq!(cells.len(), n, "{:?} revisits a cell", curve);
+            let closed = (distance(pts[0], pts[n - 1]) - 1.0).abs() < 1e-4;
+            assert_eq!(closed, curve.is_closed(), "{:?}", curve);
+        }
+    }
+
+    #[test]
+    fn z_order_visits_every_cell() {
+        let mut cells: Vec<(i32, i32)> = (0..64)
+            .map(|i| Curve::ZOrder.lattice_point(i, 3))
+            .map(|p| (p.x as i32, p.y as i32))
+            .collect();
+        assert_eq!(cells[..4], [(0, 0), (1, 0), (0, 1), (1, 1)]);
+        cells.sort();
+        cells.dedup();
+        assert_eq!(cells.len(), 64);
+    }
+
+    #[test]
+    fn sierpinski_is_a_closed_loop() {
+        let order = 3;
+        let pts = Curve::Sierpinski.points(order);
+        assert_eq!(pts.len(), 128);
+        let step = distance(pts[0], pts[1]);
+        for i in 0..pts.len() {
+            let d = distance(pts[i], pts[(i + 1) % pts.len()]);
+            assert!(d <= step * 1.5 && d > 0.0);
+        }
+    }
+
+    // Expand the rules and walk them with a turtle.
+    fn gosper_turtle(order: usize) -> Vec<Point2> {
+        let mut s = String::from("A");
+        for _ in 0..order {
+            s = s
+                .chars()
+                .map(|c| match c {
+                    'A' => GOSPER_A.to_string(),
+                    'B' => GOSPER_B.to_string(),
+                    c => c.to_string(),
+                })
+                .collect();
+        }
+        let mut pos = pt2(0.0, 0.0);
+        let mut turns = 0;
+        let mut pts = vec![pos];
+        for c in s.chars() {
+            match c {
+                '+' => turns += 1,
+                '-' => turns -= 1,
+                _ => {
+                    pos += heading(vec2(1.0, 0.0), turns);
+                    pts.push(pos);
+                }
+            }
+        }
+        pts
+    }
+
+    #[test]
+    fn gosper_matches_turtle() {
+        let order = 3;
+        let walked = gosper_turtle(order);
+        assert_eq!(walked.len(), Curve::Gosper.len(order));
+        for (i, p) in walked.iter().enumerate() {
+            assert!(distance(*p, Curve::Gosper.lattice_point(i, order)) < 1e-3);
+        }
+        let bounds = Curve::Gosper.lattice_bounds(order);
+        for p in &walked {
+            assert!(bounds.pad(-1e-3).contains(*p));
+        }
+        let (l, r) = walked
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(l, r), p| (l.min(p.x), r.max(p.x)));
+        assert!((bounds.left() - l).abs() < 1e-3 && (bounds.right() - r).abs() < 1e-3);
+    }
+
+    #[test]
+    fn points_fit() {
+        let unit = Rect::from_corners(pt2(0.0, 0.0), pt2(1.0, 1.0));
+        let rect = Rect::from_x_y_w_h(100.0, -50.0, 400.0, 200.0);
+        for curve in Curve::ALL.iter() {
+            for p in curve.points(2) {
+                assert!(unit.contains(p), "{:?}", curve);
+            }
+            for p in curve.fit(2, rect) {
+                assert!(p.x >= 0.0 && p.x <= 200.0 && p.y >= -150.0 && p.y <= 50.0);
+            }
+        }
+        // Cells centres of a 2x2 grid.
+        assert_eq!(Curve::Hilbert.point(1, 1), pt2(0.25, 0.75));
+    }
+}
@@ -0,0 +1,210 @@
+// Render every sketch without a window and rebuild README.md, and optionally
+// an html page, from the sketch registry. Static sketches are saved to
+// `img/<name>.png`, animated ones to `gif/<name>.gif`, and a thumbnail of each
+// to `img/thumbs/<name>.png`.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::raster::{self, Recorder};
+use crate::rng;
+use crate::sketch::{Kind, Sketch, SKETCHES};
+
+/// Every sketch is rendered from this seed unless another is given.
+pub const SEED: u64 = 1978;
+
+const STATIC_SIZE: u32 = 1200;
+const ANIMATED_SIZE: u32 = 400;
+const THUMB_SIZE: u32 = 240;
+
+const INTRO: &str = "## Sketches
+A collection of sketches made using the nannou creative coding package in Rust.
+
+Every sketch is run through the `sketches` launcher:
+
+```
+cargo run -- list                # list every sketch
+cargo run -- info <name>         # describe a sketch
+cargo run -- run <name> [args]   # run a sketch, e.g. `cargo run -- run hexvar -p`
+cargo run -- gallery [--html]    # render every sketch and regenerate this file
+cargo run -- new <name> [opts]   # start a new sketch from a template
+```
+
+This file is generated by `sketches gallery` from the sketch registry in
+`src/sketch/mod.rs`, edit the sketches rather than this file. The same command
+renders the images below into `img/` and `gif/`, every sketch is seeded so the
+results are repeatable.
+";
+
+/// Where the full size render of a sketch is saved, relative to the project.
+pub fn image_path(sketch: &Sketch) -> PathBuf {
+    match sketch.kind {
+        Kind::Animated => Path::new("gif").join(sketch.name).with_extension("gif"),
+        _ => Path::new("img").join(sketch.name).with_extension("png"),
+    }
+}
+
+pub fn thumb_path(sketch: &Sketch) -> PathBuf {
+    Path::new("img")
+        .join("thumbs")
+        .join(sketch.name)
+        .with_extension("png")
+}
+
+/// Render `sketch` into `root`, returns false for sketches that can't be
+/// rendered without a window.
+pub fn render(sketch: &Sketch, root: &Path, seed: u64) -> bool {
+    let render = match sketch.render {
+        Some(render) => render,
+        None => return false,
+    };
+    rng::seed(seed);
+    let size = match sketch.kind {
+        Kind::Animated => ANIMATED_SIZE,
+        _ => STATIC_SIZE,
+    };
+    let mut rec = Recorder::new(size);
+    render(&mut rec);
+    rec.save(root.join(image_path(sketch)));
+
+    let last = rec.frames().last().unwrap();
+    let thumb = root.join(thumb_path(sketch));
+    fs::create_dir_all(thumb.parent().unwrap()).unwrap();
+    raster::thumbnail(last, THUMB_SIZE)
+        .save(thumb)
+        .expect("failed to save thumbnail");
+    true
+}
+
+fn source_path(sketch: &Sketch) -> String {
+    format!("src/sketch/{}.rs", sketch.name)
+}
+
+// Paths in markdown and html always use forward slashes.
+fn link(path: PathBuf) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// The README, with images for the sketches that have been rendered into
+/// `root`.
+pub fn readme(root: &Path) -> String {
+    let mut md = String::from(INTRO);
+    for s in SKETCHES {
+        writeln!(md, "\n***\n\n# {}\n", s.title).unwrap();
+        if root.join(image_path(s)).exists() {
+            writeln!(md, "![{}]({})\n", s.title, link(image_path(s))).unwrap();
+        }
+        writeln!(md, "{}\n", s.description).unwrap();
+        if let Some(credits) = s.credits {
+            writeln!(md, "{}\n", credits).unwrap();
+        }
+        writeln!(md, "[[source code]]({})", source_path(s)).unwrap();
+    }
+    md
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The html page, with thumbnails for the sketches that have been rendered
+/// into `root`.
+pub fn html(root: &Path) -> String {
+    let mut page = String::from(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Sketches</title>
+<style>
+body { font-family: sans-serif; background: #222; color: #eee; margin: 2em; }
+a { color: #9cf; }
+.grid { display: flex; flex-wrap: wrap; gap: 1.5em; }
+.sketch { width: 240px; }
+.sketch img { display: block; max-width: 240px; }
+.sketch p { font-size: 0.85em; }
+</style>
+</head>
+<body>
+<h1>Sketches</h1>
+<div class=\"grid\">
+",
+    );
+    for s in SKETCHES {
+        writeln!(page, "<div class=\"sketch\">").unwrap();
+        if root.join(image_path(s)).exists() && root.join(thumb_path(s)).exists() {
+            writeln!(
+                page,
+                "<a href=\"{}\"><img src=\"{}\" alt=\"{}\"></a>",
+                link(image_path(s)),
+                link(thumb_path(s)),
+                escape(s.title)
+            )
+            .unwrap();
+        }
+        writeln!(page, "<h3>{}</h3>", escape(s.title)).unwrap();
+        writeln!(page, "<p>{}</p>", escape(s.description)).unwrap();
+        if let Some(credits) = s.credits {
+            writeln!(page, "<p><em>{}</em></p>", escape(credits)).unwrap();
+        }
+        writeln!(
+            page,
+            "<p><a href=\"{}\">source code</a></p>\n</div>",
+            source_path(s)
+        )
+        .unwrap();
+    }
+    page.push_str("</div>\n</body>\n</html>\n");
+    page
+}
+
+/// Render `sketches` into `root` and regenerate the pages from the registry.
+pub fn build(root: &Path, sketches: &[&Sketch], seed: u64, with_html: bool) {
+    for s in sketches {
+        print!("{:12} ", s.name);
+        if render(s, root, seed) {
+            println!("{}", link(image_path(s)));
+        } else {
+            println!("(not rendered)");
+        }
+    }
+    fs::write(root.join("README.md"), readme(root)).expect("failed to write README.md");
+    if with_html {
+        fs::write(root.join("gallery.html"), html(root)).expect("failed to write gallery.html");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_covers_every_sketch() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let md = readme(root);
+        for s in SKETCHES {
+            assert!(md.contains(&format!("# {}\n", s.title)));
+            assert!(md.contains(&format!("[[source code]](src/sketch/{}.rs)", s.name)));
+            assert!(Path::new(&source_path(s)).exists(), "missing {}", s.name);
+        }
+        let html = html(root);
+        assert_eq!(html.matches("<div class=\"sketch\">").count(), SKETCHES.len());
+    }
+
+    #[test]
+    fn readme_images_exist() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let committed = fs::read_to_string(root.join("README.md")).unwrap();
+        for md in &[readme(root), committed] {
+            for image in md.split("](").skip(1).filter_map(|s| s.split(')').next()) {
+                if image.starts_with("img/") || image.starts_with("gif/") {
+                    assert!(root.join(image).exists(), "missing {}", image);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,610 @@
+// Polygon booleans, offsetting and clipping that give explicit geometry, so
+// a cut out shape is a shape with a hole rather than a path that relies on a
+// fill rule, and plotter output gets the same outlines as the raster.
+//
+// Every operation works the same way: cut all the edges where they cross or
+// touch, keep the pieces with the result on one side and not the other, and
+// join them back up into rings. The work is done in f64 and is quadratic in
+// the number of edges, fine for the hundreds or low thousands a sketch has.
+
+use nannou::geom::Vector2;
+use nannou::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::arc::Arc;
+use crate::canvas::{polyline_segments, Join, Segment};
+
+type V = Vector2<f64>;
+
+fn to_v(p: Point2) -> V {
+    vec2(p.x as f64, p.y as f64)
+}
+
+fn to_p(v: V) -> Point2 {
+    pt2(v.x as f32, v.y as f32)
+}
+
+// Points this close are the same point.
+const SNAP: f64 = 1e-6;
+
+fn key(v: V) -> (i64, i64) {
+    ((v.x / SNAP).round() as i64, (v.y / SNAP).round() as i64)
+}
+
+/// The signed area of a ring, positive when it runs anti-clockwise.
+pub fn signed_area(ring: &[Point2]) -> f32 {
+    (0..ring.len())
+        .map(|i| ring[i].perp_dot(ring[(i + 1) % ring.len()]))
+        .sum::<f32>()
+        / 2.0
+}
+
+/// A region bounded by closed rings, outer boundaries anti-clockwise and
+/// holes clockwise. Several outer rings make a region in several pieces.
+/// Rings don't repeat their first point.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Polygon {
+    pub rings: Vec<Vec<Point2>>,
+}
+
+impl Polygon {
+    /// The region inside `outer`, in either direction.
+    pub fn new(mut outer: Vec<Point2>) -> Self {
+        if signed_area(&outer) < 0.0 {
+            outer.reverse();
+        }
+        Polygon { rings: vec![outer] }
+    }
+
+    pub fn rect(r: Rect) -> Self {
+        Polygon::new(vec![
+            r.bottom_left(),
+            r.bottom_right(),
+            r.top_right(),
+            r.top_left(),
+        ])
+    }
+
+    /// A regular polygon with `sides` sides inscribed in the circle.
+    pub fn circle(center: Point2, radius: f32, sides: usize) -> Self {
+        Polygon::new(
+            (0..sides)
+                .map(|i| {
+                    let a = i as f32 / sides as f32 * TAU;
+                    center + vec2(a.cos(), a.sin()) * radius
+                })
+                .collect(),
+        )
+    }
+
+    /// Add a hole, in either direction. It should lie inside the region, use
+    /// `difference` for anything else.
+    pub fn hole(mut self, mut ring: Vec<Point2>) -> Self {
+        if signed_area(&ring) > 0.0 {
+            ring.reverse();
+        }
+        self.rings.push(ring);
+        self
+    }
+
+    /// The region an even-odd fill of `rings` covers, e.g. paths that used
+    /// overlapping subpaths for holes.
+    pub fn even_odd(rings: Vec<Vec<Point2>>) -> Self {
+        let rs = Polygon { rings }.vs();
+        let edges = split(&[&rs]);
+        Polygon::from_vs(resolve(edges, |p| winding(&rs, p) % 2 != 0))
+    }
+
+    /// The region a nonzero fill of `rings` covers.
+    pub fn nonzero(rings: Vec<Vec<Point2>>) -> Self {
+        let rs = Polygon { rings }.vs();
+        let edges = split(&[&rs]);
+        Polygon::from_vs(resolve(edges, |p| winding(&rs, p) != 0))
+    }
+
+    fn vs(&self) -> Vec<Vec<V>> {
+        self.rings
+            .iter()
+            .map(|r| r.iter().map(|&p| to_v(p)).collect())
+            .collect()
+    }
+
+    fn from_vs(rings: Vec<Vec<V>>) -> Self {
+        Polygon {
+            rings: rings
+                .into_iter()
+                .map(|r| r.into_iter().map(to_p).collect())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rings.is_empty()
+    }
+
+    /// Holes count against the area.
+    pub fn area(&self) -> f32 {
+        self.rings.iter().map(|r| signed_area(r)).sum()
+    }
+
+    pub fn contains(&self, p: Point2) -> bool {
+        winding(&self.vs(), to_v(p)) != 0
+    }
+
+    fn boolean(&self, other: &Polygon, op: impl Fn(bool, bool) -> bool) -> Polygon {
+        let (a, b) = (self.vs(), other.vs());
+        let edges = split(&[&a, &b]);
+        Polygon::from_vs(resolve(edges, |p| {
+            op(winding(&a, p) != 0, winding(&b, p) != 0)
+        }))
+    }
+
+    pub fn union(&self, other: &Polygon) -> Polygon {
+        self.boolean(other, |a, b| a || b)
+    }
+
+    pub fn intersection(&self, other: &Polygon) -> Polygon {
+        self.boolean(other, |a, b| a && b)
+    }
+
+    pub fn difference(&self, other: &Polygon) -> Polygon {
+        self.boolean(other, |a, b| a && !b)
+    }
+
+    /// In one or the other but not both.
+    pub fn xor(&self, other: &Polygon) -> Polygon {
+        self.boolean(other, |a, b| a != b)
+    }
+
+    /// Grow the region by `distance`, or shrink it for a negative distance.
+    /// Parts narrower than twice a shrinking distance disappear. `join` fills
+    /// the corners, mitred corners are cut off where they would reach more
+    /// than four times the distance.
+    pub fn offset(&self, distance: f32, join: Join) -> Polygon {
+        if distance == 0.0 {
+            return self.clone();
+        }
+        let d = distance as f64;
+        let raw: Vec<Vec<V>> = self
+            .vs()
+            .iter()
+            .map(|ring| offset_ring(ring, d, join))
+            .collect();
+        // The raw rings loop back on themselves at corners and where they
+        // pass each other, the result is where they wind positively.
+        let edges = split(&[&raw]);
+        Polygon::from_vs(resolve(edges, |p| winding(&raw, p) > 0))
+    }
+
+    fn clip_line(&self, line: &[Point2], inside: bool) -> Vec<Vec<Point2>> {
+        if line.len() < 2 {
+            return vec![];
+        }
+        let rings = self.vs();
+        let line: Vec<V> = line.iter().map(|&p| to_v(p)).collect();
+        // Only edges near the line can cross it.
+        let near = |a: V, b: V, p: V, q: V| {
+            let e = 1e-9;
+            a.x.max(b.x) >= p.x.min(q.x) - e
+                && a.x.min(b.x) <= p.x.max(q.x) + e
+                && a.y.max(b.y) >= p.y.min(q.y) - e
+                && a.y.min(b.y) <= p.y.max(q.y) + e
+        };
+        let lo = line
+            .iter()
+            .fold(line[0], |m, v| vec2(m.x.min(v.x), m.y.min(v.y)));
+        let hi = line
+            .iter()
+            .fold(line[0], |m, v| vec2(m.x.max(v.x), m.y.max(v.y)));
+        let edges: Vec<(V, V)> = rings
+            .iter()
+            .flat_map(|ring| (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()])))
+            .filter(|&(a, b)| near(a, b, lo, hi))
+            .collect();
+        let mut runs: Vec<Vec<Point2>> = vec![];
+        let mut open = false;
+        // Whether the last piece was kept. It holds until the line crosses
+        // an edge, saving a winding count for every piece.
+        let mut last = None;
+        for w in line.windows(2) {
+            let (p, q) = (w[0], w[1]);
+            let mut ts = vec![0.0, 1.0];
+            for &(a, b) in &edges {
+                if !near(a, b, p, q) {
+                    continue;
+                }
+                if let Some((t, _)) = crossing(p, q, a, b) {
+                    ts.push(t);
+                }
+            }
+            let crossed = ts.len() > 2;
+            ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for t in ts.windows(2) {
+                if t[1] - t[0] < 1e-12 {
+                    continue;
+                }
+                let (a, b) = (p + (q - p) * t[0], p + (q - p) * t[1]);
+                let keep = match last {
+                    Some(keep) if !crossed => keep,
+                    _ => (winding(&rings, (a + b) * 0.5) != 0) == inside,
+                };
+                last = Some(keep);
+                if keep {
+                    if !open {
+                        runs.push(vec![to_p(a)]);
+                        open = true;
+                    }
+                    runs.last_mut().unwrap().push(to_p(b));
+                } else {
+                    open = false;
+                }
+            }
+        }
+        runs
+    }
+
+    /// The parts of the polyline inside the region.
+    pub fn clip(&self, line: &[Point2]) -> Vec<Vec<Point2>> {
+        self.clip_line(line, true)
+    }
+
+    /// The parts of the polyline outside the region.
+    pub fn clip_outside(&self, line: &[Point2]) -> Vec<Vec<Point2>> {
+        self.clip_line(line, false)
+    }
+
+    /// A path for `Canvas::path`, any fill rule fills it the same.
+    pub fn segments(&self) -> Vec<Segment> {
+        self.rings
+            .iter()
+            .flat_map(|r| polyline_segments(r.iter().cloned(), true))
+            .collect()
+    }
+
+    /// The rings as closed polylines, for plotters.
+    pub fn outlines(&self) -> Vec<Vec<Point2>> {
+        self.rings
+            .iter()
+            .map(|r| {
+                let mut line = r.clone();
+                line.push(r[0]);
+                line
+            })
+            .collect()
+    }
+}
+
+// How many times the rings wind anti-clockwise around `p`.
+fn winding(rings: &[Vec<V>], p: V) -> i32 {
+    let mut w = 0;
+    for ring in rings {
+        for i in 0..ring.len() {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            let side = (b - a).perp_dot(p - a);
+            if a.y <= p.y {
+                if b.y > p.y && side > 0.0 {
+                    w += 1;
+                }
+            } else if b.y <= p.y && side < 0.0 {
+                w -= 1;
+            }
+        }
+    }
+    w
+}
+
+// Where `pq` and `ab` cross, as the fraction along each, if they aren't
+// parallel.
+fn crossing(p: V, q: V, a: V, b: V) -> Option<(f64, f64)> {
+    let (r, s) = (q - p, b - a);
+    let denom = r.perp_dot(s);
+    if denom.abs() <= 1e-12 * r.magnitude() * s.magnitude() {
+        return None;
+    }
+    let t = (a - p).perp_dot(s) / denom;
+    let u = (a - p).perp_dot(r) / denom;
+    let e = 1e-9;
+    if t < -e || t > 1.0 + e || u < -e || u > 1.0 + e {
+        return None;
+    }
+    Some((t.clamp(0.0, 1.0), u.clamp(0.0, 1.0)))
+}
+
+// The edges of all the rings cut wherever they cross or touch another, with
+// overlapping pieces kept once.
+fn split(groups: &[&[Vec<V>]]) -> Vec<(V, V)> {
+    let edges: Vec<(V, V)> = groups
+        .iter()
+        .flat_map(|rings| rings.iter())
+        .flat_map(|ring| (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()])))
+        .filter(|(a, b)| key(*a) != key(*b))
+        .collect();
+    // The cuts along each edge, with the point so both edges share it.
+    let mut cuts: Vec<Vec<(f64, V)>> = edges
+        .iter()
+        .map(|&(a, b)| vec![(0.0, a), (1.0, b)])
+        .collect();
+    let boxes: Vec<(V, V)> = edges
+        .iter()
+        .map(|&(a, b)| {
+            (
+                vec2(a.x.min(b.x) - SNAP, a.y.min(b.y) - SNAP),
+                vec2(a.x.max(b.x) + SNAP, a.y.max(b.y) + SNAP),
+            )
+        })
+        .collect();
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            let (lo, hi) = (boxes[i], boxes[j]);
+            if lo.1.x < hi.0.x || hi.1.x < lo.0.x || lo.1.y < hi.0.y || hi.1.y < lo.0.y {
+                continue;
+            }
+            let (a, b) = edges[i];
+            let (c, d) = edges[j];
+            match crossing(a, b, c, d) {
+                Some((t, u)) => {
+                    // Share the existing point when the crossing is at an end.
+                    let p = if u == 0.0 {
+                        c
+                    } else if u == 1.0 {
+                        d
+                    } else if t == 0.0 {
+                        a
+                    } else if t == 1.0 {
+                        b
+                    } else {
+                        a + (b - a) * t
+                    };
+                    cuts[i].push((t, p));
+                    cuts[j].push((u, p));
+                }
+                None => {
+                    // Parallel, cut at the ends of the other if they overlap.
+                    for &(e, f, k) in &[(a, b, j), (c, d, i)] {
+                        let (g, h) = edges[k];
+                        let r = h - g;
+                        for &p in &[e, f] {
+                            let t = (p - g).dot(r) / r.magnitude2();
+                            let off = (p - g).perp_dot(r).abs() / r.magnitude();
+                            if off < SNAP && t > 0.0 && t < 1.0 {
+                                cuts[k].push((t, p));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    // Ordered so the same input gives the same rings.
+    let mut pieces = BTreeMap::new();
+    for mut cs in cuts {
+        cs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        for w in cs.windows(2) {
+            let (ka, kb) = (key(w[0].1), key(w[1].1));
+            if ka != kb {
+                pieces
+                    .entry((ka.min(kb), ka.max(kb)))
+                    .or_insert((w[0].1, w[1].1));
+            }
+        }
+    }
+    pieces.into_values().collect()
+}
+
+// Keep the edges with `inside` on one side only, turned to have it on the
+// left, and join them into rings.
+fn resolve(edges: Vec<(V, V)>, inside: impl Fn(V) -> bool) -> Vec<Vec<V>> {
+    let eps = SNAP;
+    let kept: Vec<(V, V)> = edges
+        .into_iter()
+        .filter_map(|(a, b)| {
+            let m = (a + b) * 0.5;
+            let n = vec2(a.y - b.y, b.x - a.x).normalize() * eps;
+            match (inside(m + n), inside(m - n)) {
+                (true, false) => Some((a, b)),
+                (false, true) => Some((b, a)),
+                _ => None,
+            }
+        })
+        .collect();
+    let mut from: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (i, &(a, _)) in kept.iter().enumerate() {
+        from.entry(key(a)).or_default().push(i);
+    }
+    let mut used = vec![false; kept.len()];
+    let mut rings = vec![];
+    for first in 0..kept.len() {
+        if used[first] {
+            continue;
+        }
+        let start = key(kept[first].0);
+        let mut ring = vec![];
+        let mut e = first;
+        loop {
+            used[e] = true;
+            let (a, b) = kept[e];
+            ring.push(a);
+            if key(b) == start {
+                break;
+            }
+            // Where rings touch take the sharpest right turn, keeping them
+            // apart.
+            let dir = b - a;
+            let next = from.get(&key(b)).and_then(|es| {
+                es.iter().copied().filter(|&i| !used[i]).min_by(|&i, &j| {
+                    let turn = |k: usize| {
+                        let d = kept[k].1 - kept[k].0;
+                        dir.perp_dot(d).atan2(dir.dot(d))
+                    };
+                    turn(i).partial_cmp(&turn(j)).unwrap()
+                })
+            });
+            match next {
+                Some(n) => e = n,
+                None => {
+                    ring.clear();
+                    break;
+                }
+            }
+        }
+        let ring = simplify(ring);
+        if ring.len() > 2 {
+            rings.push(ring);
+        }
+    }
+    rings
+}
+
+// Drop the points in the middle of straight runs, left by the cuts.
+fn simplify(ring: Vec<V>) -> Vec<V> {
+    let n = ring.len();
+    (0..n)
+        .filter(|&i| {
+            let (a, b, c) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+            let (u, v) = (b - a, c - b);
+            u.perp_dot(v).abs() > 1e-9 * u.magnitude() * v.magnitude() || u.dot(v) < 0.0
+        })
+        .map(|i| ring[i])
+        .collect()
+}
+
+// The ring moved `d` to the right of its edges, outwards for outer rings and
+// holes alike, looping back through the corner where the edges close in.
+fn offset_ring(ring: &[V], d: f64, join: Join) -> Vec<V> {
+    let n = ring.len();
+    let normal = |i: usize| {
+        let e = ring[(i + 1) % n] - ring[i];
+        vec2(e.y, -e.x).normalize()
+    };
+    let mut out = vec![];
+    for (i, &v) in ring.iter().enumerate() {
+        let (n1, n2) = (normal((i + n - 1) % n), normal(i));
+        // Turning left opens a gap outside the corner, turning right one
+        // inside.
+        let turn = n1.perp_dot(n2).atan2(n1.dot(n2));
+        if turn * d <= 0.0 {
+            out.extend_from_slice(&[v + n1 * d, v, v + n2 * d]);
+            continue;
+        }
+        match join {
+            Join::Round => {
+                // The arc about the origin, so only the offsets are rounded
+                // to f32.
+                let from = n1 * d.signum();
+                let arc = Arc::new(
+                    pt2(0.0, 0.0),
+                    d.abs() as f32,
+                    from.y.atan2(from.x) as f32,
+                    turn as f32,
+                );
+                out.extend(
+                    arc.points(0.1)
+                        .into_iter()
+                        .map(|p| v + vec2(p.x as f64, p.y as f64)),
+                );
+            }
+            Join::Miter if (turn.abs() / 2.0).cos() > 0.25 => {
+                let m = (n1 + n2).normalize() / (turn.abs() / 2.0).cos();
+                out.push(v + m * d);
+            }
+            _ => out.extend_from_slice(&[v + n1 * d, v + n2 * d]),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32, size: f32) -> Polygon {
+        Polygon::rect(Rect::from_corners(pt2(x, y), pt2(x + size, y + size)))
+    }
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-3 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn booleans() {
+        let a = square(0.0, 0.0, 2.0);
+        let b = square(1.0, 1.0, 2.0);
+        assert!(close(a.union(&b).area(), 7.0));
+        assert_eq!(a.union(&b).rings[0].len(), 8);
+        assert!(close(a.intersection(&b).area(), 1.0));
+        assert!(close(a.difference(&b).area(), 3.0));
+        let x = a.xor(&b);
+        assert!(close(x.area(), 6.0));
+        assert!(x.contains(pt2(0.5, 0.5)) && !x.contains(pt2(1.5, 1.5)));
+        // Apart, touching along an edge and one inside the other.
+        assert!(a.intersection(&square(5.0, 5.0, 1.0)).is_empty());
+        let joined = a.union(&square(2.0, 0.0, 2.0));
+        assert_eq!(joined.rings.len(), 1);
+        assert!(close(joined.area(), 8.0));
+        let framed = a.difference(&square(0.5, 0.5, 1.0));
+        assert_eq!(framed.rings.len(), 2);
+        assert!(close(framed.area(), 3.0));
+        assert!(!framed.contains(pt2(1.0, 1.0)) && framed.contains(pt2(0.25, 1.0)));
+        assert!(framed.rings.iter().any(|r| signed_area(r) < 0.0));
+        // Holes take part too.
+        let refilled = framed.union(&square(0.5, 0.5, 1.0));
+        assert_eq!(refilled.rings.len(), 1);
+        assert!(close(refilled.area(), 4.0));
+    }
+
+    #[test]
+    fn even_odd() {
+        let p = Polygon::even_odd(vec![
+            square(0.0, 0.0, 4.0).rings[0].clone(),
+            square(1.0, 1.0, 1.0).rings[0].clone(),
+            square(1.5, 1.5, 2.0).rings[0].clone(),
+        ]);
+        // The two inner squares overlap by a quarter, which is filled again.
+        assert!(close(p.area(), 16.0 - 1.0 - 4.0 + 2.0 * 0.25));
+        assert!(p.contains(pt2(1.75, 1.75)));
+    }
+
+    #[test]
+    fn offset() {
+        let a = square(0.0, 0.0, 10.0);
+        let grown = a.offset(1.0, Join::Round);
+        // Round corners are within a tenth of the arc.
+        assert!(grown.area() < 140.0 + PI && grown.area() > 140.0 + PI * 0.9 * 0.9);
+        assert!(grown.contains(pt2(-0.65, -0.65)) && !grown.contains(pt2(-0.75, -0.75)));
+        assert!(close(a.offset(1.0, Join::Miter).area(), 144.0));
+        assert!(close(a.offset(1.0, Join::Bevel).area(), 144.0 - 2.0));
+        assert!(close(a.offset(-1.0, Join::Round).area(), 64.0));
+        assert!(a.offset(-6.0, Join::Round).is_empty());
+        // An L, whose inside corner has to come out clean.
+        let l = Polygon::new(vec![
+            pt2(0.0, 0.0),
+            pt2(4.0, 0.0),
+            pt2(4.0, 1.0),
+            pt2(1.0, 1.0),
+            pt2(1.0, 4.0),
+            pt2(0.0, 4.0),
+        ]);
+        let thick = l.offset(0.5, Join::Miter);
+        assert_eq!(thick.rings.len(), 1);
+        assert!(close(thick.area(), 5.0 * 2.0 + 2.0 * 3.0));
+        // A frame shrinks from both sides.
+        let frame = a.difference(&square(2.0, 2.0, 6.0));
+        assert!(close(frame.offset(-0.5, Join::Miter).area(), 81.0 - 49.0));
+    }
+
+    #[test]
+    fn clip_polylines() {
+        let frame = square(0.0, 0.0, 4.0).difference(&square(1.0, 1.0, 2.0));
+        let line = vec![pt2(-1.0, 2.0), pt2(5.0, 2.0)];
+        let inside = frame.clip(&line);
+        assert_eq!(inside.len(), 2);
+        assert!(close(inside[0][0].x, 0.0) && close(inside[0][1].x, 1.0));
+        assert!(close(inside[1][0].x, 3.0) && close(inside[1][1].x, 4.0));
+        assert_eq!(frame.clip_outside(&line).len(), 3);
+        // Bends inside stay in one piece.
+        let bent = vec![pt2(0.5, -1.0), pt2(0.5, 0.5), pt2(3.5, 0.5), pt2(3.5, -1.0)];
+        let pieces = frame.clip(&bent);
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].len(), 4);
+    }
+}
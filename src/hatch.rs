@@ -0,0 +1,355 @@
+// Hatching turns filled regions into lines a pen plotter can draw, since
+// pens don't fill. Lines are clipped to the region so holes stay empty.
+//
+// With a tone the spacing follows how dark the fill is, lines at the set
+// spacing for black and further apart for lighter colours, so that the ink
+// on the paper matches the fill and colour work becomes tonal line work.
+// Straight hatching lies on lines through the origin, so neighbouring shapes
+// hatched alike line up.
+
+use nannou::color::{IntoLinSrgba, LinSrgba};
+use nannou::noise::{NoiseFn, Perlin, Seedable};
+use nannou::prelude::*;
+
+use crate::canvas::{Canvas, Join};
+use crate::geometry::Polygon;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pattern {
+    /// Straight lines at the hatch angle.
+    Parallel,
+    /// Parallel lines crossed by a second set at right angles.
+    Cross,
+    /// The outline of the region shrunk again and again.
+    Concentric,
+    /// An Archimedean spiral out from the middle of the region.
+    Spiral,
+    /// Parallel lines pushed sideways by Perlin noise up to `amplitude`,
+    /// with bumps about `wavelength` apart.
+    Noise { amplitude: f32, wavelength: f32 },
+}
+
+pub struct Hatch {
+    pub pattern: Pattern,
+    /// The gap between lines, for black when there is a tone.
+    pub spacing: f32,
+    /// The direction of straight lines in radians, and the turn of spirals.
+    pub angle: f32,
+    pub weight: f32,
+    /// Seed for the noise pattern.
+    pub seed: u32,
+    lightest: Option<f32>,
+    pen: Option<LinSrgba>,
+}
+
+// Perceptual lightness from 0 for black to 1 for white, CIE L*.
+fn lightness(c: LinSrgba) -> f32 {
+    let y = 0.2126 * c.red + 0.7152 * c.green + 0.0722 * c.blue;
+    let l = if y > 0.008_856 {
+        116.0 * y.cbrt() - 16.0
+    } else {
+        903.3 * y
+    };
+    (l / 100.0).clamp(0.0, 1.0)
+}
+
+// The extent of the region across and along lines in direction `d`, for
+// `d` along the x axis the bottom, top, left and right.
+fn extent(region: &Polygon, d: Vector2) -> (f32, f32, f32, f32) {
+    let n = vec2(-d.y, d.x);
+    let mut e = (f32::MAX, f32::MIN, f32::MAX, f32::MIN);
+    for &p in region.rings.iter().flatten() {
+        let (t, u) = (p.dot(n), p.dot(d));
+        e = (e.0.min(t), e.1.max(t), e.2.min(u), e.3.max(u));
+    }
+    e
+}
+
+// Runs go back and forth so the pen doesn't travel back across each time.
+fn boustrophedon(lines: Vec<Vec<Vec<Point2>>>) -> Vec<Vec<Point2>> {
+    lines
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, mut runs)| {
+            if i % 2 == 1 {
+                runs.reverse();
+                for run in runs.iter_mut() {
+                    run.reverse();
+                }
+            }
+            runs
+        })
+        .collect()
+}
+
+impl Hatch {
+    /// Black lines 5 apart at 45 degrees.
+    pub fn new(pattern: Pattern) -> Self {
+        Hatch {
+            pattern,
+            spacing: 5.0,
+            angle: PI / 4.0,
+            weight: 1.0,
+            seed: 0,
+            lightest: None,
+            pen: None,
+        }
+    }
+
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        assert!(spacing > 0.0, "spacing must be positive");
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Space lines by the lightness of the fill, `spacing` apart for black
+    /// and further apart for lighter colours, leaving fills blank that would
+    /// need lines more than `lightest` apart.
+    pub fn tone(mut self, lightest: f32) -> Self {
+        self.lightest = Some(lightest);
+        self
+    }
+
+    /// Draw every line in `color` rather than the colour of the fill.
+    pub fn pen<C: IntoLinSrgba<f32>>(mut self, color: C) -> Self {
+        self.pen = Some(color.into_lin_srgba());
+        self
+    }
+
+    /// The gap between lines for a fill of `color`, `None` when it is too
+    /// light to hatch.
+    pub fn spacing_for(&self, color: LinSrgba) -> Option<f32> {
+        let lightest = match self.lightest {
+            Some(lightest) => lightest,
+            None => return Some(self.spacing),
+        };
+        let darkness = (1.0 - lightness(color)) * color.alpha;
+        let spacing = self.spacing / darkness;
+        if darkness > 0.0 && spacing <= lightest {
+            Some(spacing)
+        } else {
+            None
+        }
+    }
+
+    /// Lines filling `region` at the set spacing.
+    pub fn lines(&self, region: &Polygon) -> Vec<Vec<Point2>> {
+        self.lines_at(region, self.spacing)
+    }
+
+    fn lines_at(&self, region: &Polygon, spacing: f32) -> Vec<Vec<Point2>> {
+        if region.is_empty() {
+            return vec![];
+        }
+        match self.pattern {
+            Pattern::Parallel => self.parallel(region, spacing, self.angle, 0.0, 0.0),
+            Pattern::Cross => {
+                let mut lines = self.parallel(region, spacing, self.angle, 0.0, 0.0);
+                lines.extend(self.parallel(region, spacing, self.angle + PI / 2.0, 0.0, 0.0));
+                lines
+            }
+            Pattern::Concentric => self.concentric(region, spacing),
+            Pattern::Spiral => self.spiral(region, spacing),
+            Pattern::Noise {
+                amplitude,
+                wavelength,
+            } => self.parallel(region, spacing, self.angle, amplitude, wavelength),
+        }
+    }
+
+    // Straight lines, or wavy ones with an amplitude.
+    fn parallel(
+        &self,
+        region: &Polygon,
+        spacing: f32,
+        angle: f32,
+        amplitude: f32,
+        wavelength: f32,
+    ) -> Vec<Vec<Point2>> {
+        let d = vec2(angle.cos(), angle.sin());
+        let n = vec2(-d.y, d.x);
+        let (lo, hi, start, end) = extent(region, d);
+        let (lo, hi) = (lo - amplitude, hi + amplitude);
+        // Start and end outside the region.
+        let (start, end) = (start - 1.0, end + 1.0);
+        let noise = Perlin::new().set_seed(self.seed);
+        let step = (wavelength / 8.0).max(0.5);
+        let mut lines = vec![];
+        let mut k = (lo / spacing).ceil();
+        while k * spacing <= hi {
+            let t = k * spacing;
+            let line: Vec<Point2> = if amplitude == 0.0 {
+                vec![n * t + d * start, n * t + d * end]
+            } else {
+                let steps = ((end - start) / step).ceil() as usize;
+                (0..=steps)
+                    .map(|i| {
+                        let p = n * t + d * (start + i as f32 * step);
+                        let (x, y) = (p.x / wavelength, p.y / wavelength);
+                        p + n * amplitude * noise.get([x as f64, y as f64]) as f32
+                    })
+                    .collect()
+            };
+            lines.push(region.clip(&line));
+            k += 1.0;
+        }
+        boustrophedon(lines)
+    }
+
+    fn concentric(&self, region: &Polygon, spacing: f32) -> Vec<Vec<Point2>> {
+        let (bottom, top, left, right) = extent(region, vec2(1.0, 0.0));
+        let rings = ((right - left).min(top - bottom) / spacing).ceil() as usize;
+        let mut lines = vec![];
+        for k in 0..rings {
+            let inner = region.offset(-spacing * (k as f32 + 0.5), Join::Miter);
+            if inner.is_empty() {
+                break;
+            }
+            lines.extend(inner.outlines());
+        }
+        lines
+    }
+
+    fn spiral(&self, region: &Polygon, spacing: f32) -> Vec<Vec<Point2>> {
+        let (bottom, top, left, right) = extent(region, vec2(1.0, 0.0));
+        let center = pt2((left + right) / 2.0, (bottom + top) / 2.0);
+        let reach = region
+            .rings
+            .iter()
+            .flatten()
+            .map(|p| p.distance(center))
+            .fold(0.0, f32::max)
+            + spacing;
+        let mut spiral = vec![];
+        let mut theta: f32 = 0.0;
+        loop {
+            let r = spacing * theta / TAU;
+            let a = theta + self.angle;
+            spiral.push(center + vec2(a.cos(), a.sin()) * r);
+            if r > reach {
+                break;
+            }
+            // About a quarter of the spacing along the curve each step.
+            theta += spacing / 4.0 / r.max(spacing);
+        }
+        region.clip(&spiral)
+    }
+
+    /// A copy of `canvas` for a pen plotter, with masks applied, strokes kept
+    /// and fills replaced by hatching in the fill colour. Shapes don't hide
+    /// the hatching of shapes under them.
+    pub fn canvas(&self, canvas: &Canvas) -> Canvas {
+        let canvas = canvas.clip_masks();
+        let mut hatched = Canvas::new(canvas.width, canvas.height);
+        if let Some(bg) = canvas.background_color() {
+            hatched.background(bg);
+        }
+        for shape in canvas.shapes() {
+            if let Some(fill) = shape.fill {
+                if let Some(spacing) = self.spacing_for(fill) {
+                    let color = self.pen.unwrap_or(LinSrgba { alpha: 1.0, ..fill });
+                    for line in self.lines_at(&shape.region(), spacing) {
+                        hatched.polyline(line).color(color).weight(self.weight);
+                    }
+                }
+            }
+            if shape.stroke.is_some() {
+                let mut outline = shape.clone();
+                outline.no_fill();
+                hatched.add(outline);
+            }
+        }
+        hatched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_lines_fill_the_region_around_holes() {
+        let region = Polygon::rect(Rect::from_w_h(19.0, 19.0))
+            .hole(Polygon::rect(Rect::from_w_h(10.0, 10.0)).rings.remove(0));
+        let lines = Hatch::new(Pattern::Parallel)
+            .spacing(2.0)
+            .angle(0.0)
+            .lines(&region);
+        // Lines at y = -8, -6, .., 8, those through the hole split in two.
+        assert_eq!(lines.len(), 9 + 5);
+        for line in &lines {
+            assert!(line.iter().all(|p| p.x.abs() <= 9.5 + 1e-4));
+            let mid = (line[0] + line[line.len() - 1]) / 2.0;
+            assert!(region.contains(mid));
+        }
+        // Every other line runs the other way.
+        assert!(lines[0][0].x < lines[1][0].x);
+    }
+
+    #[test]
+    fn patterns_stay_inside() {
+        let region = Polygon::circle(pt2(30.0, 0.0), 15.0, 60);
+        for pattern in [
+            Pattern::Cross,
+            Pattern::Concentric,
+            Pattern::Spiral,
+            Pattern::Noise {
+                amplitude: 3.0,
+                wavelength: 20.0,
+            },
+        ]
+        .iter()
+        {
+            let lines = Hatch::new(*pattern).spacing(3.0).lines(&region);
+            assert!(!lines.is_empty(), "{:?}", pattern);
+            for p in lines.iter().flatten() {
+                assert!(p.distance(pt2(30.0, 0.0)) < 15.0 + 1e-3, "{:?}", pattern);
+            }
+        }
+        let rings = Hatch::new(Pattern::Concentric).spacing(3.0).lines(&region);
+        assert_eq!(rings.len(), 5);
+    }
+
+    #[test]
+    fn tone_spaces_lines_by_lightness() {
+        let hatch = Hatch::new(Pattern::Parallel).spacing(2.0).tone(20.0);
+        assert_eq!(hatch.spacing_for(BLACK.into_lin_srgba()), Some(2.0));
+        assert_eq!(hatch.spacing_for(WHITE.into_lin_srgba()), None);
+        let grey = hatch.spacing_for(GREY.into_lin_srgba()).unwrap();
+        let dark = hatch.spacing_for(DIMGREY.into_lin_srgba()).unwrap();
+        assert!(2.0 < dark && dark < grey && grey < 20.0);
+    }
+
+    #[test]
+    fn canvas_fills_become_lines() {
+        let mut canvas = Canvas::new(100.0, 100.0);
+        canvas
+            .rect(pt2(0.0, 0.0), 20.0, 20.0)
+            .color(RED)
+            .stroke(BLACK);
+        canvas.ellipse(pt2(0.0, 0.0), 20.0, 20.0).color(WHITE);
+        let hatched = Hatch::new(Pattern::Parallel)
+            .tone(20.0)
+            .pen(BLACK)
+            .canvas(&canvas);
+        assert!(hatched.shapes().iter().all(|s| s.fill.is_none()));
+        let outline = hatched.shapes().last().unwrap();
+        assert_eq!(outline.segments, canvas.shapes()[0].segments);
+        assert!(hatched.shapes().len() > 2);
+    }
+}
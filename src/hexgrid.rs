@@ -0,0 +1,507 @@
+// Hexagonal grids. Hexes are addressed with axial coordinates `(q, r)`, the
+// third cube coordinate `s = -q - r` is implied, and a `Layout` places them on
+// the canvas with either flat or pointy tops. Offset coordinates are only for
+// converting to and from rows and columns. See Amit Patel's guide at
+// https://www.redblobgames.com/grids/hexagons/ for the details.
+
+use nannou::geom::Rect;
+use nannou::prelude::*;
+use std::collections::BTreeMap;
+use std::ops::{Add, Index, IndexMut, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// The six neighbours' offsets, anti-clockwise on the canvas starting from
+/// `+q`.
+pub const DIRECTIONS: [Hex; 6] = [
+    Hex { q: 1, r: 0 },
+    Hex { q: 0, r: 1 },
+    Hex { q: -1, r: 1 },
+    Hex { q: -1, r: 0 },
+    Hex { q: 0, r: -1 },
+    Hex { q: 1, r: -1 },
+];
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    pub fn new(q: i32, r: i32) -> Self {
+        Hex { q, r }
+    }
+
+    /// From cube coordinates, which must add up to zero.
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Self {
+        assert_eq!(q + r + s, 0, "cube coordinates must add up to zero");
+        Hex { q, r }
+    }
+
+    pub fn s(self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn scale(self, k: i32) -> Hex {
+        Hex::new(self.q * k, self.r * k)
+    }
+
+    pub fn neighbor(self, direction: usize) -> Hex {
+        self + DIRECTIONS[direction % 6]
+    }
+
+    pub fn neighbors(self) -> [Hex; 6] {
+        let mut ns = [self; 6];
+        for (n, d) in ns.iter_mut().zip(DIRECTIONS.iter()) {
+            *n = *n + *d;
+        }
+        ns
+    }
+
+    /// Steps from the origin.
+    pub fn length(self) -> i32 {
+        (self.q.abs() + self.r.abs() + self.s().abs()) / 2
+    }
+
+    pub fn distance(self, other: Hex) -> i32 {
+        (self - other).length()
+    }
+
+    /// Turn 60 degrees anti-clockwise about the origin.
+    pub fn rotate_left(self) -> Hex {
+        Hex::new(-self.r, -self.s())
+    }
+
+    /// Turn 60 degrees clockwise about the origin.
+    pub fn rotate_right(self) -> Hex {
+        Hex::new(-self.s(), -self.q)
+    }
+
+    /// The hex containing fractional axial coordinates.
+    pub fn round(q: f32, r: f32) -> Hex {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Hex::new(rq as i32, rr as i32)
+    }
+
+    /// The hexes `radius` steps away, in order around the ring.
+    pub fn ring(self, radius: i32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+        let mut hexes = vec![];
+        let mut hex = self + DIRECTIONS[4].scale(radius);
+        for d in DIRECTIONS.iter() {
+            for _ in 0..radius {
+                hexes.push(hex);
+                hex = hex + *d;
+            }
+        }
+        hexes
+    }
+
+    /// Every hex within `radius` steps, ring by ring outwards.
+    pub fn spiral(self, radius: i32) -> Vec<Hex> {
+        (0..=radius).flat_map(|k| self.ring(k)).collect()
+    }
+
+    /// The hexes a straight line to `other` passes through, both ends
+    /// included.
+    pub fn line(self, other: Hex) -> Vec<Hex> {
+        let n = self.distance(other);
+        // Nudge off the edges between hexes so ties round the same way.
+        let (q0, r0) = (self.q as f32 + 1e-6, self.r as f32 + 1e-6);
+        let (q1, r1) = (other.q as f32 + 1e-6, other.r as f32 + 1e-6);
+        (0..=n)
+            .map(|i| {
+                let t = if n == 0 { 0.0 } else { i as f32 / n as f32 };
+                Hex::round(q0 + (q1 - q0) * t, r0 + (r1 - r0) * t)
+            })
+            .collect()
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+/// Offset coordinates shove every other column (`Q`) or row (`R`) by half a
+/// hex, the odd ones or the even ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Offset {
+    OddQ,
+    EvenQ,
+    OddR,
+    EvenR,
+}
+
+impl Hex {
+    /// `(col, row)` in `offset` coordinates.
+    pub fn to_offset(self, offset: Offset) -> (i32, i32) {
+        match offset {
+            Offset::OddQ => (self.q, self.r + (self.q - (self.q & 1)) / 2),
+            Offset::EvenQ => (self.q, self.r + (self.q + (self.q & 1)) / 2),
+            Offset::OddR => (self.q + (self.r - (self.r & 1)) / 2, self.r),
+            Offset::EvenR => (self.q + (self.r + (self.r & 1)) / 2, self.r),
+        }
+    }
+
+    pub fn from_offset(offset: Offset, col: i32, row: i32) -> Hex {
+        match offset {
+            Offset::OddQ => Hex::new(col, row - (col - (col & 1)) / 2),
+            Offset::EvenQ => Hex::new(col, row - (col + (col & 1)) / 2),
+            Offset::OddR => Hex::new(col - (row - (row & 1)) / 2, row),
+            Offset::EvenR => Hex::new(col - (row + (row & 1)) / 2, row),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Columns of hexes with a flat edge at the top, `q` points right.
+    Flat,
+    /// Rows of hexes with a corner at the top, `q` points right.
+    Pointy,
+}
+
+/// Where hexes go on the canvas, `size` is the distance from a hex's centre
+/// to its corners and `origin` the centre of `Hex::ORIGIN`. `r` increases up
+/// the canvas.
+#[derive(Debug, Clone, Copy)]
+pub struct Layout {
+    pub orientation: Orientation,
+    pub size: f32,
+    pub origin: Point2,
+}
+
+const SQRT_3: f32 = 1.732_050_8;
+
+impl Layout {
+    pub fn new(orientation: Orientation, size: f32, origin: Point2) -> Self {
+        Layout {
+            orientation,
+            size,
+            origin,
+        }
+    }
+
+    /// Flat topped hexes over `bounds`, `Hex::ORIGIN` centred on the bottom
+    /// edge with its upper and lower left corners on the left edge.
+    pub fn flat_in(size: f32, bounds: Rect) -> Self {
+        Layout::new(
+            Orientation::Flat,
+            size,
+            pt2(bounds.left() + size / 2.0, bounds.bottom()),
+        )
+    }
+
+    /// The distance between the centres of neighbouring columns and rows.
+    pub fn spacing(&self) -> Vector2 {
+        match self.orientation {
+            Orientation::Flat => vec2(1.5 * self.size, SQRT_3 * self.size),
+            Orientation::Pointy => vec2(SQRT_3 * self.size, 1.5 * self.size),
+        }
+    }
+
+    pub fn center(&self, hex: Hex) -> Point2 {
+        let (q, r) = (hex.q as f32, hex.r as f32);
+        let p = match self.orientation {
+            Orientation::Flat => vec2(1.5 * q, SQRT_3 * (r + q / 2.0)),
+            Orientation::Pointy => vec2(SQRT_3 * (q + r / 2.0), 1.5 * r),
+        };
+        self.origin + p * self.size
+    }
+
+    /// The hex containing `p`.
+    pub fn hex_at(&self, p: Point2) -> Hex {
+        let p = (p - self.origin) / self.size;
+        match self.orientation {
+            Orientation::Flat => {
+                let q = p.x * 2.0 / 3.0;
+                Hex::round(q, p.y / SQRT_3 - q / 2.0)
+            }
+            Orientation::Pointy => {
+                let r = p.y * 2.0 / 3.0;
+                Hex::round(p.x / SQRT_3 - r / 2.0, r)
+            }
+        }
+    }
+
+    /// The angle from a hex's centre to its first corner.
+    fn corner_angle(&self) -> f32 {
+        match self.orientation {
+            Orientation::Flat => 0.0,
+            Orientation::Pointy => PI / 6.0,
+        }
+    }
+
+    /// Corners anti-clockwise, the first to the right.
+    pub fn corners(&self, hex: Hex) -> Vec<Point2> {
+        let c = self.center(hex);
+        (0..6)
+            .map(|i| {
+                let a = self.corner_angle() + i as f32 * PI / 3.0;
+                c + vec2(a.cos(), a.sin()) * self.size
+            })
+            .collect()
+    }
+
+    /// Every hex that overlaps `rect`, column by column for flat tops and row
+    /// by row for pointy ones.
+    pub fn hexes_in(&self, rect: Rect) -> Vec<Hex> {
+        let s = self.spacing();
+        let reach = rect.pad(-self.size);
+        let mut hexes = vec![];
+        let left = (reach.left() - self.origin.x) / s.x;
+        let right = (reach.right() - self.origin.x) / s.x;
+        let bottom = (reach.bottom() - self.origin.y) / s.y;
+        let top = (reach.top() - self.origin.y) / s.y;
+        match self.orientation {
+            Orientation::Flat => {
+                for q in left.floor() as i32..=right.ceil() as i32 {
+                    let shift = q as f32 / 2.0;
+                    for r in (bottom - shift).floor() as i32..=(top - shift).ceil() as i32 {
+                        hexes.push(Hex::new(q, r));
+                    }
+                }
+            }
+            Orientation::Pointy => {
+                for r in bottom.floor() as i32..=top.ceil() as i32 {
+                    let shift = r as f32 / 2.0;
+                    for q in (left - shift).floor() as i32..=(right - shift).ceil() as i32 {
+                        hexes.push(Hex::new(q, r));
+                    }
+                }
+            }
+        }
+        hexes.retain(|h| reach.contains(self.center(*h)));
+        hexes
+    }
+}
+
+/// Values stored by hex, iterated in a fixed order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HexMap<T> {
+    cells: BTreeMap<Hex, T>,
+}
+
+impl<T> HexMap<T> {
+    pub fn new() -> Self {
+        HexMap {
+            cells: BTreeMap::new(),
+        }
+    }
+
+    /// A map holding `f(hex)` for each of `hexes`.
+    pub fn from_fn(hexes: &[Hex], mut f: impl FnMut(Hex) -> T) -> Self {
+        HexMap {
+            cells: hexes.iter().map(|h| (*h, f(*h))).collect(),
+        }
+    }
+
+    pub fn insert(&mut self, hex: Hex, value: T) -> Option<T> {
+        self.cells.insert(hex, value)
+    }
+
+    pub fn get(&self, hex: Hex) -> Option<&T> {
+        self.cells.get(&hex)
+    }
+
+    pub fn get_mut(&mut self, hex: Hex) -> Option<&mut T> {
+        self.cells.get_mut(&hex)
+    }
+
+    pub fn contains(&self, hex: Hex) -> bool {
+        self.cells.contains_key(&hex)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Hex, &T)> {
+        self.cells.iter().map(|(h, v)| (*h, v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Hex, &mut T)> {
+        self.cells.iter_mut().map(|(h, v)| (*h, v))
+    }
+
+    pub fn hexes(&self) -> impl Iterator<Item = Hex> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The neighbours of `hex` that are in the map.
+    pub fn neighbors(&self, hex: Hex) -> Vec<(Hex, &T)> {
+        hex.neighbors()
+            .iter()
+            .filter_map(|n| self.cells.get(n).map(|v| (*n, v)))
+            .collect()
+    }
+}
+
+impl<T> Default for HexMap<T> {
+    fn default() -> Self {
+        HexMap::new()
+    }
+}
+
+impl<T> Index<Hex> for HexMap<T> {
+    type Output = T;
+
+    fn index(&self, hex: Hex) -> &T {
+        self.cells
+            .get(&hex)
+            .unwrap_or_else(|| panic!("no value for {:?}", hex))
+    }
+}
+
+impl<T> IndexMut<Hex> for HexMap<T> {
+    fn index_mut(&mut self, hex: Hex) -> &mut T {
+        self.cells
+            .get_mut(&hex)
+            .unwrap_or_else(|| panic!("no value for {:?}", hex))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rings_and_spirals() {
+        let c = Hex::new(2, -1);
+        for k in 0..5 {
+            let ring = c.ring(k);
+            assert_eq!(ring.len(), if k == 0 { 1 } else { 6 * k as usize });
+            assert!(ring.iter().all(|h| h.distance(c) == k));
+            for (a, b) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+                assert!(k < 2 || a.distance(*b) == 1);
+            }
+        }
+        let spiral = c.spiral(3);
+        assert_eq!(spiral.len(), 1 + 3 * 3 * 4);
+        assert_eq!(spiral[0], c);
+        assert!(c.neighbors().iter().all(|n| n.distance(c) == 1));
+    }
+
+    #[test]
+    fn rotations() {
+        let h = Hex::new(3, -1);
+        let mut r = h;
+        for _ in 0..6 {
+            r = r.rotate_left();
+            assert_eq!(r.length(), h.length());
+        }
+        assert_eq!(r, h);
+        assert_eq!(h.rotate_left().rotate_right(), h);
+        assert_eq!(DIRECTIONS[0].rotate_left(), DIRECTIONS[1]);
+        assert_eq!(Hex::from_cube(1, 2, -3).s(), -3);
+    }
+
+    #[test]
+    fn lines_are_connected() {
+        let a = Hex::new(-3, 1);
+        let b = Hex::new(4, -5);
+        let line = a.line(b);
+        assert_eq!(line.len() as i32, a.distance(b) + 1);
+        assert_eq!((line[0], *line.last().unwrap()), (a, b));
+        assert!(line.windows(2).all(|w| w[0].distance(w[1]) == 1));
+        assert_eq!(a.line(a), vec![a]);
+    }
+
+    #[test]
+    fn offsets_round_trip() {
+        for offset in [Offset::OddQ, Offset::EvenQ, Offset::OddR, Offset::EvenR].iter() {
+            for h in Hex::ORIGIN.spiral(4) {
+                let (col, row) = h.to_offset(*offset);
+                assert_eq!(Hex::from_offset(*offset, col, row), h, "{:?}", offset);
+            }
+        }
+        assert_eq!(Hex::new(1, 0).to_offset(Offset::OddQ), (1, 0));
+        assert_eq!(Hex::new(1, 0).to_offset(Offset::EvenQ), (1, 1));
+    }
+
+    #[test]
+    fn flat_in_puts_a_corner_on_the_edge() {
+        let bounds = Rect::from_x_y_w_h(10.0, 5.0, 200.0, 100.0);
+        let layout = Layout::flat_in(8.0, bounds);
+        let corners = layout.corners(Hex::ORIGIN);
+        assert!((corners[2].x - bounds.left()).abs() < 1e-4);
+        assert!((corners[4].x - bounds.left()).abs() < 1e-4);
+        assert_eq!(layout.center(Hex::ORIGIN).y, bounds.bottom());
+    }
+
+    #[test]
+    fn pixels_round_trip() {
+        for orientation in [Orientation::Flat, Orientation::Pointy].iter() {
+            let layout = Layout::new(*orientation, 20.0, pt2(13.0, -7.0));
+            for h in Hex::ORIGIN.spiral(5) {
+                let c = layout.center(h);
+                assert_eq!(layout.hex_at(c), h);
+                for p in layout.corners(h) {
+                    assert!((p.distance(c) - 20.0).abs() < 1e-3);
+                    // Just inside a corner is still the same hex.
+                    assert_eq!(layout.hex_at(c + (p - c) * 0.95), h);
+                }
+                for (d, n) in h.neighbors().iter().enumerate() {
+                    let a = (layout.center(*n) - c).angle();
+                    let expected = PI / 6.0 - layout.corner_angle() + d as f32 * PI / 3.0;
+                    assert!((a - expected).sin().abs() < 1e-3 && (a - expected).cos() > 0.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hexes_cover_rect() {
+        for orientation in [Orientation::Flat, Orientation::Pointy].iter() {
+            let layout = Layout::new(*orientation, 15.0, pt2(-40.0, 3.0));
+            let rect = Rect::from_w_h(300.0, 200.0);
+            let hexes = layout.hexes_in(rect);
+            let map = HexMap::from_fn(&hexes, |h| h.q);
+            assert_eq!(map.len(), hexes.len());
+            for x in (-150..=150).step_by(10) {
+                for y in (-100..=100).step_by(10) {
+                    assert!(map.contains(layout.hex_at(pt2(x as f32, y as f32))));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hex_map() {
+        let mut map = HexMap::new();
+        for h in Hex::ORIGIN.spiral(1) {
+            map.insert(h, h.length());
+        }
+        map[Hex::ORIGIN] = 10;
+        assert_eq!(map[Hex::ORIGIN], 10);
+        assert_eq!(map.neighbors(Hex::new(1, 0)).len(), 3);
+        assert_eq!(map.get(Hex::new(5, 5)), None);
+        assert_eq!(map.iter().map(|(_, v)| *v).sum::<i32>(), 16);
+    }
+}
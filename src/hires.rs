@@ -1,18 +1,26 @@
-use getopts::Options;
+// Render a canvas far larger than the screen to a gpu texture. The window
+// shows the texture scaled down to a tenth of its size, and `-p` saves the full
+// resolution image to `<capture_directory>/image.png`.
+
+use lazy_static::lazy_static;
 use nannou::prelude::*;
-use std::env;
+use std::sync::RwLock;
 
+use crate::canvas::Canvas;
+use crate::{capture_directory, cli};
 
-fn scene(draw: &Draw, _w: u32, _h: u32) {
-    draw.background().color(BLACK);
-    draw.ellipse().color(ORANGE).w_h(1000.0, 1000.0);
+lazy_static! {
+    static ref SCENE: RwLock<Option<fn() -> Canvas>> = RwLock::new(None);
 }
 
-fn main() {
+/// Run a sketch that draws `scene` once at the canvas's full size.
+pub fn run(scene: fn() -> Canvas) {
+    *SCENE.write().unwrap() = Some(scene);
     nannou::app(model).update(update).exit(exit).run();
 }
 
 struct Model {
+    canvas: Canvas,
     texture: wgpu::Texture,
     draw: nannou::Draw,
     renderer: nannou::draw::Renderer,
@@ -21,7 +29,9 @@ struct Model {
 }
 
 fn model(app: &App) -> Model {
-    let texture_size = [6_000, 6_000];
+    let scene = SCENE.read().unwrap().expect("no hi-res scene to draw");
+    let canvas = scene();
+    let texture_size = [canvas.width as u32, canvas.height as u32];
 
     let [win_w, win_h] = [texture_size[0] / 10, texture_size[1] / 10];
     let w_id = app
@@ -62,9 +72,10 @@ fn model(app: &App) -> Model {
         dst_format,
     );
 
-    std::fs::create_dir_all(&capture_directory(app)).unwrap();
+    std::fs::create_dir_all(capture_directory(app)).unwrap();
 
     Model {
+        canvas,
         texture,
         draw,
         renderer,
@@ -74,14 +85,7 @@ fn model(app: &App) -> Model {
 }
 
 fn update(app: &App, model: &mut Model, _update: Update) {
-    let args: Vec<String> = env::args().collect();
-    let mut opts = Options::new();
-    opts.optflag("p", "png", "save frames to file as png.");
-    let matches = match opts.parse(&args[1..]) {
-        Ok(m) => m,
-        Err(f) => panic!(f.to_string()),
-    };
-    let png = matches.opt_present("p");
+    let png = cli::png();
 
     let elapsed_frames = app.main_window().elapsed_frames();
     if elapsed_frames > 0 {
@@ -90,10 +94,7 @@ fn update(app: &App, model: &mut Model, _update: Update) {
 
     let draw = &model.draw;
     draw.reset();
-
-    let [w, h] = model.texture.size();
-
-    scene(draw, w, h);
+    model.canvas.draw(draw);
 
     let window = app.main_window();
     let device = window.swap_chain_device();
@@ -113,9 +114,7 @@ fn update(app: &App, model: &mut Model, _update: Update) {
     window.swap_chain_queue().submit(&[encoder.finish()]);
 
     if png {
-        let path = capture_directory(app)
-            .join("image")
-            .with_extension("png");
+        let path = capture_directory(app).join("image").with_extension("png");
         snapshot
             .read(move |result| {
                 let image = result.expect("failed to map texture memory");
@@ -132,7 +131,7 @@ fn view(_app: &App, model: &Model, frame: Frame) {
     let mut encoder = frame.command_encoder();
     model
         .texture_reshaper
-        .encode_render_pass(frame.texture_view(), &mut *encoder);
+        .encode_render_pass(frame.texture_view(), &mut encoder);
 }
 
 fn exit(app: &App, model: Model) {
@@ -141,13 +140,7 @@ fn exit(app: &App, model: Model) {
     let device = window.swap_chain_device();
     model
         .texture_capturer
-        .await_active_snapshots(&device)
+        .await_active_snapshots(device)
         .unwrap();
     println!("Done!");
 }
-
-fn capture_directory(app: &App) -> std::path::PathBuf {
-    app.project_path()
-        .expect("could not locate project_path")
-        .join(app.exe_name().unwrap())
-}
\ No newline at end of file
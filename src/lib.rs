@@ -1,30 +1,81 @@
+use nannou::color::white_point::D65;
 use nannou::color::{Alpha, IntoLinSrgba, Lab, Laba};
 use nannou::ease::cubic::ease_in_out;
 use nannou::math::{Basis2, Rad};
 use nannou::prelude::*;
-use nannou::{
-    color::white_point::D65,
-    draw::{primitive::Path, Drawing},
-};
+
+use arc::Arc;
+use canvas::{Canvas, Shape};
+use geometry::Polygon;
+use rng::random_range;
+
+pub mod arc;
+pub mod canvas;
+pub mod cli;
+pub mod curves;
+pub mod gallery;
+pub mod geometry;
+pub mod hatch;
+pub mod hexgrid;
+pub mod hires;
+pub mod lsystem;
+pub mod packing;
+pub mod particles;
+pub mod phyllotaxis;
+pub mod polyline;
+pub mod raster;
+pub mod rng;
+pub mod sampler;
+pub mod sampling;
+pub mod scaffold;
+pub mod sketch;
+pub mod spatial;
+pub mod stroke;
+pub mod symmetry;
+pub mod truchet;
+pub mod vector;
+pub mod wfc;
 
 // File path related functions  ------------------------------------------------
 
+// Sketches run through the launcher are named after the sketch, otherwise
+// after the executable.
+pub fn sketch_name(app: &App) -> String {
+    match cli::sketch_name() {
+        Some(name) => name.to_string(),
+        None => app.exe_name().unwrap(),
+    }
+}
+
 pub fn img_path(app: &App) -> std::path::PathBuf {
     app.project_path()
         .expect("failed to locate `project_path`")
         .join("img")
-        .join(format!("{}", app.exe_name().unwrap()))
+        .join(sketch_name(app))
         .with_extension("png")
 }
 
 pub fn gif_path(app: &App, frame: &Frame) -> std::path::PathBuf {
-    app.project_path()
-        .expect("failed to locate `project_path`")
-        .join(app.exe_name().unwrap())
+    capture_directory(app)
         .join(format!("frame_{:03}", frame.nth()))
         .with_extension("png")
 }
 
+pub fn capture_directory(app: &App) -> std::path::PathBuf {
+    app.project_path()
+        .expect("could not locate project_path")
+        .join(sketch_name(app))
+}
+
+/// `img/<name>.<extension>` in the project, for sketches that save their
+/// output without a window.
+pub fn output_path(name: &str, extension: &str) -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("img")
+        .join(name)
+        .with_extension(extension)
+}
+
 // -----------------------------------------------------------------------------
 
 pub fn clock(frame: u64) -> f32 {
@@ -60,9 +111,9 @@ pub fn random_rgba() -> LinSrgba {
 
 pub fn set_opacity(c: LinSrgba, o: f32) -> LinSrgba {
     srgba(
-        c.red as f32 / 255.,
-        c.green as f32 / 255.,
-        c.blue as f32 / 255.,
+        c.red / 255.,
+        c.green / 255.,
+        c.blue / 255.,
         o,
     )
     .into_lin_srgba()
@@ -78,7 +129,7 @@ pub fn with_opacity(c: nannou::color::Srgb<u8>, o: f32) -> nannou::color::rgb::S
     )
 }
 
-// This should no longer ge necessary now that nannou had transforms for the 
+// This should no longer ge necessary now that nannou had transforms for the
 // draw function.
 pub fn rotate_pt(p: Point2<f32>, turn: f32) -> Point2<f32> {
     let rad = Rad(turns_to_rad(turn));
@@ -89,62 +140,55 @@ pub fn rotate_pt(p: Point2<f32>, turn: f32) -> Point2<f32> {
 
 // -----------------------------------------------------------------------------
 
-pub fn circle_mask<T>(draw: &Draw, width: f32, height: f32, radius: f32, color: T)
+// Paint over everything outside a circle. `Canvas::with_mask` clips to the
+// circle instead, leaving what's underneath showing.
+pub fn circle_mask<T>(canvas: &mut Canvas, radius: f32, color: T)
 where
     T: IntoLinSrgba<f32>,
 {
-    use nannou::geom::path::Builder;
-    let mut builder = Builder::new();
-    let w2 = width / 2.;
-    let h2 = height / 2.;
-    builder = builder.move_to(pt2(-w2, h2));
-    builder = builder.line_to(pt2(w2, h2));
-    builder = builder.line_to(pt2(w2, -h2));
-    builder = builder.line_to(pt2(-w2, -h2));
-    builder = builder.move_to(pt2(0., -radius));
-    builder = builder.arc(pt2(0., 0.), vec2(radius, radius), TAU, 0.);
-    builder = builder.close();
-
-    let p = builder.build();
-
-    // draw arc
-    draw.path().fill().color(color).events(p.iter());
+    let sheet = Polygon::rect(canvas.bounds());
+    let hole = Polygon::circle(pt2(0., 0.), radius, 360);
+    canvas.path(sheet.difference(&hole).segments()).color(color);
+}
+
+/// Points along the arc from `start_deg` through `angle_deg` degrees,
+/// anticlockwise when positive, within a tenth of a pixel of it.
+pub fn arc_points(center: Point2, start_deg: f32, angle_deg: f32, radius: f32) -> Vec<Point2> {
+    Arc::degrees(center, radius, start_deg, angle_deg).points(0.1)
 }
 
+/// Stroke the arc from `start_deg` through `angle_deg` degrees with exact
+/// curves, see `arc::Arc` for elliptical arcs.
 pub fn arc<C>(
-    draw: &Draw,
+    canvas: &mut Canvas,
+    center: Point2,
     start_deg: f32,
     angle_deg: f32,
     radius: f32,
     color: C,
     weight: f32,
-) -> Drawing<Path<f32>, f32>
+) -> &mut Shape
 where
     C: IntoLinSrgba<f32>,
 {
-    // ) -> DrawingPath {
-    let start = start_deg as usize;
-    let end = start + angle_deg as usize;
-    let pts = (start..=end).map(|i| {
-        let theta = i as f32 / 360.0 * TAU;
-        pt2(radius * theta.cos(), radius * theta.sin())
-    });
-
-    draw.polyline()
+    let segments = Arc::degrees(center, radius, start_deg, angle_deg).segments();
+    canvas
+        .path(segments)
+        .no_fill()
+        .stroke(color)
         .join_round()
-        .color(color)
         .weight(weight)
-        .points(pts)
 }
 
-pub fn border(app: &App, draw: &Draw, width: f32) {
-    let (xy, wh) = app.window_rect().xy_wh();
-    draw.rect()
-        .xy(xy)
-        .wh(wh)
-        .color(srgba(0.0, 0.0, 0.0, 0.0))
+// A black frame over the edge of the canvas. Draw inside
+// `Canvas::with_mask(Mask::rect(..))` to keep the drawing off the margin.
+pub fn border(canvas: &mut Canvas, width: f32) {
+    let (w, h) = (canvas.width, canvas.height);
+    canvas
+        .rect(pt2(0.0, 0.0), w, h)
+        .no_fill()
         .stroke(BLACK)
-        .stroke_weight(width);
+        .weight(width);
 }
 
 // -----------------------------------------------------------------------------
@@ -219,7 +263,7 @@ where
         self.grid[row * m + col]
     }
 
-    pub fn iter<'a>(&'a self) -> GridIter<'a, T> {
+    pub fn iter(&self) -> GridIter<'_, T> {
         GridIter {
             grid: self,
             i: 0,
@@ -271,6 +315,8 @@ where
     }
 }
 
+/// Points on the curve `(f(t), g(t))` every `delta` in `t` up to `max`, see
+/// `sampling` for points spaced along the curve instead.
 pub fn gen_points(
     f: impl Fn(f32) -> f32,
     g: impl Fn(f32) -> f32,
@@ -0,0 +1,475 @@
+// Lindenmayer systems and a turtle to draw them. A word is a list of modules,
+// a symbol with optional parameters written `F(2.5)`. Rules rewrite every
+// module of a word at once, and the turtle turns the result into polylines
+// that can be added straight to a canvas.
+
+use nannou::color::IntoLinSrgba;
+use nannou::geom::Rect;
+use nannou::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::canvas::Canvas;
+use crate::rng::random_range;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module {
+    pub symbol: char,
+    pub params: Vec<f32>,
+}
+
+impl Module {
+    pub fn new(symbol: char, params: &[f32]) -> Self {
+        Module {
+            symbol,
+            params: params.to_vec(),
+        }
+    }
+
+    fn param(&self) -> Option<f32> {
+        self.params.first().copied()
+    }
+}
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol)?;
+        if !self.params.is_empty() {
+            let params: Vec<String> = self.params.iter().map(|p| p.to_string()).collect();
+            write!(f, "({})", params.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a word such as `F(1)[+(25.7)A(0.5,2)]`, spaces are ignored.
+pub fn parse(word: &str) -> Vec<Module> {
+    let mut modules = vec![];
+    let mut chars = word.chars().filter(|c| !c.is_whitespace()).peekable();
+    while let Some(symbol) = chars.next() {
+        let mut params = vec![];
+        if chars.peek() == Some(&'(') {
+            chars.next();
+            let mut list = String::new();
+            loop {
+                match chars.next() {
+                    Some(')') => break,
+                    Some(c) => list.push(c),
+                    None => panic!("unclosed parameter list in `{}`", word),
+                }
+            }
+            params = list
+                .split(',')
+                .map(|p| {
+                    p.parse()
+                        .unwrap_or_else(|_| panic!("invalid parameter `{}` in `{}`", p, word))
+                })
+                .collect();
+        }
+        modules.push(Module { symbol, params });
+    }
+    modules
+}
+
+pub fn to_string(word: &[Module]) -> String {
+    word.iter().map(|m| m.to_string()).collect()
+}
+
+/// Builds a successor from a module's parameters.
+type Successor = Box<dyn Fn(&[f32]) -> Option<Vec<Module>>>;
+
+enum Production {
+    Fixed(Vec<Module>),
+    /// Successors with their relative weights.
+    Stochastic(Vec<(f32, Vec<Module>)>),
+    /// Computes the successor from the module's parameters, `None` leaves the
+    /// module as it is.
+    Parametric(Successor),
+}
+
+pub struct LSystem {
+    pub axiom: Vec<Module>,
+    rules: HashMap<char, Production>,
+}
+
+impl LSystem {
+    pub fn new(axiom: &str) -> Self {
+        LSystem {
+            axiom: parse(axiom),
+            rules: HashMap::new(),
+        }
+    }
+
+    /// Replace every `symbol` with `successor`.
+    pub fn rule(mut self, symbol: char, successor: &str) -> Self {
+        self.rules
+            .insert(symbol, Production::Fixed(parse(successor)));
+        self
+    }
+
+    /// Replace every `symbol` with one of `successors` picked at random by
+    /// weight, independently for each module.
+    pub fn stochastic(mut self, symbol: char, successors: &[(f32, &str)]) -> Self {
+        let choices = successors.iter().map(|(w, s)| (*w, parse(s))).collect();
+        self.rules.insert(symbol, Production::Stochastic(choices));
+        self
+    }
+
+    /// Replace every `symbol` with the word `successor` builds from its
+    /// parameters, a rule whose condition isn't met returns `None`.
+    pub fn parametric<F>(mut self, symbol: char, successor: F) -> Self
+    where
+        F: Fn(&[f32]) -> Option<Vec<Module>> + 'static,
+    {
+        self.rules
+            .insert(symbol, Production::Parametric(Box::new(successor)));
+        self
+    }
+
+    /// Rewrite `word` once.
+    pub fn step(&self, word: &[Module]) -> Vec<Module> {
+        let mut next = vec![];
+        for m in word {
+            match self.rules.get(&m.symbol) {
+                Some(Production::Fixed(successor)) => next.extend_from_slice(successor),
+                Some(Production::Stochastic(choices)) => {
+                    let total: f32 = choices.iter().map(|(w, _)| w).sum();
+                    let mut pick = random_range(0.0, total);
+                    let mut chosen = &choices[choices.len() - 1].1;
+                    for (w, successor) in choices {
+                        if pick < *w {
+                            chosen = successor;
+                            break;
+                        }
+                        pick -= w;
+                    }
+                    next.extend_from_slice(chosen);
+                }
+                Some(Production::Parametric(f)) => match f(&m.params) {
+                    Some(successor) => next.extend(successor),
+                    None => next.push(m.clone()),
+                },
+                None => next.push(m.clone()),
+            }
+        }
+        next
+    }
+
+    /// The axiom rewritten `n` times.
+    pub fn iterate(&self, n: usize) -> Vec<Module> {
+        (0..n).fold(self.axiom.clone(), |word, _| self.step(&word))
+    }
+}
+
+/// A polyline drawn by the turtle, `depth` is how many branches deep it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub points: Vec<Point2>,
+    pub weight: f32,
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    pos: Point2,
+    heading: f32,
+    length: f32,
+    weight: f32,
+    depth: usize,
+}
+
+/// Interprets a word as turtle commands:
+///
+/// - `F`, `G` and any other symbols in `draws`: move forward drawing a line,
+///   by the module's parameter if it has one.
+/// - `f`: move forward without drawing.
+/// - `+` and `-`: turn left and right by `angle` degrees, or the parameter.
+/// - `|`: turn around.
+/// - `[` and `]`: start and end a branch. A branch's lines are `length_scale`
+///   times as long and `weight_scale` times as wide as its parent's.
+/// - `!`: set the line weight to the parameter, or scale it by
+///   `weight_scale`.
+///
+/// Other symbols are ignored.
+#[derive(Debug, Clone)]
+pub struct Turtle {
+    pub start: Point2,
+    /// Degrees anti-clockwise from the x axis, 90 points up.
+    pub heading: f32,
+    pub length: f32,
+    pub angle: f32,
+    pub weight: f32,
+    pub length_scale: f32,
+    pub weight_scale: f32,
+    pub draws: Vec<char>,
+}
+
+impl Turtle {
+    pub fn new(length: f32, angle: f32) -> Self {
+        Turtle {
+            start: pt2(0.0, 0.0),
+            heading: 90.0,
+            length,
+            angle,
+            weight: 1.0,
+            length_scale: 1.0,
+            weight_scale: 1.0,
+            draws: vec!['F', 'G'],
+        }
+    }
+
+    pub fn start(mut self, start: Point2, heading: f32) -> Self {
+        self.start = start;
+        self.heading = heading;
+        self
+    }
+
+    pub fn weight(mut self, weight: f32, scale: f32) -> Self {
+        self.weight = weight;
+        self.weight_scale = scale;
+        self
+    }
+
+    pub fn length_scale(mut self, scale: f32) -> Self {
+        self.length_scale = scale;
+        self
+    }
+
+    /// Symbols that draw a line, in place of `F` and `G`.
+    pub fn draws(mut self, symbols: &str) -> Self {
+        self.draws = symbols.chars().collect();
+        self
+    }
+
+    pub fn interpret(&self, word: &[Module]) -> Vec<Line> {
+        let mut lines = vec![];
+        let mut stack = vec![];
+        let mut s = State {
+            pos: self.start,
+            heading: self.heading.to_radians(),
+            length: self.length,
+            weight: self.weight,
+            depth: 0,
+        };
+        let mut points = vec![];
+        // Lines break wherever the turtle jumps or changes weight.
+        let finish = |points: &mut Vec<Point2>, lines: &mut Vec<Line>, s: &State| {
+            if points.len() > 1 {
+                lines.push(Line {
+                    points: std::mem::take(points),
+                    weight: s.weight,
+                    depth: s.depth,
+                });
+            }
+            points.clear();
+        };
+        for m in word {
+            match m.symbol {
+                c if self.draws.contains(&c) => {
+                    if points.is_empty() {
+                        points.push(s.pos);
+                    }
+                    let d = m.param().unwrap_or(s.length);
+                    s.pos += vec2(s.heading.cos(), s.heading.sin()) * d;
+                    points.push(s.pos);
+                }
+                'f' => {
+                    finish(&mut points, &mut lines, &s);
+                    let d = m.param().unwrap_or(s.length);
+                    s.pos += vec2(s.heading.cos(), s.heading.sin()) * d;
+                }
+                '+' => s.heading += m.param().unwrap_or(self.angle).to_radians(),
+                '-' => s.heading -= m.param().unwrap_or(self.angle).to_radians(),
+                '|' => s.heading += PI,
+                '[' => {
+                    finish(&mut points, &mut lines, &s);
+                    stack.push(s);
+                    s.depth += 1;
+                    s.length *= self.length_scale;
+                    s.weight *= self.weight_scale;
+                }
+                ']' => {
+                    finish(&mut points, &mut lines, &s);
+                    s = stack.pop().expect("unbalanced `]` in word");
+                }
+                '!' => {
+                    finish(&mut points, &mut lines, &s);
+                    s.weight = m.param().unwrap_or(s.weight * self.weight_scale);
+                }
+                _ => {}
+            }
+        }
+        finish(&mut points, &mut lines, &s);
+        lines
+    }
+}
+
+/// The smallest rectangle containing every line.
+pub fn bounds(lines: &[Line]) -> Option<Rect> {
+    let mut points = lines.iter().flat_map(|l| l.points.iter());
+    let first = *points.next()?;
+    Some(points.fold(Rect::from_corners(first, first), |r, p| {
+        r.stretch_to_point(*p)
+    }))
+}
+
+/// Scale and move the lines to fit `rect`, keeping their proportions. Line
+/// weights are left alone.
+pub fn fit(lines: &[Line], rect: Rect) -> Vec<Line> {
+    let b = match bounds(lines) {
+        Some(b) => b,
+        None => return vec![],
+    };
+    let scale = (rect.w() / b.w().max(f32::EPSILON)).min(rect.h() / b.h().max(f32::EPSILON));
+    lines
+        .iter()
+        .map(|l| Line {
+            points: l
+                .points
+                .iter()
+                .map(|p| rect.xy() + (*p - b.xy()) * scale)
+                .collect(),
+            ..l.clone()
+        })
+        .collect()
+}
+
+/// Add the lines to `canvas` with round joins and caps.
+pub fn draw<C>(canvas: &mut Canvas, lines: &[Line], color: C)
+where
+    C: IntoLinSrgba<f32> + Copy,
+{
+    for l in lines {
+        canvas
+            .polyline(l.points.clone())
+            .color(color)
+            .weight(l.weight)
+            .caps_round()
+            .join_round();
+    }
+}
+
+// A few classic systems ------------------------------------------------------
+
+pub fn koch_snowflake() -> (LSystem, Turtle) {
+    (
+        LSystem::new("F--F--F").rule('F', "F+F--F+F"),
+        Turtle::new(1.0, 60.0).start(pt2(0.0, 0.0), 0.0),
+    )
+}
+
+pub fn dragon() -> (LSystem, Turtle) {
+    (
+        LSystem::new("FX").rule('X', "X+YF+").rule('Y', "-FX-Y"),
+        Turtle::new(1.0, 90.0),
+    )
+}
+
+/// Prusinkiewicz and Lindenmayer's fractal plant.
+pub fn plant() -> (LSystem, Turtle) {
+    (
+        LSystem::new("X")
+            .rule('X', "F+[[X]-X]-F[-FX]+X")
+            .rule('F', "FF"),
+        Turtle::new(1.0, 25.0).weight(3.0, 0.7),
+    )
+}
+
+/// A plant that grows differently every time.
+pub fn stochastic_plant() -> (LSystem, Turtle) {
+    (
+        LSystem::new("F").stochastic(
+            'F',
+            &[(1.0, "F[+F]F[-F]F"), (1.0, "F[+F]F"), (1.0, "F[-F]F")],
+        ),
+        Turtle::new(1.0, 25.7).weight(4.0, 0.75),
+    )
+}
+
+/// A tree whose branches shrink by a parameter, written `A(length)`.
+pub fn parametric_tree() -> (LSystem, Turtle) {
+    const R: f32 = 1.456;
+    (
+        LSystem::new("A(1)").parametric('A', |p| {
+            let s = p[0];
+            if s < 0.05 {
+                return None;
+            }
+            Some(parse(&format!("F({})[+A({})][-A({})]", s, s / R, s / R)))
+        }),
+        Turtle::new(1.0, 40.0).weight(6.0, 0.7),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+
+    #[test]
+    fn parses_parameters() {
+        let word = parse("F(1.5)+ [A(2,-3)]f");
+        assert_eq!(word.len(), 6);
+        assert_eq!(word[0], Module::new('F', &[1.5]));
+        assert_eq!(word[3], Module::new('A', &[2.0, -3.0]));
+        assert_eq!(to_string(&word), "F(1.5)+[A(2,-3)]f");
+    }
+
+    #[test]
+    fn algae_grows_by_fibonacci() {
+        let algae = LSystem::new("A").rule('A', "AB").rule('B', "A");
+        let lengths: Vec<usize> = (0..7).map(|n| algae.iterate(n).len()).collect();
+        assert_eq!(lengths, vec![1, 2, 3, 5, 8, 13, 21]);
+        assert_eq!(to_string(&algae.iterate(4)), "ABAABABA");
+    }
+
+    #[test]
+    fn koch_snowflake_closes() {
+        let (system, turtle) = koch_snowflake();
+        let lines = turtle.interpret(&system.iterate(3));
+        assert_eq!(lines.len(), 1);
+        let points = &lines[0].points;
+        assert_eq!(points.len(), 3 * 4usize.pow(3) + 1);
+        assert!((points[0] - points[points.len() - 1]).magnitude() < 1e-3);
+    }
+
+    #[test]
+    fn branches_scale_and_return() {
+        let turtle = Turtle::new(10.0, 90.0).weight(4.0, 0.5).length_scale(0.5);
+        let lines = turtle.interpret(&parse("F[+F]F"));
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].points.len(), 2);
+        assert!((lines[0].points[1] - pt2(0.0, 10.0)).magnitude() < 1e-4);
+        assert_eq!(lines[1].depth, 1);
+        assert_eq!(lines[1].weight, 2.0);
+        assert!((lines[1].points[1] - pt2(-5.0, 10.0)).magnitude() < 1e-4);
+        assert_eq!(lines[2].weight, 4.0);
+        assert!((lines[2].points[1] - pt2(0.0, 20.0)).magnitude() < 1e-4);
+    }
+
+    #[test]
+    fn stochastic_rules_repeat_with_seed() {
+        let (system, _) = stochastic_plant();
+        rng::seed(3);
+        let a = system.iterate(3);
+        rng::seed(3);
+        let b = system.iterate(3);
+        assert_eq!(a, b);
+        let words: Vec<String> = (0..20).map(|_| to_string(&system.iterate(1))).collect();
+        assert!(words.contains(&"F[+F]F".to_string()));
+        assert!(words.contains(&"F[-F]F".to_string()));
+    }
+
+    #[test]
+    fn parametric_rules_stop() {
+        let (system, turtle) = parametric_tree();
+        let word = system.iterate(20);
+        assert_eq!(system.step(&word), word);
+        let lines = turtle.interpret(&word);
+        let b = bounds(&lines).unwrap();
+        let fitted = fit(&lines, Rect::from_w_h(100.0, 100.0));
+        let f = bounds(&fitted).unwrap();
+        assert!((f.w().max(f.h()) - 100.0).abs() < 1e-3);
+        assert!((f.w() / f.h() - b.w() / b.h()).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,151 @@
+use getopts::Options;
+use std::env;
+use std::path::Path;
+use std::process;
+
+use sketches::gallery;
+use sketches::scaffold::{self, Template, TEMPLATES};
+use sketches::sketch::{self, Sketch, SKETCHES};
+
+fn usage() -> ! {
+    eprintln!("Usage: sketches <command>");
+    eprintln!();
+    eprintln!("Commands:");
+    eprintln!("    list                 list every sketch");
+    eprintln!("    run <name> [args]    run a sketch, passing it any remaining arguments");
+    eprintln!("    info <name>          describe a sketch");
+    eprintln!("    gallery [names]      render sketches headlessly and regenerate README.md");
+    eprintln!("        --html           also write gallery.html");
+    eprintln!("        -s, --seed SEED  seed to render with, default {}", gallery::SEED);
+    eprintln!("    new <name>           start a new sketch from a template");
+    eprintln!("        -t, --template   one of {}, default static", template_names());
+    eprintln!("        --size WxH       canvas size, default 900x600 or 6000x6000 for hi-res");
+    process::exit(1);
+}
+
+fn lookup(name: Option<&String>) -> &'static Sketch {
+    let name = match name {
+        Some(name) => name,
+        None => usage(),
+    };
+    match sketch::find(name) {
+        Some(s) => s,
+        None => {
+            eprintln!("No sketch named `{}`, try `sketches list`.", name);
+            process::exit(1);
+        }
+    }
+}
+
+fn list() {
+    let width = SKETCHES.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    for s in SKETCHES {
+        println!("{:w$}  {:11}  {}", s.name, s.kind, s.description, w = width);
+    }
+}
+
+fn info(s: &Sketch) {
+    println!("name:        {}", s.name);
+    println!("title:       {}", s.title);
+    println!("kind:        {}", s.kind);
+    println!("description: {}", s.description);
+    if let Some(credits) = s.credits {
+        println!("credits:     {}", credits);
+    }
+}
+
+fn build_gallery(args: &[String]) {
+    let mut opts = Options::new();
+    opts.optflag("", "html", "also write gallery.html.");
+    opts.optopt("s", "seed", "seed to render with.", "SEED");
+    let matches = match opts.parse(args) {
+        Ok(m) => m,
+        Err(f) => {
+            eprintln!("{}", f);
+            usage();
+        }
+    };
+    let seed = match matches.opt_str("s") {
+        Some(s) => s.parse().unwrap_or_else(|_| usage()),
+        None => gallery::SEED,
+    };
+    let sketches: Vec<&Sketch> = if matches.free.is_empty() {
+        SKETCHES.iter().collect()
+    } else {
+        matches.free.iter().map(|name| lookup(Some(name))).collect()
+    };
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    gallery::build(root, &sketches, seed, matches.opt_present("html"));
+}
+
+fn template_names() -> String {
+    TEMPLATES
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_size(size: &str) -> Option<(u32, u32)> {
+    let mut parts = size.split('x');
+    let w = parts.next()?.parse().ok()?;
+    let h = parts.next()?.parse().ok()?;
+    match parts.next() {
+        None if w > 0 && h > 0 => Some((w, h)),
+        _ => None,
+    }
+}
+
+fn new_sketch(args: &[String]) {
+    let mut opts = Options::new();
+    opts.optopt("t", "template", "template to start from.", "TEMPLATE");
+    opts.optopt("", "size", "canvas size.", "WxH");
+    let matches = match opts.parse(args) {
+        Ok(m) => m,
+        Err(f) => {
+            eprintln!("{}", f);
+            usage();
+        }
+    };
+    let name = match matches.free.as_slice() {
+        [name] => name,
+        _ => usage(),
+    };
+    let template = match matches.opt_str("t") {
+        Some(t) => Template::from_name(&t).unwrap_or_else(|| {
+            eprintln!("No template named `{}`, use one of {}.", t, template_names());
+            process::exit(1);
+        }),
+        None => Template::Static,
+    };
+    let (width, height) = match matches.opt_str("size") {
+        Some(size) => parse_size(&size).unwrap_or_else(|| {
+            eprintln!("Invalid size `{}`, expected WIDTHxHEIGHT.", size);
+            process::exit(1);
+        }),
+        None => template.default_size(),
+    };
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    match scaffold::create(root, template, name, width, height) {
+        Ok(path) => {
+            println!("Created {}", path.display());
+            println!("Run it with `cargo run -- run {}`.", name);
+        }
+        Err(e) => {
+            eprintln!("{}.", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("list") => list(),
+        Some("info") => info(lookup(args.get(2))),
+        Some("run") => lookup(args.get(2)).launch(args.iter().skip(3).cloned().collect()),
+        Some("gallery") => build_gallery(&args[2..]),
+        Some("new") => new_sketch(&args[2..]),
+        _ => usage(),
+    }
+}
@@ -0,0 +1,509 @@
+// Packing circles, or copies of a convex shape, into a region without
+// overlap. Two ways to fill it:
+//
+// - `growth` throws down random centres and grows each shape from there until
+//   it touches a neighbour, the edge or the maximum size, keeping it if it is
+//   at least the minimum. Sizes are uneven and gaps fill in with small shapes.
+// - `front_chain` lays circles tangent to each other out from the centre,
+//   each one touching two on the outside of the pack, for a tight fill with
+//   sizes straight from the range. See Wang, W. et al (2006). "Visualization
+//   of large hierarchical data by circle packing", as in d3's `packSiblings`.
+
+use nannou::prelude::*;
+
+use crate::particles::Bounds;
+use crate::rng;
+use crate::spatial::HashGrid;
+use crate::Grid;
+
+/// A placed circle, or a copy of the shape scaled by `radius` and turned by
+/// `angle` about `center`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    pub center: Point2,
+    pub radius: f32,
+    pub angle: f32,
+}
+
+type Mask = Box<dyn Fn(Point2) -> bool>;
+
+pub struct Packing {
+    pub bounds: Bounds,
+    pub min_radius: f32,
+    pub max_radius: f32,
+    /// The gap left between shapes, and between shapes and the edge.
+    pub padding: f32,
+    /// Failed tries in a row before `growth` gives up.
+    pub attempts: usize,
+    pub count: Option<usize>,
+    pub seed: Option<u64>,
+    density: Option<Grid<f32>>,
+    mask: Option<Mask>,
+    shape: Option<Vec<Point2>>,
+    rotate: bool,
+}
+
+// How far `p` is inside the bounds, negative outside.
+fn room(bounds: &Bounds, p: Point2) -> f32 {
+    match *bounds {
+        Bounds::Rect(r) => (p.x - r.left())
+            .min(r.right() - p.x)
+            .min(p.y - r.bottom())
+            .min(r.top() - p.y),
+        Bounds::Circle(c, r) => r - p.distance(c),
+    }
+}
+
+// The gap between two convex polygons along the axis that separates them
+// most, negative if they overlap.
+fn gap(a: &[Point2], b: &[Point2]) -> f32 {
+    let mut best = f32::MIN;
+    for (poly, other) in [(a, b), (b, a)].iter() {
+        for i in 0..poly.len() {
+            let e = poly[(i + 1) % poly.len()] - poly[i];
+            let n = vec2(e.y, -e.x).normalize();
+            let project = |ps: &[Point2]| {
+                ps.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+                    let d = p.dot(n);
+                    (lo.min(d), hi.max(d))
+                })
+            };
+            let (a0, a1) = project(poly);
+            let (b0, b1) = project(other);
+            best = best.max((b0 - a1).max(a0 - b1));
+        }
+    }
+    best
+}
+
+// The centre of a circle of radius `r` touching both `a` and `b`, on the
+// side that keeps the front turning the same way.
+fn place(a: (Point2, f32), b: (Point2, f32), r: f32) -> Point2 {
+    let d = a.0 - b.0;
+    let d2 = d.magnitude2();
+    if d2 == 0.0 {
+        return b.0 + vec2(r, 0.0);
+    }
+    let ar = (b.1 + r) * (b.1 + r);
+    let br = (a.1 + r) * (a.1 + r);
+    if ar > br {
+        let x = (d2 + br - ar) / (2.0 * d2);
+        let y = (br / d2 - x * x).max(0.0).sqrt();
+        pt2(a.0.x - x * d.x - y * d.y, a.0.y - x * d.y + y * d.x)
+    } else {
+        let x = (d2 + ar - br) / (2.0 * d2);
+        let y = (ar / d2 - x * x).max(0.0).sqrt();
+        pt2(b.0.x + x * d.x - y * d.y, b.0.y + x * d.y + y * d.x)
+    }
+}
+
+fn intersects(a: (Point2, f32), b: (Point2, f32)) -> bool {
+    let dr = a.1 + b.1 - 1e-6;
+    dr > 0.0 && dr * dr > a.0.distance2(b.0)
+}
+
+impl Packing {
+    /// Circles with radii from 5 to 50 inside `bounds`, 1000 failed tries in a
+    /// row end a `growth` packing.
+    pub fn new(bounds: Bounds) -> Self {
+        Packing {
+            bounds,
+            min_radius: 5.0,
+            max_radius: 50.0,
+            padding: 0.0,
+            attempts: 1000,
+            count: None,
+            seed: None,
+            density: None,
+            mask: None,
+            shape: None,
+            rotate: true,
+        }
+    }
+
+    pub fn radius(mut self, min: f32, max: f32) -> Self {
+        assert!(0.0 < min && min <= max, "bad radius range");
+        self.min_radius = min;
+        self.max_radius = max;
+        self
+    }
+
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Stop after `count` shapes.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Reseed the generator before packing so the same packing comes out
+    /// every time.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Values from 0 to 1, where the grid is 1 shapes are kept to the
+    /// minimum size and where it is 0 they may grow to the maximum.
+    pub fn density(mut self, grid: Grid<f32>) -> Self {
+        self.density = Some(grid);
+        self
+    }
+
+    /// Only place shapes where `inside` holds for the centre and rim.
+    pub fn mask(mut self, inside: impl Fn(Point2) -> bool + 'static) -> Self {
+        self.mask = Some(Box::new(inside));
+        self
+    }
+
+    /// Pack copies of a convex polygon around the origin in place of
+    /// circles, the radii scale it. Only `growth` packs shapes.
+    pub fn shape(mut self, outline: Vec<Point2>) -> Self {
+        assert!(outline.len() > 2, "a shape needs three points");
+        self.shape = Some(outline);
+        self
+    }
+
+    /// Whether shapes are turned at random, they are by default.
+    pub fn rotate(mut self, rotate: bool) -> Self {
+        self.rotate = rotate;
+        self
+    }
+
+    /// The largest radius allowed at `p`.
+    pub fn max_radius_at(&self, p: Point2) -> f32 {
+        match &self.density {
+            Some(grid) => {
+                let d = grid.get(p.x, p.y).clamp(0.0, 1.0);
+                self.max_radius - d * (self.max_radius - self.min_radius)
+            }
+            None => self.max_radius,
+        }
+    }
+
+    // How far the shape reaches from its centre at radius 1.
+    fn extent(&self) -> f32 {
+        match &self.shape {
+            Some(s) => s.iter().map(|p| p.magnitude()).fold(0.0, f32::max),
+            None => 1.0,
+        }
+    }
+
+    /// The outline of a placement, a polygon of 64 sides for circles.
+    pub fn outline(&self, p: &Placement) -> Vec<Point2> {
+        match &self.shape {
+            Some(s) => s
+                .iter()
+                .map(|v| p.center + v.rotate(p.angle) * p.radius)
+                .collect(),
+            None => (0..64)
+                .map(|i| {
+                    let a = i as f32 / 64.0 * TAU;
+                    p.center + vec2(a.cos(), a.sin()) * p.radius
+                })
+                .collect(),
+        }
+    }
+
+    fn masked(&self, outline: &[Point2]) -> bool {
+        match &self.mask {
+            Some(inside) => outline.iter().all(|&p| inside(p)),
+            None => true,
+        }
+    }
+
+    // Whether `p` fits with the shapes already placed near it.
+    fn fits(&self, p: &Placement, placed: &[Placement], index: &HashGrid) -> bool {
+        let outline = self.outline(p);
+        let pad = self.padding;
+        if !outline.iter().all(|&v| room(&self.bounds, v) >= pad) || !self.masked(&outline) {
+            return false;
+        }
+        let reach = (p.radius + self.max_radius) * self.extent() + pad;
+        index.within(p.center, reach).into_iter().all(|i| {
+            let q = &placed[i];
+            match self.shape {
+                Some(_) => gap(&outline, &self.outline(q)) >= pad,
+                None => p.center.distance(q.center) >= p.radius + q.radius + pad,
+            }
+        })
+    }
+
+    // The largest circle at `c` up to `cap` clear of the edge and its
+    // neighbours.
+    fn circle_room(&self, c: Point2, cap: f32, placed: &[Placement], index: &HashGrid) -> f32 {
+        let pad = self.padding;
+        let reach = cap + self.max_radius + pad;
+        index
+            .within(c, reach)
+            .into_iter()
+            .map(|i| c.distance(placed[i].center) - placed[i].radius - pad)
+            .fold(cap.min(room(&self.bounds, c) - pad), f32::min)
+    }
+
+    /// Grow shapes from random centres until `attempts` tries in a row fail
+    /// or there are `count` of them.
+    pub fn growth(&self) -> Vec<Placement> {
+        if let Some(seed) = self.seed {
+            rng::seed(seed);
+        }
+        let mut placed: Vec<Placement> = vec![];
+        let mut index = HashGrid::new(2.0 * self.max_radius * self.extent() + self.padding);
+        let mut fails = 0;
+        while fails < self.attempts && self.count.is_none_or(|n| placed.len() < n) {
+            let center = self.bounds.random_point();
+            let cap = self.max_radius_at(center);
+            let angle = if self.rotate && self.shape.is_some() {
+                rng::random_range(0.0, TAU)
+            } else {
+                0.0
+            };
+            let at = |radius| Placement {
+                center,
+                radius,
+                angle,
+            };
+            let radius = match self.shape {
+                None => {
+                    // Shrink to fit the mask.
+                    let mut r = self.circle_room(center, cap, &placed, &index);
+                    while r >= self.min_radius && !self.masked(&self.outline(&at(r))) {
+                        r *= 0.9;
+                    }
+                    r
+                }
+                Some(_) if !self.fits(&at(self.min_radius), &placed, &index) => 0.0,
+                Some(_) => {
+                    // Bigger copies hold the smaller, so search for the size.
+                    let (mut lo, mut hi) = (self.min_radius, cap);
+                    if self.fits(&at(hi), &placed, &index) {
+                        lo = hi;
+                    }
+                    for _ in 0..12 {
+                        let mid = (lo + hi) / 2.0;
+                        if self.fits(&at(mid), &placed, &index) {
+                            lo = mid;
+                        } else {
+                            hi = mid;
+                        }
+                    }
+                    lo
+                }
+            };
+            if radius >= self.min_radius {
+                placed.push(at(radius));
+                index.insert(center);
+                fails = 0;
+            } else {
+                fails += 1;
+            }
+        }
+        placed
+    }
+
+    /// Circles tangent to one another spreading out from the centre of the
+    /// bounds, keeping the ones that fit.
+    pub fn front_chain(&self) -> Vec<Placement> {
+        assert!(self.shape.is_none(), "the front chain only packs circles");
+        if let Some(seed) = self.seed {
+            rng::seed(seed);
+        }
+        let (origin, extent) = match self.bounds {
+            Bounds::Rect(r) => (r.xy(), 0.5 * r.w().hypot(r.h())),
+            Bounds::Circle(c, r) => (c, r),
+        };
+        let half = self.padding / 2.0;
+        let radius_at = |p: Point2| {
+            let r = rng::random_range(self.min_radius, self.max_radius_at(origin + p));
+            r + half
+        };
+        // Centres relative to the origin, radii padded.
+        let mut cs: Vec<(Point2, f32)> = vec![];
+        let r0 = radius_at(pt2(0.0, 0.0));
+        let r1 = radius_at(pt2(0.0, 0.0));
+        cs.push((pt2(-r1, 0.0), r0));
+        cs.push((pt2(r0, 0.0), r1));
+        let r2 = radius_at(pt2(0.0, 0.0));
+        cs.push((place(cs[1], cs[0], r2), r2));
+        // The front, a ring of circles linked both ways.
+        let mut next = vec![1, 2, 0];
+        let mut prev = vec![2, 0, 1];
+        let score = |cs: &[(Point2, f32)], next: &[usize], n: usize| {
+            let (a, b) = (cs[n], cs[next[n]]);
+            (a.0 * b.1 + b.0 * a.1) / (a.1 + b.1)
+        };
+        let placement = |&(c, r): &(Point2, f32)| Placement {
+            center: origin + c,
+            radius: r - half,
+            angle: 0.0,
+        };
+        let inside = |p: &Placement| {
+            room(&self.bounds, p.center) - self.padding >= p.radius && self.masked(&self.outline(p))
+        };
+        let (mut a, mut b) = (0, 1);
+        let limit = extent + self.max_radius;
+        let mut fits = cs.iter().map(placement).filter(inside).count();
+        // Place next to the pair of the front nearest the middle until the
+        // front has left the bounds.
+        while score(&cs, &next, a).magnitude() < limit && self.count.is_none_or(|n| fits < n) {
+            let r = radius_at(score(&cs, &next, a));
+            'retry: loop {
+                let p = place(cs[a], cs[b], r);
+                // Look for overlaps along the front both ways from the pair,
+                // cutting the front back to any circle that is hit.
+                let (mut j, mut k) = (next[b], prev[a]);
+                let (mut sj, mut sk) = (cs[b].1, cs[a].1);
+                loop {
+                    if sj <= sk {
+                        if intersects(cs[j], (p, r)) {
+                            b = j;
+                            next[a] = b;
+                            prev[b] = a;
+                            continue 'retry;
+                        }
+                        sj += cs[j].1;
+                        j = next[j];
+                    } else {
+                        if intersects(cs[k], (p, r)) {
+                            a = k;
+                            next[a] = b;
+                            prev[b] = a;
+                            continue 'retry;
+                        }
+                        sk += cs[k].1;
+                        k = prev[k];
+                    }
+                    if j == next[k] {
+                        break;
+                    }
+                }
+                let c = cs.len();
+                cs.push((p, r));
+                next.push(b);
+                prev.push(a);
+                next[a] = c;
+                prev[b] = c;
+                // The next pair is the one nearest the middle.
+                let mut best = score(&cs, &next, a).magnitude2();
+                let mut n = next[c];
+                while n != c {
+                    let s = score(&cs, &next, n).magnitude2();
+                    if s < best {
+                        a = n;
+                        best = s;
+                    }
+                    n = next[n];
+                }
+                b = next[a];
+                break;
+            }
+            if inside(&placement(&cs[cs.len() - 1])) {
+                fits += 1;
+            }
+        }
+        cs.iter()
+            .map(placement)
+            .filter(inside)
+            .take(self.count.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overlapping(placed: &[Placement], padding: f32) -> bool {
+        placed.iter().enumerate().any(|(i, p)| {
+            placed[i + 1..]
+                .iter()
+                .any(|q| p.center.distance(q.center) < p.radius + q.radius + padding - 1e-3)
+        })
+    }
+
+    #[test]
+    fn growth_packs_circles() {
+        let bounds = Bounds::Rect(Rect::from_w_h(400.0, 300.0));
+        let packing = Packing::new(bounds).radius(4.0, 40.0).padding(2.0).seed(5);
+        let placed = packing.growth();
+        assert!(placed.len() > 50);
+        assert!(!overlapping(&placed, 2.0));
+        assert!(placed.iter().all(|p| p.radius >= 4.0
+            && p.radius <= 40.0
+            && room(&bounds, p.center) >= p.radius + 2.0));
+        // Seeded packings repeat.
+        assert_eq!(placed, packing.growth());
+        assert_eq!(Packing::new(bounds).count(10).growth().len(), 10);
+    }
+
+    #[test]
+    fn front_chain_packs_tightly() {
+        let bounds = Bounds::Circle(pt2(10.0, 0.0), 200.0);
+        let placed = Packing::new(bounds)
+            .radius(8.0, 12.0)
+            .padding(1.0)
+            .seed(2)
+            .front_chain();
+        assert!(!overlapping(&placed, 1.0));
+        assert!(placed
+            .iter()
+            .all(|p| room(&bounds, p.center) >= p.radius + 1.0));
+        // Better than 70% of the area.
+        let area: f32 = placed.iter().map(|p| PI * (p.radius + 0.5).powi(2)).sum();
+        assert!(area > 0.7 * PI * 200.0 * 200.0);
+    }
+
+    #[test]
+    fn density_and_mask() {
+        let bounds = Bounds::Rect(Rect::from_w_h(400.0, 400.0));
+        // Small on the left, large on the right, nothing in the top half.
+        let density = Grid::new(400.0, 400.0, 10.0, |x, _| if x < 0.0 { 1.0 } else { 0.0 });
+        let placed = Packing::new(bounds)
+            .radius(3.0, 30.0)
+            .density(density)
+            .mask(|p| p.y <= 0.0)
+            .seed(9)
+            .growth();
+        assert!(placed
+            .iter()
+            .all(|p| p.center.y + p.radius <= 1e-3 && (p.center.x >= 0.0 || p.radius <= 3.0)));
+        assert!(placed.iter().any(|p| p.radius > 20.0));
+    }
+
+    #[test]
+    fn shapes() {
+        let square = vec![
+            pt2(-1.0, -1.0),
+            pt2(1.0, -1.0),
+            pt2(1.0, 1.0),
+            pt2(-1.0, 1.0),
+        ];
+        let bounds = Bounds::Rect(Rect::from_w_h(300.0, 300.0));
+        let packing = Packing::new(bounds)
+            .shape(square)
+            .radius(5.0, 30.0)
+            .padding(1.0)
+            .seed(4);
+        let placed = packing.growth();
+        assert!(placed.len() > 20);
+        for (i, p) in placed.iter().enumerate() {
+            for q in &placed[i + 1..] {
+                assert!(gap(&packing.outline(p), &packing.outline(q)) >= 1.0 - 1e-3);
+            }
+        }
+        let upright = Packing::new(bounds)
+            .shape(vec![pt2(0.0, 1.0), pt2(-1.0, -1.0), pt2(1.0, -1.0)])
+            .rotate(false)
+            .count(5)
+            .growth();
+        assert!(upright.iter().all(|p| p.angle == 0.0));
+    }
+}
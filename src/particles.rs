@@ -0,0 +1,515 @@
+// A particle system. Each step the forces acting on a particle are added up
+// into its acceleration, the acceleration into its velocity and the velocity
+// into its position, then the boundary decides what happens to particles that
+// left it. Particles carry `data` for whatever the sketch needs to draw them.
+
+use nannou::geom::Rect;
+use nannou::prelude::*;
+use std::collections::VecDeque;
+
+use crate::rng::random_range;
+use crate::Grid;
+
+#[derive(Debug, Clone)]
+pub struct Particle<T = ()> {
+    pub position: Point2,
+    pub velocity: Vector2,
+    pub acceleration: Vector2,
+    pub mass: f32,
+    /// Steps since the particle was born.
+    pub age: u32,
+    /// Steps the particle lives for, forever if `None`.
+    pub lifespan: Option<u32>,
+    /// Recent positions, oldest first, as many as the system keeps.
+    pub trail: VecDeque<Point2>,
+    pub alive: bool,
+    pub data: T,
+}
+
+impl Particle<()> {
+    pub fn new(position: Point2) -> Self {
+        Particle::with_data(position, ())
+    }
+}
+
+impl<T> Particle<T> {
+    pub fn with_data(position: Point2, data: T) -> Self {
+        Particle {
+            position,
+            velocity: vec2(0.0, 0.0),
+            acceleration: vec2(0.0, 0.0),
+            mass: 1.0,
+            age: 0,
+            lifespan: None,
+            trail: VecDeque::new(),
+            alive: true,
+            data,
+        }
+    }
+
+    pub fn velocity(mut self, velocity: Vector2) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    pub fn mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    pub fn lifespan(mut self, steps: u32) -> Self {
+        self.lifespan = Some(steps);
+        self
+    }
+
+    pub fn apply_force(&mut self, force: Vector2) {
+        self.acceleration += force / self.mass;
+    }
+
+    /// The fraction of its life the particle has left, always 1 for
+    /// particles that live forever.
+    pub fn life(&self) -> f32 {
+        match self.lifespan {
+            Some(n) => 1.0 - (self.age as f32 / n as f32).min(1.0),
+            None => 1.0,
+        }
+    }
+
+    fn update(&mut self, max_speed: Option<f32>, trail: usize) {
+        self.velocity += self.acceleration;
+        if let Some(s) = max_speed {
+            self.velocity = self.velocity.limit_magnitude(s);
+        }
+        self.position += self.velocity;
+        self.acceleration = vec2(0.0, 0.0);
+        self.age += 1;
+        if self.lifespan.is_some_and(|n| self.age >= n) {
+            self.alive = false;
+        }
+        if trail > 0 {
+            self.trail.push_back(self.position);
+            while self.trail.len() > trail {
+                self.trail.pop_front();
+            }
+        }
+    }
+}
+
+/// Something pushing particles around, given a particle's position, velocity
+/// and mass. Closures of the same shape are forces too.
+pub trait Force {
+    fn force(&self, position: Point2, velocity: Vector2, mass: f32) -> Vector2;
+}
+
+impl<F> Force for F
+where
+    F: Fn(Point2, Vector2, f32) -> Vector2,
+{
+    fn force(&self, position: Point2, velocity: Vector2, mass: f32) -> Vector2 {
+        self(position, velocity, mass)
+    }
+}
+
+/// The same acceleration for every particle, whatever its mass.
+pub struct Gravity(pub Vector2);
+
+impl Force for Gravity {
+    fn force(&self, _position: Point2, _velocity: Vector2, mass: f32) -> Vector2 {
+        self.0 * mass
+    }
+}
+
+/// Slows particles down in proportion to their speed.
+pub struct Drag(pub f32);
+
+impl Force for Drag {
+    fn force(&self, _position: Point2, velocity: Vector2, _mass: f32) -> Vector2 {
+        -velocity * self.0
+    }
+}
+
+/// Pulls particles towards `position` with the inverse square law, or pushes
+/// them away when `strength` is negative. Distances are taken to be at least
+/// `min_distance` so nothing is flung off at the centre.
+pub struct Attractor {
+    pub position: Point2,
+    pub strength: f32,
+    pub min_distance: f32,
+}
+
+impl Force for Attractor {
+    fn force(&self, position: Point2, _velocity: Vector2, mass: f32) -> Vector2 {
+        let d = self.position - position;
+        let r = d.magnitude().max(self.min_distance);
+        if d.magnitude2() == 0.0 {
+            return vec2(0.0, 0.0);
+        }
+        d.normalize() * self.strength * mass / (r * r)
+    }
+}
+
+/// Steers particles to move at `speed` along the angles of a flow field,
+/// `steer` of 1 turns them in a single step.
+pub struct Flow {
+    pub grid: Grid<f32>,
+    pub speed: f32,
+    pub steer: f32,
+}
+
+impl Force for Flow {
+    fn force(&self, position: Point2, velocity: Vector2, mass: f32) -> Vector2 {
+        let angle = self.grid.get(position.x, position.y);
+        let desired = vec2(angle.cos(), angle.sin()) * self.speed;
+        (desired - velocity) * self.steer * mass
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bounds {
+    Rect(Rect),
+    Circle(Point2, f32),
+}
+
+impl Bounds {
+    pub fn contains(&self, p: Point2) -> bool {
+        match *self {
+            Bounds::Rect(r) => r.contains(p),
+            Bounds::Circle(c, r) => p.distance(c) <= r,
+        }
+    }
+
+    /// A point picked evenly from inside.
+    pub fn random_point(&self) -> Point2 {
+        match *self {
+            Bounds::Rect(r) => pt2(
+                random_range(r.left(), r.right()),
+                random_range(r.bottom(), r.top()),
+            ),
+            Bounds::Circle(c, r) => {
+                let d = r * random_range(0.0f32, 1.0).sqrt();
+                let a = random_range(0.0, TAU);
+                c + vec2(a.cos(), a.sin()) * d
+            }
+        }
+    }
+}
+
+/// What happens to a particle that leaves the bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// Comes back in on the opposite side.
+    Wrap,
+    /// Reflects off the boundary.
+    Bounce,
+    /// Starts again at a random point inside.
+    Respawn,
+    /// Dies.
+    Kill,
+}
+
+impl Policy {
+    fn apply<T>(self, bounds: Bounds, p: &mut Particle<T>) {
+        if bounds.contains(p.position) {
+            return;
+        }
+        match (self, bounds) {
+            (Policy::Wrap, Bounds::Rect(r)) => {
+                p.position.x = r.left() + (p.position.x - r.left()).rem_euclid(r.w());
+                p.position.y = r.bottom() + (p.position.y - r.bottom()).rem_euclid(r.h());
+                p.trail.clear();
+            }
+            (Policy::Wrap, Bounds::Circle(c, r)) => {
+                p.position = c - (p.position - c).normalize() * r;
+                p.trail.clear();
+            }
+            (Policy::Bounce, Bounds::Rect(r)) => {
+                let (x, y) = (p.position.x, p.position.y);
+                if x > r.right() || x < r.left() {
+                    let edge = if x > r.right() { r.right() } else { r.left() };
+                    p.position.x = 2.0 * edge - x;
+                    p.velocity.x = -p.velocity.x;
+                }
+                if y > r.top() || y < r.bottom() {
+                    let edge = if y > r.top() { r.top() } else { r.bottom() };
+                    p.position.y = 2.0 * edge - y;
+                    p.velocity.y = -p.velocity.y;
+                }
+            }
+            (Policy::Bounce, Bounds::Circle(c, r)) => {
+                let d = p.position - c;
+                let n = d.normalize();
+                p.position = c + n * (2.0 * r - d.magnitude()).max(0.0);
+                p.velocity -= n * 2.0 * p.velocity.dot(n);
+            }
+            (Policy::Respawn, _) => {
+                p.position = bounds.random_point();
+                p.trail.clear();
+                p.age = 0;
+            }
+            (Policy::Kill, _) => p.alive = false,
+        }
+    }
+}
+
+/// Releases `rate` particles a step, on average, from `position`. They head
+/// off in `direction`, give or take half the `spread`, at a speed between
+/// `speed.0` and `speed.1`, and get their data from `data`.
+pub struct Emitter<T = ()> {
+    pub position: Point2,
+    pub rate: f32,
+    pub speed: (f32, f32),
+    pub direction: f32,
+    pub spread: f32,
+    pub lifespan: Option<u32>,
+    pub mass: f32,
+    data: Box<dyn FnMut() -> T>,
+    due: f32,
+}
+
+impl<T> Emitter<T> {
+    pub fn new(position: Point2, rate: f32, data: impl FnMut() -> T + 'static) -> Self {
+        Emitter {
+            position,
+            rate,
+            speed: (1.0, 1.0),
+            direction: 0.0,
+            spread: TAU,
+            lifespan: None,
+            mass: 1.0,
+            data: Box::new(data),
+            due: 0.0,
+        }
+    }
+
+    pub fn speed(mut self, min: f32, max: f32) -> Self {
+        self.speed = (min, max);
+        self
+    }
+
+    pub fn direction(mut self, angle: f32, spread: f32) -> Self {
+        self.direction = angle;
+        self.spread = spread;
+        self
+    }
+
+    pub fn lifespan(mut self, steps: u32) -> Self {
+        self.lifespan = Some(steps);
+        self
+    }
+
+    pub fn mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    pub fn emit(&mut self) -> Vec<Particle<T>> {
+        self.due += self.rate;
+        let mut particles = vec![];
+        while self.due >= 1.0 {
+            self.due -= 1.0;
+            let a = self.direction + random_range(-0.5, 0.5) * self.spread;
+            let s = random_range(self.speed.0, self.speed.1);
+            let mut p = Particle::with_data(self.position, (self.data)())
+                .velocity(vec2(a.cos(), a.sin()) * s)
+                .mass(self.mass);
+            p.lifespan = self.lifespan;
+            particles.push(p);
+        }
+        particles
+    }
+}
+
+pub struct System<T = ()> {
+    pub particles: Vec<Particle<T>>,
+    forces: Vec<Box<dyn Force>>,
+    bounds: Option<(Bounds, Policy)>,
+    emitters: Vec<Emitter<T>>,
+    trail: usize,
+    max_speed: Option<f32>,
+}
+
+impl<T> System<T> {
+    pub fn new() -> Self {
+        System {
+            particles: vec![],
+            forces: vec![],
+            bounds: None,
+            emitters: vec![],
+            trail: 0,
+            max_speed: None,
+        }
+    }
+
+    pub fn force(mut self, force: impl Force + 'static) -> Self {
+        self.forces.push(Box::new(force));
+        self
+    }
+
+    pub fn bounds(mut self, bounds: Bounds, policy: Policy) -> Self {
+        self.bounds = Some((bounds, policy));
+        self
+    }
+
+    pub fn emitter(mut self, emitter: Emitter<T>) -> Self {
+        self.emitters.push(emitter);
+        self
+    }
+
+    /// Keep the last `n` positions of each particle.
+    pub fn trail(mut self, n: usize) -> Self {
+        self.trail = n;
+        self
+    }
+
+    pub fn max_speed(mut self, speed: f32) -> Self {
+        self.max_speed = Some(speed);
+        self
+    }
+
+    pub fn add(&mut self, particle: Particle<T>) {
+        self.particles.push(particle);
+    }
+
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Emit, move every particle one step and clear out the dead.
+    pub fn step(&mut self) {
+        for e in self.emitters.iter_mut() {
+            self.particles.extend(e.emit());
+        }
+        for p in self.particles.iter_mut() {
+            let f = self.forces.iter().fold(vec2(0.0, 0.0), |f, force| {
+                f + force.force(p.position, p.velocity, p.mass)
+            });
+            p.apply_force(f);
+            p.update(self.max_speed, self.trail);
+            if let Some((bounds, policy)) = self.bounds {
+                policy.apply(bounds, p);
+            }
+        }
+        self.particles.retain(|p| p.alive);
+    }
+}
+
+impl<T> Default for System<T> {
+    fn default() -> Self {
+        System::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+
+    #[test]
+    fn forces_move_particles() {
+        let mut system = System::new().force(Gravity(vec2(0.0, -1.0)));
+        system.add(Particle::new(pt2(0.0, 0.0)).mass(3.0));
+        system.add(Particle::new(pt2(0.0, 0.0)).velocity(vec2(2.0, 0.0)));
+        for _ in 0..3 {
+            system.step();
+        }
+        // Heavy or light they fall 1 + 2 + 3.
+        assert_eq!(system.particles[0].position, pt2(0.0, -6.0));
+        assert_eq!(system.particles[1].position, pt2(6.0, -6.0));
+
+        let mut system = System::new().force(Drag(0.5));
+        system.add(Particle::new(pt2(0.0, 0.0)).velocity(vec2(4.0, 0.0)));
+        system.step();
+        assert_eq!(system.particles[0].velocity, vec2(2.0, 0.0));
+
+        let pull = Attractor {
+            position: pt2(10.0, 0.0),
+            strength: 100.0,
+            min_distance: 5.0,
+        };
+        assert_eq!(
+            pull.force(pt2(0.0, 0.0), vec2(0.0, 0.0), 2.0),
+            vec2(2.0, 0.0)
+        );
+        assert_eq!(
+            pull.force(pt2(9.0, 0.0), vec2(0.0, 0.0), 1.0),
+            vec2(4.0, 0.0)
+        );
+
+        let grid = Grid::new(100.0, 100.0, 10.0, |_, _| PI / 2.0);
+        let flow = Flow {
+            grid,
+            speed: 2.0,
+            steer: 1.0,
+        };
+        let mut system = System::new().force(flow).max_speed(1.5);
+        system.add(Particle::new(pt2(0.0, 0.0)).velocity(vec2(1.0, 0.0)));
+        system.step();
+        let v = system.particles[0].velocity;
+        assert!(v.x.abs() < 1e-6 && (v.y - 1.5).abs() < 1e-6);
+        // Closures are forces too.
+        let mut system = System::new().force(|p: Point2, _v: Vector2, _m: f32| -p);
+        system.add(Particle::new(pt2(1.0, 1.0)));
+        system.step();
+        assert_eq!(system.particles[0].position, pt2(0.0, 0.0));
+    }
+
+    #[test]
+    fn boundary_policies() {
+        let rect = Rect::from_w_h(100.0, 100.0);
+        let moving = || Particle::new(pt2(45.0, 0.0)).velocity(vec2(10.0, 0.0));
+        let run = |bounds: Bounds, policy: Policy| {
+            let mut system = System::new().bounds(bounds, policy).trail(5);
+            system.add(moving());
+            system.step();
+            system.particles
+        };
+        let p = &run(Bounds::Rect(rect), Policy::Wrap)[0];
+        assert_eq!(p.position, pt2(-45.0, 0.0));
+        assert!(p.trail.is_empty());
+        let p = &run(Bounds::Rect(rect), Policy::Bounce)[0];
+        assert_eq!((p.position, p.velocity), (pt2(45.0, 0.0), vec2(-10.0, 0.0)));
+        assert!(run(Bounds::Rect(rect), Policy::Kill).is_empty());
+        rng::seed(1);
+        let p = &run(Bounds::Rect(rect), Policy::Respawn)[0];
+        assert!(rect.contains(p.position) && p.age == 0);
+
+        let circle = Bounds::Circle(pt2(0.0, 0.0), 50.0);
+        let p = &run(circle, Policy::Wrap)[0];
+        assert!(p.position.distance(pt2(-50.0, 0.0)) < 1e-4);
+        let p = &run(circle, Policy::Bounce)[0];
+        assert!(p.position.distance(pt2(45.0, 0.0)) < 1e-4);
+        assert!(p.velocity.distance(vec2(-10.0, 0.0)) < 1e-4);
+        for _ in 0..100 {
+            assert!(circle.contains(circle.random_point()));
+        }
+    }
+
+    #[test]
+    fn emitters_and_lifespans() {
+        rng::seed(2);
+        let mut count = 0;
+        let emitter = Emitter::new(pt2(0.0, 0.0), 0.5, move || {
+            count += 1;
+            count
+        })
+        .speed(1.0, 2.0)
+        .direction(PI / 2.0, 0.2)
+        .lifespan(4);
+        let mut system = System::new().emitter(emitter).trail(3);
+        for _ in 0..10 {
+            system.step();
+        }
+        // One every other step, each lasting four steps.
+        assert_eq!(system.len(), 2);
+        let data: Vec<i32> = system.particles.iter().map(|p| p.data).collect();
+        assert_eq!(data, vec![4, 5]);
+        let p = &system.particles[0];
+        assert_eq!(p.trail.len(), 3);
+        assert_eq!(p.life(), 0.25);
+        assert!(p.velocity.y > 0.9 && p.velocity.x.abs() < 0.3);
+    }
+}
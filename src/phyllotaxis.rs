@@ -0,0 +1,284 @@
+// Phyllotaxis, the arrangement of florets in a sunflower head or scales on a
+// pine cone. Floret `n` is turned `n` times the divergence angle and placed
+// at a distance that grows with `n`, with the golden angle and a square root
+// law this is Vogel's model. See Vogel, H (1979). "A better way to construct
+// the sunflower head".
+
+use nannou::color::IntoLinSrgba;
+use nannou::prelude::*;
+
+/// 360 degrees divided in the golden ratio, about 137.5 degrees.
+pub const GOLDEN_ANGLE: f32 = 2.399_963_3;
+
+/// How far floret `n` is from the centre, in units of the scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Law {
+    /// `sqrt(n)`, Fermat's spiral, evenly packed florets.
+    Sqrt,
+    /// `n`, an Archimedean spiral, florets thinning out towards the rim.
+    Linear,
+    /// `n^p`.
+    Power(f32),
+    /// `e^(k n) - 1`, a logarithmic spiral.
+    Exponential(f32),
+}
+
+impl Law {
+    pub fn radius(self, n: f32) -> f32 {
+        match self {
+            Law::Sqrt => n.sqrt(),
+            Law::Linear => n,
+            Law::Power(p) => n.powf(p),
+            Law::Exponential(k) => (k * n).exp() - 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Surface {
+    Disk,
+    /// Spread evenly over a sphere of radius `scale`, tipped `tilt` radians
+    /// towards the viewer and drawn from the front.
+    Sphere {
+        tilt: f32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Floret {
+    pub index: usize,
+    /// `index / count`, 0 at the centre and nearly 1 at the rim.
+    pub t: f32,
+    pub position: Point2,
+    /// Towards the viewer on a sphere, 0 on a disk.
+    pub depth: f32,
+    pub size: f32,
+    pub color: LinSrgba,
+}
+
+type SizeFn = Box<dyn Fn(&Floret) -> f32>;
+type ColorFn = Box<dyn Fn(&Floret) -> LinSrgba>;
+
+pub struct Phyllotaxis {
+    pub count: usize,
+    pub scale: f32,
+    pub divergence: f32,
+    pub law: Law,
+    pub surface: Surface,
+    size: SizeFn,
+    color: ColorFn,
+}
+
+impl Phyllotaxis {
+    /// `count` florets of size 1 on a disk, following Vogel's model.
+    pub fn new(count: usize, scale: f32) -> Self {
+        Phyllotaxis {
+            count,
+            scale,
+            divergence: GOLDEN_ANGLE,
+            law: Law::Sqrt,
+            surface: Surface::Disk,
+            size: Box::new(|_| 1.0),
+            color: Box::new(|_| WHITE.into_lin_srgba()),
+        }
+    }
+
+    /// The angle between consecutive florets, in radians.
+    pub fn divergence(mut self, angle: f32) -> Self {
+        self.divergence = angle;
+        self
+    }
+
+    pub fn law(mut self, law: Law) -> Self {
+        self.law = law;
+        self
+    }
+
+    pub fn sphere(mut self, tilt: f32) -> Self {
+        self.surface = Surface::Sphere { tilt };
+        self
+    }
+
+    pub fn size(mut self, f: impl Fn(&Floret) -> f32 + 'static) -> Self {
+        self.size = Box::new(f);
+        self
+    }
+
+    pub fn color(mut self, f: impl Fn(&Floret) -> LinSrgba + 'static) -> Self {
+        self.color = Box::new(f);
+        self
+    }
+
+    /// Distance from the centre and angle of floret `n` on a disk.
+    pub fn polar(&self, n: usize) -> (f32, f32) {
+        let n = n as f32;
+        (self.scale * self.law.radius(n), self.divergence * n)
+    }
+
+    // Floret `n` on the surface and its depth.
+    fn place(&self, n: usize) -> (Point2, f32) {
+        match self.surface {
+            Surface::Disk => {
+                let (r, theta) = self.polar(n);
+                (pt2(r * theta.cos(), r * theta.sin()), 0.0)
+            }
+            Surface::Sphere { tilt } => {
+                // Equal areas between florets, from the north pole south.
+                let z = 1.0 - 2.0 * (n as f32 + 0.5) / self.count as f32;
+                let r = (1.0 - z * z).sqrt();
+                let theta = self.divergence * n as f32;
+                let p = vec3(r * theta.cos(), r * theta.sin(), z);
+                // Turn about the x axis so the pole leans towards the viewer.
+                let (s, c) = tilt.sin_cos();
+                let y = p.y * c - p.z * s;
+                let depth = p.y * s + p.z * c;
+                (pt2(p.x, y) * self.scale, depth * self.scale)
+            }
+        }
+    }
+
+    pub fn position(&self, n: usize) -> Point2 {
+        self.place(n).0
+    }
+
+    pub fn florets(&self) -> Vec<Floret> {
+        (0..self.count)
+            .map(|n| {
+                let (position, depth) = self.place(n);
+                let mut floret = Floret {
+                    index: n,
+                    t: n as f32 / self.count as f32,
+                    position,
+                    depth,
+                    size: 1.0,
+                    color: WHITE.into_lin_srgba(),
+                };
+                floret.size = (self.size)(&floret);
+                floret.color = (self.color)(&floret);
+                floret
+            })
+            .collect()
+    }
+
+    /// The florets facing the viewer, back to front, the ones to draw on a
+    /// sphere.
+    pub fn visible(&self) -> Vec<Floret> {
+        let mut florets: Vec<Floret> = self
+            .florets()
+            .into_iter()
+            .filter(|f| f.depth >= 0.0)
+            .collect();
+        florets.sort_by(|a, b| a.depth.partial_cmp(&b.depth).unwrap());
+        florets
+    }
+
+    /// The `k` spiral arms joining every `k`th floret, arms are easy to see
+    /// when `k` is one of the `parastichies`.
+    pub fn arms(&self, k: usize) -> Vec<Vec<Point2>> {
+        (0..k.min(self.count))
+            .map(|j| {
+                (j..self.count)
+                    .step_by(k)
+                    .map(|n| self.position(n))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The two numbers of spiral arms seen at the rim, consecutive Fibonacci
+    /// numbers for the golden angle. They are the steps in index from the
+    /// outermost floret to its nearest neighbours on two different arms.
+    /// `None` when there aren't two, as for too few florets or a rational
+    /// divergence whose florets line up on a single family of arms.
+    pub fn parastichies(&self) -> Option<(usize, usize)> {
+        if self.count < 3 {
+            return None;
+        }
+        let last = self.count - 1;
+        let p = self.position(last);
+        let mut near: Vec<(f32, usize)> = (1..last.min(1000))
+            .map(|d| (self.position(last - d).distance(p), d))
+            .collect();
+        near.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        let (gap, first) = near[0];
+        // Multiples of a step lie along the same arm, and a neighbour much
+        // further off than the nearest isn't on a crossing arm but a
+        // separate spoke.
+        let second = near[1..]
+            .iter()
+            .take_while(|(d, _)| *d < 3.0 * gap)
+            .map(|(_, d)| *d)
+            .find(|d| d % first != 0 && first % d != 0)?;
+        Some((first.min(second), first.max(second)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vogel() {
+        let ph = Phyllotaxis::new(100, 6.0).divergence(2.4);
+        let (r, theta) = ph.polar(25);
+        assert_eq!(r, 30.0);
+        assert!((theta - 60.0).abs() < 1e-4);
+        let p = ph.position(25);
+        assert!((p.magnitude() - 30.0).abs() < 1e-3);
+        assert!((GOLDEN_ANGLE - PI * (3.0 - 5f32.sqrt())).abs() < 1e-6);
+        assert_eq!(Law::Linear.radius(4.0), 4.0);
+        assert_eq!(Law::Power(1.5).radius(4.0), 8.0);
+        assert_eq!(Law::Exponential(0.5).radius(0.0), 0.0);
+    }
+
+    #[test]
+    fn florets_get_size_and_color() {
+        let ph = Phyllotaxis::new(10, 1.0)
+            .size(|f| 1.0 + f.t)
+            .color(|f| lin_srgba(f.t, 0.0, 0.0, 1.0));
+        let florets = ph.florets();
+        assert_eq!(florets.len(), 10);
+        assert_eq!(florets[5].size, 1.5);
+        assert_eq!(florets[5].color.red, 0.5);
+        assert!(florets.iter().all(|f| f.depth == 0.0));
+    }
+
+    #[test]
+    fn sphere() {
+        let ph = Phyllotaxis::new(500, 100.0).sphere(0.3);
+        let florets = ph.florets();
+        for f in &florets {
+            let r = (f.position.magnitude2() + f.depth * f.depth).sqrt();
+            assert!((r - 100.0).abs() < 1e-2);
+        }
+        let visible = ph.visible();
+        // About half face the viewer.
+        assert!((visible.len() as i32 - 250).abs() < 10);
+        assert!(visible.windows(2).all(|w| w[0].depth <= w[1].depth));
+    }
+
+    #[test]
+    fn arms_and_parastichies() {
+        let ph = Phyllotaxis::new(2000, 6.0);
+        let (a, b) = ph.parastichies().unwrap();
+        let fib = [1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233];
+        let i = fib
+            .iter()
+            .position(|f| *f == a)
+            .expect("not a Fibonacci number");
+        assert_eq!(b, fib[i + 1]);
+        assert!(a >= 21);
+        let arms = ph.arms(a);
+        assert_eq!(arms.len(), a);
+        assert_eq!(arms.iter().map(|a| a.len()).sum::<usize>(), 2000);
+        // Neighbours along an arm are close, so it reads as a spiral.
+        let rim = &arms[0][arms[0].len() - 2..];
+        assert!(rim[0].distance(rim[1]) < 6.0 * 3.0);
+        // Too few florets, and every floret on the eight arms of a rational
+        // divergence.
+        assert_eq!(Phyllotaxis::new(3, 6.0).parastichies(), None);
+        assert_eq!(Phyllotaxis::new(4, 6.0).parastichies(), None);
+        let eighths = Phyllotaxis::new(500, 6.0).divergence(TAU / 8.0);
+        assert_eq!(eighths.parastichies(), None);
+    }
+}
@@ -0,0 +1,588 @@
+// Smoothing, simplifying and measuring polylines. Traced lines come out
+// either jagged, from a long step, or with far more points than the eye or a
+// plotter needs, from a short one.
+//
+// - `chaikin` and `catmull_rom` smooth, the first cutting corners and the
+//   second passing through every point.
+// - `fit_bspline` finds a few control points for a cubic B-spline close to
+//   the line, and `bspline_segments` draws it exactly with cubics.
+// - `rdp` and `visvalingam` drop points that barely change the shape.
+// - `resample` spaces points evenly along the line.
+
+use nannou::prelude::*;
+
+use crate::canvas::Segment;
+
+/// The length of the line.
+pub fn length(points: &[Point2]) -> f32 {
+    points.windows(2).map(|w| w[0].distance(w[1])).sum()
+}
+
+/// The distance along the line to each point, starting at 0.
+pub fn arc_lengths(points: &[Point2]) -> Vec<f32> {
+    let mut total = 0.0;
+    let mut lengths = Vec::with_capacity(points.len());
+    for (i, &p) in points.iter().enumerate() {
+        if i > 0 {
+            total += points[i - 1].distance(p);
+        }
+        lengths.push(total);
+    }
+    lengths
+}
+
+/// The point `distance` along the line, clamped to its ends.
+pub fn point_at(points: &[Point2], distance: f32) -> Point2 {
+    assert!(!points.is_empty(), "no points");
+    let mut left = distance.max(0.0);
+    for w in points.windows(2) {
+        let d = w[0].distance(w[1]);
+        if left <= d && d > 0.0 {
+            return w[0].lerp(w[1], left / d);
+        }
+        left -= d;
+    }
+    points[points.len() - 1]
+}
+
+/// The signed curvature at each point, one over the radius of the circle
+/// through it and its neighbours, positive where the line turns left. The
+/// ends have none.
+pub fn curvature(points: &[Point2]) -> Vec<f32> {
+    (0..points.len())
+        .map(|i| {
+            if i == 0 || i + 1 == points.len() {
+                return 0.0;
+            }
+            let (a, b, c) = (points[i - 1], points[i], points[i + 1]);
+            let sides = a.distance(b) * b.distance(c) * a.distance(c);
+            if sides == 0.0 {
+                0.0
+            } else {
+                2.0 * (b - a).perp_dot(c - b) / sides
+            }
+        })
+        .collect()
+}
+
+/// Points about `spacing` apart along the line, spread evenly so that both
+/// ends are kept.
+pub fn resample(points: &[Point2], spacing: f32) -> Vec<Point2> {
+    assert!(spacing > 0.0, "spacing must be positive");
+    if points.len() < 2 {
+        return points.to_vec();
+    }
+    let total = length(points);
+    let n = (total / spacing).round().max(1.0) as usize;
+    let step = total / n as f32;
+    let mut resampled = Vec::with_capacity(n + 1);
+    resampled.push(points[0]);
+    // Walk the segments once rather than searching from the start each time.
+    let (mut i, mut start) = (0, 0.0);
+    for k in 1..n {
+        let s = k as f32 * step;
+        let mut d = points[i].distance(points[i + 1]);
+        while start + d < s && i + 2 < points.len() {
+            start += d;
+            i += 1;
+            d = points[i].distance(points[i + 1]);
+        }
+        let t = if d > 0.0 { (s - start) / d } else { 0.0 };
+        resampled.push(points[i].lerp(points[i + 1], t.min(1.0)));
+    }
+    resampled.push(points[points.len() - 1]);
+    resampled
+}
+
+/// Chaikin's corner cutting, each pass replaces every segment by points a
+/// quarter and three quarters along it. Open lines keep their ends.
+pub fn chaikin(points: &[Point2], passes: usize, closed: bool) -> Vec<Point2> {
+    let mut line = points.to_vec();
+    for _ in 0..passes {
+        if line.len() < 3 {
+            break;
+        }
+        let n = line.len();
+        let segments = if closed { n } else { n - 1 };
+        let mut cut = Vec::with_capacity(2 * n);
+        if !closed {
+            cut.push(line[0]);
+        }
+        for i in 0..segments {
+            let (a, b) = (line[i], line[(i + 1) % n]);
+            cut.push(a.lerp(b, 0.25));
+            cut.push(a.lerp(b, 0.75));
+        }
+        if !closed {
+            cut.push(line[n - 1]);
+        }
+        line = cut;
+    }
+    line
+}
+
+/// A centripetal Catmull-Rom spline through every point, with `samples`
+/// points per segment. The centripetal form doesn't loop or overshoot where
+/// points bunch up. Open lines are extended by reflecting their ends.
+pub fn catmull_rom(points: &[Point2], samples: usize, closed: bool) -> Vec<Point2> {
+    let n = points.len();
+    if n < 3 || samples == 0 {
+        return points.to_vec();
+    }
+    let at = |i: isize| -> Point2 {
+        if closed {
+            points[i.rem_euclid(n as isize) as usize]
+        } else if i < 0 {
+            points[0] * 2.0 - points[1]
+        } else if i >= n as isize {
+            points[n - 1] * 2.0 - points[n - 2]
+        } else {
+            points[i as usize]
+        }
+    };
+    let segments = if closed { n } else { n - 1 };
+    let mut spline = Vec::with_capacity(segments * samples + 1);
+    for i in 0..segments as isize {
+        let p = [at(i - 1), at(i), at(i + 1), at(i + 2)];
+        // Knots spaced by the square root of the distance between points.
+        let mut t = [0.0; 4];
+        for k in 1..4 {
+            t[k] = t[k - 1] + p[k - 1].distance(p[k]).sqrt().max(1e-6);
+        }
+        for s in 0..samples {
+            let u = t[1] + (t[2] - t[1]) * s as f32 / samples as f32;
+            let lerp = |a: Point2, b: Point2, ta: f32, tb: f32| {
+                a * ((tb - u) / (tb - ta)) + b * ((u - ta) / (tb - ta))
+            };
+            let a1 = lerp(p[0], p[1], t[0], t[1]);
+            let a2 = lerp(p[1], p[2], t[1], t[2]);
+            let a3 = lerp(p[2], p[3], t[2], t[3]);
+            let b1 = lerp(a1, a2, t[0], t[2]);
+            let b2 = lerp(a2, a3, t[1], t[3]);
+            spline.push(lerp(b1, b2, t[1], t[2]));
+        }
+    }
+    spline.push(if closed { points[0] } else { points[n - 1] });
+    spline
+}
+
+// The uniform cubic B-spline basis at `t` in a span.
+fn basis(t: f32) -> [f32; 4] {
+    let u = 1.0 - t;
+    [
+        u * u * u / 6.0,
+        (3.0 * t * t * t - 6.0 * t * t + 4.0) / 6.0,
+        (-3.0 * t * t * t + 3.0 * t * t + 3.0 * t + 1.0) / 6.0,
+        t * t * t / 6.0,
+    ]
+}
+
+// The end control points are tripled so the spline starts and ends on them,
+// giving `controls + 1` spans.
+fn control(controls: &[Point2], k: usize) -> Point2 {
+    controls[(k.max(2) - 2).min(controls.len() - 1)]
+}
+
+// For the point `u` spans along, the controls it depends on and how much.
+fn weights(u: f32, spans: usize) -> impl Iterator<Item = (usize, f32)> {
+    let j = (u.floor() as usize).min(spans - 1);
+    let b = basis(u - j as f32);
+    (0..4).map(move |k| (j + k, b[k]))
+}
+
+/// Points along the cubic B-spline with `controls`, `samples` per span. It
+/// starts on the first control point and ends on the last.
+pub fn bspline(controls: &[Point2], samples: usize) -> Vec<Point2> {
+    if controls.len() < 2 {
+        return controls.to_vec();
+    }
+    let spans = controls.len() + 1;
+    let mut curve: Vec<Point2> = (0..=spans * samples)
+        .map(|i| {
+            weights(i as f32 / samples as f32, spans)
+                .map(|(k, w)| control(controls, k) * w)
+                .fold(pt2(0.0, 0.0), |a, b| a + b)
+        })
+        .collect();
+    // Exactly, despite rounding.
+    curve[0] = controls[0];
+    curve[spans * samples] = controls[controls.len() - 1];
+    curve
+}
+
+/// The same spline as `bspline` as exact cubic segments, one per span.
+pub fn bspline_segments(controls: &[Point2]) -> Vec<Segment> {
+    if controls.len() < 2 {
+        return vec![];
+    }
+    let mut segments = vec![Segment::MoveTo(controls[0])];
+    for j in 0..controls.len() + 1 {
+        let p: Vec<Point2> = (j..j + 4).map(|k| control(controls, k)).collect();
+        segments.push(Segment::CubicTo(
+            (p[1] * 2.0 + p[2]) / 3.0,
+            (p[1] + p[2] * 2.0) / 3.0,
+            (p[1] + p[2] * 4.0 + p[3]) / 6.0,
+        ));
+    }
+    segments
+}
+
+/// Least squares fit of a cubic B-spline with `controls` control points to
+/// the line, pinned to its ends. Draw it with `bspline` or
+/// `bspline_segments`.
+pub fn fit_bspline(points: &[Point2], controls: usize) -> Vec<Point2> {
+    assert!(controls >= 2, "a spline needs two control points");
+    let n = points.len();
+    assert!(n >= 2, "nothing to fit");
+    let m = controls.min(n);
+    if m == 2 {
+        return vec![points[0], points[n - 1]];
+    }
+    // Start with the points placed along the spline in proportion to the
+    // distance along the line, then move each to the nearest point of the
+    // last fit and fit again.
+    let spans = (m + 1) as f32;
+    let lengths = arc_lengths(points);
+    let total = lengths[n - 1].max(f32::EPSILON);
+    let mut params: Vec<f32> = lengths.iter().map(|s| s / total * spans).collect();
+    let mut fitted = fit_at(points, &params, m);
+    const SAMPLES: usize = 16;
+    for _ in 0..8 {
+        let curve = bspline(&fitted, SAMPLES);
+        for (u, &p) in params.iter_mut().zip(points) {
+            let nearest = (0..curve.len())
+                .min_by(|&i, &j| {
+                    let (a, b) = (curve[i].distance2(p), curve[j].distance2(p));
+                    a.partial_cmp(&b).unwrap()
+                })
+                .unwrap();
+            *u = nearest as f32 / SAMPLES as f32;
+            // Gauss-Newton steps towards the foot of the perpendicular.
+            for _ in 0..3 {
+                let (q, dq) = evaluate(&fitted, *u);
+                let d2 = dq.magnitude2();
+                if d2 > 0.0 {
+                    *u = (*u - (q - p).dot(dq) / d2).clamp(0.0, spans);
+                }
+            }
+        }
+        fitted = fit_at(points, &params, m);
+    }
+    fitted
+}
+
+// The point `u` spans along the spline and the derivative there.
+fn evaluate(controls: &[Point2], u: f32) -> (Point2, Vector2) {
+    let spans = controls.len() + 1;
+    let j = (u.floor() as usize).min(spans - 1);
+    let t = u - j as f32;
+    let b = basis(t);
+    let s = 1.0 - t;
+    let db = [
+        -s * s / 2.0,
+        (3.0 * t * t - 4.0 * t) / 2.0,
+        (-3.0 * t * t + 2.0 * t + 1.0) / 2.0,
+        t * t / 2.0,
+    ];
+    let (mut p, mut d) = (pt2(0.0, 0.0), vec2(0.0, 0.0));
+    for k in 0..4 {
+        let c = control(controls, j + k);
+        p += c * b[k];
+        d += c * db[k];
+    }
+    (p, d)
+}
+
+// The least squares fit with each point at the given distance in spans
+// along the spline. The free controls are all but the ends.
+fn fit_at(points: &[Point2], params: &[f32], m: usize) -> Vec<Point2> {
+    let (first, last) = (points[0], points[points.len() - 1]);
+    let spans = m + 1;
+    let free = m - 2;
+    let mut a = vec![vec![0.0f64; free]; free];
+    let mut bx = vec![0.0f64; free];
+    let mut by = vec![0.0f64; free];
+    for (p, &u) in points.iter().zip(params) {
+        let mut row = vec![0.0f64; free];
+        let mut rest = *p;
+        for (k, w) in weights(u, spans) {
+            let c = (k.max(2) - 2).min(m - 1);
+            if c == 0 {
+                rest -= first * w;
+            } else if c == m - 1 {
+                rest -= last * w;
+            } else {
+                row[c - 1] += w as f64;
+            }
+        }
+        for i in 0..free {
+            if row[i] == 0.0 {
+                continue;
+            }
+            for j in 0..free {
+                a[i][j] += row[i] * row[j];
+            }
+            bx[i] += row[i] * rest.x as f64;
+            by[i] += row[i] * rest.y as f64;
+        }
+    }
+    // A tiny ridge keeps controls that no point pulls on from making the
+    // system singular.
+    for (i, row) in a.iter_mut().enumerate() {
+        row[i] += 1e-6;
+    }
+    let xs = solve(a.clone(), bx);
+    let ys = solve(a, by);
+    let mut fitted = vec![first];
+    fitted.extend(xs.iter().zip(&ys).map(|(&x, &y)| pt2(x as f32, y as f32)));
+    fitted.push(last);
+    fitted
+}
+
+// Gaussian elimination with partial pivoting.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[i][col].abs().partial_cmp(&a[j][col].abs()).unwrap())
+            .unwrap();
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..n {
+            let f = a[row][col] / a[col][col];
+            if f == 0.0 {
+                continue;
+            }
+            let (above, below) = a.split_at_mut(row);
+            for (x, y) in below[0][col..].iter_mut().zip(&above[col][col..]) {
+                *x -= f * y;
+            }
+            b[row] -= f * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    x
+}
+
+// The distance from `p` to the segment `ab`.
+pub(crate) fn segment_distance(p: Point2, a: Point2, b: Point2) -> f32 {
+    let ab = b - a;
+    let len2 = ab.magnitude2();
+    if len2 == 0.0 {
+        return p.distance(a);
+    }
+    let t = ((p - a).dot(ab) / len2).clamp(0.0, 1.0);
+    p.distance(a + ab * t)
+}
+
+/// Ramer–Douglas–Peucker, keeps the ends and the fewest points in between
+/// so that no dropped point is more than `epsilon` from the line.
+pub fn rdp(points: &[Point2], epsilon: f32) -> Vec<Point2> {
+    let n = points.len();
+    if n < 3 {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[n - 1] = true;
+    let mut stack = vec![(0, n - 1)];
+    while let Some((i, j)) = stack.pop() {
+        let (mut far, mut worst) = (0, 0.0);
+        for k in i + 1..j {
+            let d = segment_distance(points[k], points[i], points[j]);
+            if d > worst {
+                far = k;
+                worst = d;
+            }
+        }
+        if worst > epsilon {
+            keep[far] = true;
+            stack.push((i, far));
+            stack.push((far, j));
+        }
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter(|(_, k)| *k)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+/// Visvalingam–Whyatt, drops the point making the smallest triangle with its
+/// neighbours until every triangle is at least `area`. Keeps the ends, and
+/// rounds off shapes more gently than `rdp`.
+pub fn visvalingam(points: &[Point2], area: f32) -> Vec<Point2> {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    let n = points.len();
+    if n < 3 {
+        return points.to_vec();
+    }
+    let triangle = |a: usize, b: usize, c: usize| {
+        ((points[b] - points[a]).perp_dot(points[c] - points[a]) / 2.0).abs()
+    };
+    // A linked list of the points still in, and each point's area when it
+    // was last queued so stale entries can be skipped.
+    let mut prev: Vec<usize> = (0..n).map(|i| i.saturating_sub(1)).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1).min(n - 1)).collect();
+    let mut areas = vec![f32::INFINITY; n];
+    let mut heap = BinaryHeap::new();
+    // Areas are non-negative, so their bits order the same way they do.
+    for (i, a) in areas.iter_mut().enumerate().take(n - 1).skip(1) {
+        *a = triangle(i - 1, i, i + 1);
+        heap.push(Reverse((a.to_bits(), i)));
+    }
+    let mut removed = vec![false; n];
+    while let Some(Reverse((bits, i))) = heap.pop() {
+        if removed[i] || bits != areas[i].to_bits() {
+            continue;
+        }
+        if areas[i] >= area {
+            break;
+        }
+        removed[i] = true;
+        let (a, b) = (prev[i], next[i]);
+        next[a] = b;
+        prev[b] = a;
+        // A neighbour's area never drops below the one just removed, so the
+        // order of removal follows the areas.
+        for &k in &[a, b] {
+            if k != 0 && k != n - 1 {
+                areas[k] = triangle(prev[k], k, next[k]).max(areas[i]);
+                heap.push(Reverse((areas[k].to_bits(), k)));
+            }
+        }
+    }
+    points
+        .iter()
+        .zip(removed)
+        .filter(|(_, r)| !r)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(p: Point2, q: Point2, tolerance: f32) -> bool {
+        p.distance(q) < tolerance
+    }
+
+    fn circle(n: usize, r: f32) -> Vec<Point2> {
+        (0..=n)
+            .map(|i| {
+                let a = i as f32 / n as f32 * TAU;
+                pt2(a.cos(), a.sin()) * r
+            })
+            .collect()
+    }
+
+    #[test]
+    fn measures_length_position_and_curvature() {
+        let line = vec![pt2(0.0, 0.0), pt2(3.0, 0.0), pt2(3.0, 4.0)];
+        assert_eq!(length(&line), 7.0);
+        assert_eq!(arc_lengths(&line), vec![0.0, 3.0, 7.0]);
+        assert!(close(point_at(&line, 5.0), pt2(3.0, 2.0), 1e-6));
+        assert_eq!(point_at(&line, 10.0), pt2(3.0, 4.0));
+        let k = curvature(&circle(100, 20.0));
+        assert!(k[1..100].iter().all(|k| (k - 1.0 / 20.0).abs() < 1e-3));
+        assert!(curvature(&[pt2(0.0, 0.0), pt2(1.0, 0.0), pt2(2.0, -1.0)])[1] < 0.0);
+    }
+
+    #[test]
+    fn resamples_evenly() {
+        let line = vec![pt2(0.0, 0.0), pt2(10.0, 0.0), pt2(10.0, 10.5)];
+        let points = resample(&line, 2.0);
+        assert_eq!(points.len(), 11);
+        assert_eq!(points[10], pt2(10.0, 10.5));
+        let steps: Vec<f32> = points.windows(2).map(|w| w[0].distance(w[1])).collect();
+        assert!(steps.iter().all(|d| *d < 2.05 + 1e-3));
+        assert!(close(points[5], pt2(10.0, 0.25), 1e-4));
+    }
+
+    #[test]
+    fn smoothing_keeps_ends_and_points() {
+        let zigzag: Vec<Point2> = (0..6)
+            .map(|i| pt2(i as f32 * 10.0, if i % 2 == 0 { 0.0 } else { 10.0 }))
+            .collect();
+        let cut = chaikin(&zigzag, 2, false);
+        assert_eq!(cut.len(), 2 + 2 * (2 + 2 * 5 - 1));
+        assert_eq!((cut[0], cut[cut.len() - 1]), (zigzag[0], zigzag[5]));
+        assert_eq!(chaikin(&zigzag, 1, true).len(), 12);
+        let spline = catmull_rom(&zigzag, 8, false);
+        assert_eq!(spline.len(), 5 * 8 + 1);
+        for (i, p) in zigzag.iter().enumerate() {
+            assert!(close(spline[i * 8], *p, 1e-4));
+        }
+        let ring = catmull_rom(&zigzag, 4, true);
+        assert_eq!(ring[ring.len() - 1], zigzag[0]);
+    }
+
+    #[test]
+    fn bspline_fit_and_segments_agree() {
+        let controls = vec![
+            pt2(0.0, 0.0),
+            pt2(50.0, 80.0),
+            pt2(100.0, -40.0),
+            pt2(150.0, 60.0),
+            pt2(200.0, 0.0),
+        ];
+        let curve = bspline(&controls, 40);
+        assert_eq!(
+            (curve[0], curve[curve.len() - 1]),
+            (controls[0], controls[4])
+        );
+        // The controls come back given where each point lies on the curve.
+        let params: Vec<f32> = (0..curve.len()).map(|i| i as f32 / 40.0).collect();
+        let exact = fit_at(&curve, &params, 5);
+        assert!(exact
+            .iter()
+            .zip(&controls)
+            .all(|(p, q)| close(*p, *q, 1e-3)));
+        // Without that a few more controls follow it closely.
+        let fitted = fit_bspline(&resample(&curve, 1.0), 8);
+        let refit = bspline(&fitted, 40);
+        let worst = curve
+            .iter()
+            .map(|p| {
+                refit
+                    .iter()
+                    .map(|q| p.distance(*q))
+                    .fold(f32::MAX, f32::min)
+            })
+            .fold(0.0, f32::max);
+        assert!(worst < 2.0, "{}", worst);
+        let segments = bspline_segments(&controls);
+        assert_eq!(segments.len(), 1 + 6);
+        match segments[3] {
+            Segment::CubicTo(_, _, end) => assert!(close(end, curve[3 * 40], 1e-3)),
+            _ => panic!("expected a cubic"),
+        }
+    }
+
+    #[test]
+    fn simplification_keeps_the_shape() {
+        let mut line: Vec<Point2> = (0..=100).map(|i| pt2(i as f32, 0.0)).collect();
+        line[50].y = 0.01;
+        line.push(pt2(100.0, 50.0));
+        assert_eq!(
+            rdp(&line, 0.1),
+            vec![pt2(0.0, 0.0), pt2(100.0, 0.0), pt2(100.0, 50.0)]
+        );
+        assert!(rdp(&line, 0.001).contains(&line[50]));
+        let smooth = visvalingam(&line, 1.0);
+        assert_eq!(
+            smooth,
+            vec![pt2(0.0, 0.0), pt2(100.0, 0.0), pt2(100.0, 50.0)]
+        );
+        let round = visvalingam(&circle(360, 100.0), 5.0);
+        assert!(round.len() < 80 && round.len() > 30, "{}", round.len());
+    }
+}
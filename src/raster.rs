@@ -0,0 +1,517 @@
+// Render a canvas with tiny-skia, no window or gpu required. A `Recorder`
+// collects the frames of a sketch and saves them as a png or an animated gif.
+//
+// Shapes inside a mask are drawn onto a transparent layer, which is faded by
+// the mask and drawn down onto the layer below when the mask's scope ends.
+
+use nannou::color::{LinSrgba, Srgba};
+use nannou::geom::pt2;
+use nannou::image::{imageops, GrayImage, Rgba, RgbaImage};
+use std::fs::File;
+use std::path::Path;
+use tiny_skia::{
+    Canvas as SkCanvas, Color, FillRule as SkFillRule, LineCap, LineJoin, Paint, PathBuilder,
+    Pixmap, PixmapPaint, Stroke as SkStroke, Transform,
+};
+
+use crate::canvas::{Canvas, Cap, FillRule, Join, Mask, MaskSource, Segment, Shape};
+
+fn sk_color(c: LinSrgba) -> Color {
+    let c: Srgba = Srgba::from_linear(c);
+    Color::from_rgba(
+        c.red.clamp(0.0, 1.0),
+        c.green.clamp(0.0, 1.0),
+        c.blue.clamp(0.0, 1.0),
+        c.alpha.clamp(0.0, 1.0),
+    )
+    .unwrap()
+}
+
+fn paint(c: LinSrgba) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(sk_color(c));
+    paint.anti_alias = true;
+    paint
+}
+
+fn sk_path(segments: &[Segment]) -> Option<tiny_skia::Path> {
+    let mut pb = PathBuilder::new();
+    for s in segments {
+        match *s {
+            Segment::MoveTo(p) => pb.move_to(p.x, p.y),
+            Segment::LineTo(p) => pb.line_to(p.x, p.y),
+            Segment::CubicTo(c1, c2, p) => pb.cubic_to(c1.x, c1.y, c2.x, c2.y, p.x, p.y),
+            Segment::Close => pb.close(),
+        }
+    }
+    pb.finish()
+}
+
+fn sk_rule(rule: FillRule) -> SkFillRule {
+    match rule {
+        FillRule::NonZero => SkFillRule::Winding,
+        FillRule::EvenOdd => SkFillRule::EvenOdd,
+    }
+}
+
+// nannou puts the origin at the center with y pointing up.
+fn sk_transform(canvas: &Canvas, scale: f32) -> Transform {
+    Transform::from_row(
+        scale,
+        0.0,
+        0.0,
+        -scale,
+        canvas.width * scale / 2.0,
+        canvas.height * scale / 2.0,
+    )
+    .unwrap()
+}
+
+fn draw_shape(sk: &mut SkCanvas, shape: &Shape) {
+    let path = match sk_path(&shape.segments) {
+        Some(path) => path,
+        None => return,
+    };
+    if let Some(fill) = shape.fill {
+        sk.fill_path(&path, &paint(fill), sk_rule(shape.rule));
+    }
+    if let Some(stroke) = shape.scaled_stroke() {
+        let sk_stroke = SkStroke {
+            width: stroke.weight,
+            line_cap: match stroke.cap {
+                Cap::Butt => LineCap::Butt,
+                Cap::Round => LineCap::Round,
+                Cap::Square => LineCap::Square,
+            },
+            line_join: match stroke.join {
+                Join::Miter => LineJoin::Miter,
+                Join::Round => LineJoin::Round,
+                Join::Bevel => LineJoin::Bevel,
+            },
+            ..SkStroke::default()
+        };
+        sk.stroke_path(&path, &paint(stroke.color), &sk_stroke);
+    }
+}
+
+/// Draw `canvas` onto `pixmap`, scaling canvas units by `scale`.
+pub fn render(canvas: &Canvas, pixmap: &mut Pixmap, scale: f32) {
+    if let Some(bg) = canvas.background_color() {
+        pixmap.fill(sk_color(bg));
+    }
+    let ts = sk_transform(canvas, scale);
+    // The open layers with the mask scope each belongs to.
+    let mut layers: Vec<(usize, Pixmap)> = vec![];
+    for shape in canvas.shapes() {
+        let chain = canvas.mask_chain(shape.mask);
+        let keep = layers
+            .iter()
+            .zip(&chain)
+            .take_while(|((i, _), j)| i == *j)
+            .count();
+        while layers.len() > keep {
+            merge_layer(canvas, scale, pixmap, &mut layers);
+        }
+        for &i in &chain[keep..] {
+            let layer = Pixmap::new(pixmap.width(), pixmap.height()).unwrap();
+            layers.push((i, layer));
+        }
+        let target = match layers.last_mut() {
+            Some((_, layer)) => layer,
+            None => &mut *pixmap,
+        };
+        let mut sk = SkCanvas::from(target.as_mut());
+        sk.set_transform(ts);
+        draw_shape(&mut sk, shape);
+    }
+    while !layers.is_empty() {
+        merge_layer(canvas, scale, pixmap, &mut layers);
+    }
+}
+
+// Fade the top layer by its mask and draw it onto the one below.
+fn merge_layer(canvas: &Canvas, scale: f32, base: &mut Pixmap, layers: &mut Vec<(usize, Pixmap)>) {
+    let (i, mut layer) = layers.pop().unwrap();
+    let mask = &canvas.masks()[i].mask;
+    let alpha = mask_alpha(canvas, mask, layer.width(), layer.height(), scale);
+    for (px, &a) in layer.data_mut().chunks_mut(4).zip(&alpha) {
+        for c in px {
+            *c = ((*c as u32 * a as u32 + 127) / 255) as u8;
+        }
+    }
+    let target = match layers.last_mut() {
+        Some((_, layer)) => layer,
+        None => base,
+    };
+    let mut sk = SkCanvas::from(target.as_mut());
+    sk.draw_pixmap(0, 0, layer.as_ref(), &PixmapPaint::default());
+}
+
+// How much `mask` lets through at each pixel, row by row.
+fn mask_alpha(canvas: &Canvas, mask: &Mask, width: u32, height: u32, scale: f32) -> Vec<u8> {
+    let mut values: Vec<f32> = match &mask.source {
+        MaskSource::Path { segments, rule, .. } => {
+            let mut pixmap = Pixmap::new(width, height).unwrap();
+            if let Some(path) = sk_path(segments) {
+                let mut sk = SkCanvas::from(pixmap.as_mut());
+                sk.set_transform(sk_transform(canvas, scale));
+                sk.fill_path(
+                    &path,
+                    &paint(LinSrgba::new(1.0, 1.0, 1.0, 1.0)),
+                    sk_rule(*rule),
+                );
+            }
+            pixmap.pixels().iter().map(|p| p.alpha() as f32).collect()
+        }
+        source => (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let p = pt2(
+                    (x as f32 + 0.5) / scale - canvas.width / 2.0,
+                    canvas.height / 2.0 - (y as f32 + 0.5) / scale,
+                );
+                source.value(p) * 255.0
+            })
+            .collect(),
+    };
+    if mask.feather > 0.0 {
+        blur(
+            &mut values,
+            width as usize,
+            height as usize,
+            mask.sigma() * scale,
+        );
+    }
+    values
+        .into_iter()
+        .map(|v| {
+            let v = v.round().clamp(0.0, 255.0) as u8;
+            if mask.inverse {
+                255 - v
+            } else {
+                v
+            }
+        })
+        .collect()
+}
+
+/// How much `mask` lets through across the canvas, as rendered at `scale`.
+pub fn mask_image(canvas: &Canvas, mask: &Mask, scale: f32) -> GrayImage {
+    let pixmap = new_pixmap(canvas, scale);
+    let (w, h) = (pixmap.width(), pixmap.height());
+    GrayImage::from_raw(w, h, mask_alpha(canvas, mask, w, h, scale)).unwrap()
+}
+
+// A gaussian blur made of three box blurs, with the edges extended outwards.
+fn blur(values: &mut [f32], width: usize, height: usize, sigma: f32) {
+    let mut column = vec![0.0; height];
+    for r in box_radii(sigma, 3) {
+        for row in values.chunks_mut(width) {
+            box_blur(row, r);
+        }
+        for x in 0..width {
+            for (y, c) in column.iter_mut().enumerate() {
+                *c = values[y * width + x];
+            }
+            box_blur(&mut column, r);
+            for (y, c) in column.iter().enumerate() {
+                values[y * width + x] = *c;
+            }
+        }
+    }
+}
+
+// Radii of `n` box blurs that together approximate a gaussian.
+fn box_radii(sigma: f32, n: usize) -> Vec<usize> {
+    let n = n as f32;
+    let ideal = (12.0 * sigma * sigma / n + 1.0).sqrt();
+    let mut lower = ideal.floor();
+    if lower % 2.0 == 0.0 {
+        lower -= 1.0;
+    }
+    let lower = lower.max(1.0);
+    let m = ((12.0 * sigma * sigma - n * lower * lower - 4.0 * n * lower - 3.0 * n)
+        / (-4.0 * lower - 4.0))
+        .round();
+    (0..n as usize)
+        .map(|i| {
+            let w = if (i as f32) < m { lower } else { lower + 2.0 };
+            (w as usize - 1) / 2
+        })
+        .collect()
+}
+
+fn box_blur(line: &mut [f32], r: usize) {
+    let n = line.len();
+    if r == 0 || n == 0 {
+        return;
+    }
+    let (first, last) = (line[0], line[n - 1]);
+    let mut prefix = vec![0.0; n + 1];
+    for i in 0..n {
+        prefix[i + 1] = prefix[i] + line[i];
+    }
+    for (i, v) in line.iter_mut().enumerate() {
+        let lo = i as isize - r as isize;
+        let hi = i + r;
+        let mut sum = prefix[hi.min(n - 1) + 1] - prefix[lo.max(0) as usize];
+        sum += (-lo).max(0) as f32 * first;
+        sum += hi.saturating_sub(n - 1) as f32 * last;
+        *v = sum / (2 * r + 1) as f32;
+    }
+}
+
+fn new_pixmap(canvas: &Canvas, scale: f32) -> Pixmap {
+    let w = (canvas.width * scale).round().max(1.0) as u32;
+    let h = (canvas.height * scale).round().max(1.0) as u32;
+    Pixmap::new(w, h).expect("failed to allocate pixmap")
+}
+
+/// Convert tiny-skia's premultiplied pixels into an image.
+pub fn to_image(pixmap: &Pixmap) -> RgbaImage {
+    let mut data = Vec::with_capacity(pixmap.data().len());
+    for p in pixmap.pixels() {
+        let c = p.demultiply();
+        data.extend_from_slice(&[c.red(), c.green(), c.blue(), c.alpha()]);
+    }
+    RgbaImage::from_raw(pixmap.width(), pixmap.height(), data).unwrap()
+}
+
+/// A pixmap holding `image`, to render a canvas over.
+pub fn from_image(image: &RgbaImage) -> Pixmap {
+    let mut pixmap = Pixmap::new(image.width(), image.height()).expect("failed to allocate pixmap");
+    for (p, q) in pixmap.data_mut().chunks_exact_mut(4).zip(image.pixels()) {
+        let [r, g, b, a] = q.0;
+        let m = |c: u8| ((c as u32 * a as u32 + 127) / 255) as u8;
+        p.copy_from_slice(&[m(r), m(g), m(b), a]);
+    }
+    pixmap
+}
+
+/// `image` scaled down to fit in a `size` pixel square.
+pub fn thumbnail(image: &RgbaImage, size: u32) -> RgbaImage {
+    let scale = size as f32 / image.width().max(image.height()) as f32;
+    let (w, h) = (
+        (image.width() as f32 * scale).round().max(1.0) as u32,
+        (image.height() as f32 * scale).round().max(1.0) as u32,
+    );
+    imageops::thumbnail(image, w, h)
+}
+
+/// `images` in rows of `columns` on black, each centred in a cell the size of
+/// the largest, with a `gap` pixel margin around every cell.
+pub fn contact_sheet(images: &[RgbaImage], columns: u32, gap: u32) -> RgbaImage {
+    assert!(
+        !images.is_empty() && columns > 0,
+        "a contact sheet needs images and columns"
+    );
+    let cell_w = images.iter().map(|i| i.width()).max().unwrap();
+    let cell_h = images.iter().map(|i| i.height()).max().unwrap();
+    let rows = (images.len() as u32).div_ceil(columns);
+    let columns = columns.min(images.len() as u32);
+    let mut sheet = RgbaImage::from_pixel(
+        columns * (cell_w + gap) + gap,
+        rows * (cell_h + gap) + gap,
+        Rgba([0, 0, 0, 255]),
+    );
+    for (i, image) in images.iter().enumerate() {
+        let (col, row) = (i as u32 % columns, i as u32 / columns);
+        let x = gap + col * (cell_w + gap) + (cell_w - image.width()) / 2;
+        let y = gap + row * (cell_h + gap) + (cell_h - image.height()) / 2;
+        imageops::overlay(&mut sheet, image, x, y);
+    }
+    sheet
+}
+
+/// Render `canvas` to a png at `path`.
+pub fn save_png<P: AsRef<Path>>(canvas: &Canvas, path: P, scale: f32) {
+    let mut pixmap = new_pixmap(canvas, scale);
+    render(canvas, &mut pixmap, scale);
+    to_image(&pixmap)
+        .save(path)
+        .expect("failed to save png image");
+}
+
+/// Renders frames the way a nannou window would, each canvas is drawn over the
+/// previous one unless it sets a background, and keeps the captured frames.
+pub struct Recorder {
+    max_size: f32,
+    scale: f32,
+    pixmap: Option<Pixmap>,
+    frames: Vec<RgbaImage>,
+    /// Time between frames of a gif in milliseconds.
+    pub delay: u16,
+}
+
+impl Recorder {
+    /// Scale frames down so that neither side is longer than `max_size`.
+    pub fn new(max_size: u32) -> Self {
+        Recorder {
+            max_size: max_size as f32,
+            scale: 1.0,
+            pixmap: None,
+            frames: vec![],
+            delay: 1000 / 30,
+        }
+    }
+
+    /// Draw `canvas` without capturing a frame.
+    pub fn draw(&mut self, canvas: &Canvas) {
+        if self.pixmap.is_none() {
+            self.scale = (self.max_size / canvas.width.max(canvas.height)).min(1.0);
+            self.pixmap = Some(new_pixmap(canvas, self.scale));
+        }
+        render(canvas, self.pixmap.as_mut().unwrap(), self.scale);
+    }
+
+    /// Capture what has been drawn so far as a frame.
+    pub fn capture(&mut self) {
+        let pixmap = self.pixmap.as_ref().expect("nothing has been drawn");
+        self.frames.push(to_image(pixmap));
+    }
+
+    /// Draw `canvas` and capture it.
+    pub fn frame(&mut self, canvas: &Canvas) {
+        self.draw(canvas);
+        self.capture();
+    }
+
+    pub fn frames(&self) -> &[RgbaImage] {
+        &self.frames
+    }
+
+    pub fn is_animated(&self) -> bool {
+        self.frames.len() > 1
+    }
+
+    /// Save a single frame as a png, or every frame as a looping gif.
+    pub fn save<P: AsRef<Path>>(&self, path: P) {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).expect("failed to create output directory");
+        }
+        if !self.is_animated() {
+            let frame = self.frames.first().expect("no frames were captured");
+            frame.save(path).expect("failed to save png image");
+            return;
+        }
+        let first = &self.frames[0];
+        let (w, h) = (first.width() as u16, first.height() as u16);
+        let file = File::create(path).expect("failed to create gif");
+        let mut encoder = gif::Encoder::new(file, w, h, &[]).unwrap();
+        encoder.set_repeat(gif::Repeat::Infinite).unwrap();
+        for image in &self.frames {
+            let mut pixels = image.clone().into_raw();
+            let mut frame = gif::Frame::from_rgba_speed(w, h, &mut pixels, 10);
+            frame.delay = (self.delay / 10).max(2);
+            encoder
+                .write_frame(&frame)
+                .expect("failed to write gif frame");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::Channel;
+    use nannou::prelude::*;
+
+    #[test]
+    fn renders_with_y_up() {
+        let mut canvas = Canvas::new(20.0, 20.0);
+        canvas.background(BLACK);
+        canvas.rect(pt2(5.0, 5.0), 10.0, 10.0).color(WHITE);
+        let mut pixmap = Pixmap::new(20, 20).unwrap();
+        render(&canvas, &mut pixmap, 1.0);
+        let image = to_image(&pixmap);
+        assert_eq!(image.get_pixel(15, 5).0, [255, 255, 255, 255]);
+        assert_eq!(image.get_pixel(5, 15).0, [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn contact_sheet_lays_out_cells() {
+        let wide = RgbaImage::from_pixel(40, 20, Rgba([255, 0, 0, 255]));
+        let tall = RgbaImage::from_pixel(10, 30, Rgba([0, 0, 255, 255]));
+        let sheet = contact_sheet(&[wide.clone(), tall, wide], 2, 4);
+        // Two columns and two rows of 40 by 30 cells.
+        assert_eq!(sheet.dimensions(), (2 * 44 + 4, 2 * 34 + 4));
+        // The wide image is centred down its cell, the tall one across.
+        assert_eq!(sheet.get_pixel(4, 8).0, [0, 0, 0, 255]);
+        assert_eq!(sheet.get_pixel(4, 9).0, [255, 0, 0, 255]);
+        assert_eq!(sheet.get_pixel(48 + 15, 4).0, [0, 0, 255, 255]);
+        assert_eq!(sheet.get_pixel(48 + 14, 4).0, [0, 0, 0, 255]);
+        assert_eq!(sheet.get_pixel(4, 38 + 5).0, [255, 0, 0, 255]);
+        assert_eq!(thumbnail(&sheet, 46).dimensions(), (46, 36));
+    }
+
+    #[test]
+    fn recorder_scales_and_accumulates() {
+        let mut rec = Recorder::new(10);
+        let mut canvas = Canvas::new(40.0, 20.0);
+        canvas.background(BLACK);
+        rec.frame(&canvas);
+        let mut canvas = Canvas::new(40.0, 20.0);
+        canvas.rect(pt2(-10.0, 0.0), 20.0, 20.0).color(WHITE);
+        rec.frame(&canvas);
+        assert!(rec.is_animated());
+        let last = &rec.frames()[1];
+        assert_eq!((last.width(), last.height()), (10, 5));
+        assert_eq!(last.get_pixel(1, 2).0, [255, 255, 255, 255]);
+        assert_eq!(last.get_pixel(8, 2).0, [0, 0, 0, 255]);
+    }
+
+    fn masked(mask: Mask) -> RgbaImage {
+        let mut canvas = Canvas::new(40.0, 40.0);
+        canvas.background(BLACK);
+        canvas.with_mask(mask, |c| {
+            c.rect(pt2(0.0, 0.0), 40.0, 40.0).color(WHITE);
+        });
+        let mut pixmap = Pixmap::new(40, 40).unwrap();
+        render(&canvas, &mut pixmap, 1.0);
+        to_image(&pixmap)
+    }
+
+    #[test]
+    fn masks_clip_and_invert() {
+        let image = masked(Mask::circle(pt2(0.0, 0.0), 10.0));
+        assert_eq!(image.get_pixel(20, 20).0, [255, 255, 255, 255]);
+        assert_eq!(image.get_pixel(2, 2).0, [0, 0, 0, 255]);
+        let image = masked(Mask::circle(pt2(0.0, 0.0), 10.0).inverse());
+        assert_eq!(image.get_pixel(20, 20).0, [0, 0, 0, 255]);
+        assert_eq!(image.get_pixel(2, 2).0, [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn feathered_masks_fade_at_the_edge() {
+        let image = masked(Mask::rect(pt2(-10.0, 0.0), 20.0, 40.0).feather(10.0));
+        let row: Vec<u8> = (0..40).map(|x| image.get_pixel(x, 20).0[0]).collect();
+        assert_eq!(row[0], 255);
+        assert_eq!(row[39], 0);
+        assert!(row[20] > 64 && row[20] < 192);
+        assert!(row.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn image_masks_and_nested_masks() {
+        let luma = RgbaImage::from_fn(4, 4, |x, y| {
+            let v = if x < 2 && y < 2 { 255 } else { 0 };
+            nannou::image::Rgba([v, v, v, 255])
+        });
+        let mask = Mask::image(luma, Rect::from_w_h(40.0, 40.0), Channel::Luminance);
+        let mut canvas = Canvas::new(40.0, 40.0);
+        canvas.background(BLACK);
+        canvas.with_mask(mask, |c| {
+            c.with_mask(Mask::rect(pt2(-15.0, 15.0), 10.0, 10.0).inverse(), |c| {
+                c.rect(pt2(0.0, 0.0), 40.0, 40.0).color(WHITE);
+            });
+        });
+        let mut pixmap = Pixmap::new(40, 40).unwrap();
+        render(&canvas, &mut pixmap, 1.0);
+        let image = to_image(&pixmap);
+        // The top left quarter of the image is white, less the corner.
+        assert_eq!(image.get_pixel(12, 12).0, [255, 255, 255, 255]);
+        assert_eq!(image.get_pixel(2, 2).0, [0, 0, 0, 255]);
+        assert_eq!(image.get_pixel(35, 35).0, [0, 0, 0, 255]);
+    }
+}
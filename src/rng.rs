@@ -0,0 +1,63 @@
+// A seedable random number generator shared by the sketches. Sketches use
+// these functions in place of nannou's `random` and `random_range` so that a
+// run started with `--seed` (and every gallery render) is reproducible.
+
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::{Distribution, Standard};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// Restart the generator from `seed`.
+pub fn seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+/// Run `f` with the generator, e.g. to sample from a `rand_distr`
+/// distribution.
+pub fn with_rng<T>(f: impl FnOnce(&mut StdRng) -> T) -> T {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+pub fn random<T>() -> T
+where
+    Standard: Distribution<T>,
+{
+    with_rng(|rng| rng.gen())
+}
+
+/// A value in `[min, max)`, the bounds may be given in either order.
+pub fn random_range<T>(min: T, max: T) -> T
+where
+    T: PartialOrd + SampleUniform,
+{
+    if min == max {
+        return min;
+    }
+    if min < max {
+        with_rng(|rng| rng.gen_range(min..max))
+    } else {
+        with_rng(|rng| rng.gen_range(max..min))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_runs_repeat() {
+        seed(7);
+        let a: Vec<f32> = (0..5).map(|_| random_range(0.0, 10.0)).collect();
+        seed(7);
+        let b: Vec<f32> = (0..5).map(|_| random_range(0.0, 10.0)).collect();
+        assert_eq!(a, b);
+        assert!(a.iter().all(|x| (0.0..10.0).contains(x)));
+        assert_eq!(random_range(3, 3), 3);
+        assert!((-2..1).contains(&random_range(1, -2)));
+    }
+}
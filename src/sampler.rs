@@ -0,0 +1,330 @@
+// Reading colors out of an image for sketches that are driven by one. The
+// image is laid over a rectangle in nannou space, by default centred on the
+// origin one pixel to a unit, and sampled at any point with y up. Points off
+// the image take the nearest edge pixel.
+//
+// Rules draw streaks of the image's colors, a ray from each point along a
+// path through the image in the color found there. A style is a list of
+// rules drawn in turn, over the image in a window or headless with `render`.
+
+use nannou::color::{IntoLinSrgba, LinSrgba};
+use nannou::image::{self, RgbaImage};
+use nannou::prelude::*;
+use std::path::Path;
+
+use crate::canvas::Canvas;
+use crate::raster;
+
+pub struct Sampler {
+    pub image: RgbaImage,
+    /// Where the image lies in nannou space.
+    pub bounds: Rect,
+}
+
+impl Sampler {
+    pub fn new(image: RgbaImage) -> Self {
+        let bounds = Rect::from_w_h(image.width() as f32, image.height() as f32);
+        Sampler { image, bounds }
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> Self {
+        let image = image::open(path.as_ref())
+            .unwrap_or_else(|e| panic!("failed to open {}: {}", path.as_ref().display(), e));
+        Sampler::new(image.to_rgba8())
+    }
+
+    /// Stretch the image over `bounds`.
+    pub fn fit(mut self, bounds: Rect) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    /// Where `p` falls on the image, in pixels from the top left corner.
+    /// Pixel `(i, j)` covers `i..i + 1` across and `j..j + 1` down.
+    pub fn to_image(&self, p: Point2) -> Point2 {
+        let (w, h) = self.image.dimensions();
+        let b = self.bounds;
+        pt2(
+            (p.x - b.left()) / b.w() * w as f32,
+            (b.top() - p.y) / b.h() * h as f32,
+        )
+    }
+
+    /// The point in nannou space at `q` on the image.
+    pub fn to_space(&self, q: Point2) -> Point2 {
+        let (w, h) = self.image.dimensions();
+        let b = self.bounds;
+        pt2(
+            b.left() + q.x / w as f32 * b.w(),
+            b.top() - q.y / h as f32 * b.h(),
+        )
+    }
+
+    /// The pixel at column `x` and row `y`, the nearest edge pixel when off
+    /// the image.
+    pub fn pixel(&self, x: i64, y: i64) -> LinSrgba {
+        let (w, h) = self.image.dimensions();
+        let x = x.clamp(0, w as i64 - 1) as u32;
+        let y = y.clamp(0, h as i64 - 1) as u32;
+        let [r, g, b, a] = self.image.get_pixel(x, y).0;
+        srgba8(r, g, b, a).into_lin_srgba()
+    }
+
+    /// The color of the pixel under `p`.
+    pub fn nearest(&self, p: Point2) -> LinSrgba {
+        let q = self.to_image(p);
+        self.pixel(q.x.floor() as i64, q.y.floor() as i64)
+    }
+
+    /// The color at `p` blended between the four nearest pixel centres.
+    pub fn bilinear(&self, p: Point2) -> LinSrgba {
+        let q = self.to_image(p) - vec2(0.5, 0.5);
+        let (x, y) = (q.x.floor(), q.y.floor());
+        let (fx, fy) = (q.x - x, q.y - y);
+        let (x, y) = (x as i64, y as i64);
+        let mix = |a: LinSrgba, b: LinSrgba, t: f32| {
+            lin_srgba(
+                a.red + (b.red - a.red) * t,
+                a.green + (b.green - a.green) * t,
+                a.blue + (b.blue - a.blue) * t,
+                a.alpha + (b.alpha - a.alpha) * t,
+            )
+        };
+        let top = mix(self.pixel(x, y), self.pixel(x + 1, y), fx);
+        let bottom = mix(self.pixel(x, y + 1), self.pixel(x + 1, y + 1), fx);
+        mix(top, bottom, fy)
+    }
+
+    /// Relative luminance at `p`, 0 for black and 1 for white.
+    pub fn luminance(&self, p: Point2) -> f32 {
+        let c = self.bilinear(p);
+        0.2126 * c.red + 0.7152 * c.green + 0.0722 * c.blue
+    }
+
+    /// Hue at `p` in degrees from red, 0 for greys.
+    pub fn hue(&self, p: Point2) -> f32 {
+        let c = Srgb::from_linear(self.bilinear(p).color);
+        let (r, g, b) = (c.red, c.green, c.blue);
+        let max = r.max(g).max(b);
+        let d = max - r.min(g).min(b);
+        if d <= 0.0 {
+            return 0.0;
+        }
+        let h = if max == r {
+            (g - b) / d
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (h * 60.0).rem_euclid(360.0)
+    }
+}
+
+// Rules ------------------------------------------------------------------------
+
+/// The path of points a rule samples, about a pixel apart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Along {
+    /// The diagonal from the top left corner to the bottom right.
+    Falling,
+    /// The diagonal from the bottom left corner to the top right.
+    Rising,
+    /// A circle about the centre, its radius a fraction of the height.
+    Circle(f32),
+}
+
+/// Where a ray runs from its point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ray {
+    Down,
+    Up,
+    Left,
+    Right,
+    /// To the centre.
+    In,
+    /// Away from the centre to eight times as far again.
+    Out,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub along: Along,
+    pub rays: Vec<Ray>,
+    pub weight: f32,
+}
+
+impl Rule {
+    pub fn new(along: Along) -> Self {
+        Rule {
+            along,
+            rays: vec![],
+            weight: 1.0,
+        }
+    }
+
+    pub fn ray(mut self, ray: Ray) -> Self {
+        self.rays.push(ray);
+        self
+    }
+
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// The points along the path over `bounds`.
+    pub fn points(&self, bounds: Rect) -> Vec<Point2> {
+        let c = bounds.xy();
+        match self.along {
+            Along::Falling | Along::Rising => {
+                let slope = bounds.h() / bounds.w();
+                let slope = if self.along == Along::Falling {
+                    -slope
+                } else {
+                    slope
+                };
+                (0..bounds.w().ceil() as usize)
+                    .map(|i| {
+                        let x = bounds.left() + i as f32 + 0.5 - c.x;
+                        c + vec2(x, slope * x)
+                    })
+                    .collect()
+            }
+            Along::Circle(f) => {
+                let r = f * bounds.h();
+                let n = (TAU * r).ceil().max(3.0) as usize;
+                (0..n)
+                    .map(|i| {
+                        let a = i as f32 / n as f32 * TAU;
+                        c + vec2(a.cos(), a.sin()) * r
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn end(ray: Ray, p: Point2, bounds: Rect) -> Point2 {
+        match ray {
+            Ray::Down => pt2(p.x, bounds.bottom()),
+            Ray::Up => pt2(p.x, bounds.top()),
+            Ray::Left => pt2(bounds.left(), p.y),
+            Ray::Right => pt2(bounds.right(), p.y),
+            Ray::In => bounds.xy(),
+            Ray::Out => bounds.xy() + (p - bounds.xy()) * 9.0,
+        }
+    }
+
+    /// Draw the rays over the sampler's image in the colors under their
+    /// points.
+    pub fn draw(&self, sampler: &Sampler, canvas: &mut Canvas) {
+        let bounds = sampler.bounds;
+        for p in self.points(bounds) {
+            let mut color = sampler.nearest(p);
+            color.alpha = 1.0;
+            for &ray in &self.rays {
+                canvas
+                    .line(p, Rule::end(ray, p, bounds))
+                    .color(color)
+                    .weight(self.weight);
+            }
+        }
+    }
+}
+
+/// Draw each of `rules` in turn.
+pub fn draw(rules: &[Rule], sampler: &Sampler, canvas: &mut Canvas) {
+    for rule in rules {
+        rule.draw(sampler, canvas);
+    }
+}
+
+/// The image with `rules` drawn over it, at its own size.
+pub fn render(rules: &[Rule], sampler: &Sampler) -> RgbaImage {
+    let (w, h) = sampler.image.dimensions();
+    let mut canvas = Canvas::new(w as f32, h as f32);
+    let sampler = Sampler {
+        image: sampler.image.clone(),
+        bounds: canvas.bounds(),
+    };
+    draw(rules, &sampler, &mut canvas);
+    let mut pixmap = raster::from_image(&sampler.image);
+    raster::render(&canvas, &mut pixmap, 1.0);
+    raster::to_image(&pixmap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nannou::image::Rgba;
+
+    // Red on the left half and blue on the right, with a white top row.
+    fn sampler() -> Sampler {
+        Sampler::new(RgbaImage::from_fn(4, 2, |x, y| {
+            if y == 0 {
+                Rgba([255, 255, 255, 255])
+            } else if x < 2 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 255])
+            }
+        }))
+    }
+
+    #[test]
+    fn coordinates_round_trip() {
+        let s = sampler().fit(Rect::from_x_y_w_h(10.0, 0.0, 8.0, 4.0));
+        assert_eq!(s.to_image(pt2(6.0, 2.0)), pt2(0.0, 0.0));
+        assert_eq!(s.to_image(pt2(14.0, -2.0)), pt2(4.0, 2.0));
+        let p = pt2(11.3, -0.7);
+        assert!(s.to_space(s.to_image(p)).distance(p) < 1e-5);
+    }
+
+    #[test]
+    fn sampling_stays_on_the_image() {
+        let s = sampler();
+        assert_eq!(s.nearest(pt2(-1.5, -0.5)), s.pixel(0, 1));
+        // Far off the image in every direction.
+        assert_eq!(s.nearest(pt2(-100.0, -100.0)), s.pixel(0, 1));
+        assert_eq!(s.nearest(pt2(100.0, 100.0)), s.pixel(3, 0));
+        assert_eq!(s.bilinear(pt2(1e6, -1e6)), s.pixel(3, 1));
+        // Halfway between red and blue.
+        let c = s.bilinear(pt2(0.0, -0.5));
+        assert!((c.red - 0.5).abs() < 1e-4 && (c.blue - 0.5).abs() < 1e-4);
+        assert!((s.luminance(pt2(0.0, 0.5)) - 1.0).abs() < 1e-4);
+        assert!((s.hue(pt2(-1.5, -0.5))).abs() < 1e-3);
+        assert!((s.hue(pt2(1.5, -0.5)) - 240.0).abs() < 1e-3);
+        assert_eq!(s.hue(pt2(0.0, 1.0)), 0.0);
+    }
+
+    #[test]
+    fn rules_sample_paths() {
+        let bounds = Rect::from_w_h(40.0, 20.0);
+        let falling = Rule::new(Along::Falling).points(bounds);
+        assert_eq!(falling.len(), 40);
+        assert!(falling[0].y > 9.0 && falling[39].y < -9.0);
+        let circle = Rule::new(Along::Circle(0.5)).points(bounds);
+        assert!(circle.iter().all(|p| (p.magnitude() - 10.0).abs() < 1e-4));
+        assert_eq!(circle.len(), (TAU * 10.0).ceil() as usize);
+    }
+
+    #[test]
+    fn render_streaks_over_the_image() {
+        // Green over red.
+        let s = Sampler::new(RgbaImage::from_fn(20, 10, |_, y| {
+            if y < 5 {
+                Rgba([0, 255, 0, 255])
+            } else {
+                Rgba([255, 0, 0, 255])
+            }
+        }));
+        // The falling diagonal is in the green on the left, so streaks down
+        // from it paint green over the red there.
+        let rules = [Rule::new(Along::Falling).ray(Ray::Down)];
+        let image = render(&rules, &s);
+        assert_eq!(image.dimensions(), (20, 10));
+        assert_eq!(image.get_pixel(2, 9).0, [0, 255, 0, 255]);
+        assert_eq!(image.get_pixel(18, 9).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(18, 0).0, [0, 255, 0, 255]);
+    }
+}
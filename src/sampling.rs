@@ -0,0 +1,239 @@
+// Sampling parametric curves. A fixed step in the parameter, as `gen_points`
+// takes, crowds points where the curve moves slowly and leaves gaps where it
+// moves fast. Here points can instead be spaced evenly along the curve,
+// placed more densely where it bends, or fixed in number, and each comes with
+// its parameter.
+//
+// Arc length is measured on a table of the curve flattened to within a
+// ten-thousandth of its size, with the parameter for a length interpolated
+// between table entries. Two curves sampled `evenly` with the same count pair
+// up point for point by the fraction of their length travelled.
+
+use nannou::prelude::*;
+use std::ops::Range;
+
+use crate::polyline::{arc_lengths, segment_distance};
+
+/// Points on a curve and the parameter each was taken at.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Samples {
+    pub t: Vec<f32>,
+    pub points: Vec<Point2>,
+}
+
+impl Samples {
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    fn push(&mut self, t: f32, p: Point2) {
+        self.t.push(t);
+        self.points.push(p);
+    }
+}
+
+// Adaptive sampling cuts the range into this many pieces before looking at
+// how flat they are, so wiggles between two samples aren't missed.
+const START: usize = 32;
+// And halves a piece at most this many times.
+const DEPTH: usize = 16;
+
+/// `n` points evenly spaced in the parameter, from the start of `range` to
+/// the end.
+pub fn fixed(curve: impl Fn(f32) -> Point2, range: Range<f32>, n: usize) -> Samples {
+    let mut samples = Samples::default();
+    for i in 0..n {
+        let f = if n > 1 {
+            i as f32 / (n - 1) as f32
+        } else {
+            0.0
+        };
+        let t = range.start + (range.end - range.start) * f;
+        samples.push(t, curve(t));
+    }
+    samples
+}
+
+/// Points closer together where the curve bends, so that the polyline
+/// through them is within `tolerance` of the curve.
+pub fn adaptive(curve: impl Fn(f32) -> Point2, range: Range<f32>, tolerance: f32) -> Samples {
+    let start = fixed(&curve, range, START + 1);
+    let mut samples = Samples::default();
+    samples.push(start.t[0], start.points[0]);
+    for i in 0..START {
+        let a = (start.t[i], start.points[i]);
+        let b = (start.t[i + 1], start.points[i + 1]);
+        subdivide(&curve, a, b, tolerance, 0, &mut samples);
+    }
+    samples
+}
+
+// Adds the samples after `a` up to and including `b`.
+fn subdivide(
+    curve: &impl Fn(f32) -> Point2,
+    (a, pa): (f32, Point2),
+    (b, pb): (f32, Point2),
+    tolerance: f32,
+    depth: usize,
+    samples: &mut Samples,
+) {
+    if depth < DEPTH {
+        // The middle alone can sit on the chord of an S bend.
+        let ts = [a + (b - a) * 0.25, (a + b) / 2.0, a + (b - a) * 0.75];
+        let ps = [curve(ts[0]), curve(ts[1]), curve(ts[2])];
+        if ps.iter().any(|&p| segment_distance(p, pa, pb) > tolerance) {
+            let m = (ts[1], ps[1]);
+            subdivide(curve, (a, pa), m, tolerance, depth + 1, samples);
+            subdivide(curve, m, (b, pb), tolerance, depth + 1, samples);
+            return;
+        }
+    }
+    samples.push(b, pb);
+}
+
+/// Points `spacing` apart along the curve, measured along it, with the last
+/// at the end of `range` and perhaps closer to the one before.
+pub fn by_length(curve: impl Fn(f32) -> Point2, range: Range<f32>, spacing: f32) -> Samples {
+    assert!(spacing > 0.0, "spacing must be positive");
+    let table = table(&curve, range);
+    let lengths = arc_lengths(&table.points);
+    let total = lengths[lengths.len() - 1];
+    let mut distances: Vec<f32> = (0..)
+        .map(|i| i as f32 * spacing)
+        .take_while(|&d| d < total - spacing * 1e-3)
+        .collect();
+    distances.push(total);
+    at_lengths(&curve, &table, &lengths, &distances)
+}
+
+/// `n` points evenly spaced along the curve, both ends included.
+pub fn evenly(curve: impl Fn(f32) -> Point2, range: Range<f32>, n: usize) -> Samples {
+    let table = table(&curve, range);
+    let lengths = arc_lengths(&table.points);
+    let total = lengths[lengths.len() - 1];
+    let distances: Vec<f32> = (0..n)
+        .map(|i| {
+            if i + 1 == n && n > 1 {
+                total
+            } else {
+                total * i as f32 / (n - 1).max(1) as f32
+            }
+        })
+        .collect();
+    at_lengths(&curve, &table, &lengths, &distances)
+}
+
+// The curve flattened closely enough to measure.
+fn table(curve: &impl Fn(f32) -> Point2, range: Range<f32>) -> Samples {
+    let coarse = fixed(curve, range.clone(), START + 1);
+    let (lo, hi) = coarse.points.iter().fold(
+        (pt2(f32::MAX, f32::MAX), pt2(f32::MIN, f32::MIN)),
+        |(lo, hi), p| {
+            (
+                pt2(lo.x.min(p.x), lo.y.min(p.y)),
+                pt2(hi.x.max(p.x), hi.y.max(p.y)),
+            )
+        },
+    );
+    let size = lo.distance(hi);
+    adaptive(curve, range, (size * 1e-4).max(1e-6))
+}
+
+// The samples at increasing `distances` along the curve.
+fn at_lengths(
+    curve: &impl Fn(f32) -> Point2,
+    table: &Samples,
+    lengths: &[f32],
+    distances: &[f32],
+) -> Samples {
+    let mut samples = Samples::default();
+    let last = lengths.len() - 1;
+    let mut j = 0;
+    for &d in distances {
+        while j + 1 < last && lengths[j + 1] < d {
+            j += 1;
+        }
+        // The piece between table entries is flat, so the length along it
+        // is the distance from its start. Bisect for the parameter there,
+        // since the curve's speed can change across the piece.
+        let (start, along) = (table.points[j], d - lengths[j]);
+        let (mut lo, mut hi) = (table.t[j], table.t[j + 1]);
+        let mut t = if along <= 0.0 { lo } else { hi };
+        if along > 0.0 && along < lengths[j + 1] - lengths[j] {
+            for _ in 0..24 {
+                t = (lo + hi) / 2.0;
+                if curve(t).distance(start) < along {
+                    lo = t;
+                } else {
+                    hi = t;
+                }
+            }
+        }
+        samples.push(t, curve(t));
+    }
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circle(t: f32) -> Point2 {
+        // Slow near t = 0 and fast near t = 1.
+        let a = TAU * t * t;
+        pt2(a.cos(), a.sin()) * 100.0
+    }
+
+    fn gaps(points: &[Point2]) -> Vec<f32> {
+        points.windows(2).map(|w| w[0].distance(w[1])).collect()
+    }
+
+    #[test]
+    fn fixed_steps_in_the_parameter() {
+        let s = fixed(circle, 0.0..1.0, 5);
+        assert_eq!(s.t, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(s.points[2], circle(0.5));
+        assert_eq!(fixed(circle, 0.0..1.0, 1).t, vec![0.0]);
+    }
+
+    #[test]
+    fn evenly_spaces_points_along_the_curve() {
+        let s = evenly(circle, 0.0..1.0, 41);
+        assert_eq!(s.len(), 41);
+        assert_eq!((s.t[0], s.t[40]), (0.0, 1.0));
+        let g = gaps(&s.points);
+        let chord = 200.0 * (PI / 40.0).sin();
+        assert!(g.iter().all(|&d| (d - chord).abs() < 0.01), "{:?}", g);
+        // The parameter steps shrink as the curve speeds up.
+        assert!(s.t[1] - s.t[0] > 4.0 * (s.t[40] - s.t[39]));
+    }
+
+    #[test]
+    fn by_length_ends_at_the_end() {
+        let line = |t: f32| pt2(t * 10.0, 0.0);
+        let s = by_length(line, 0.0..1.0, 3.0);
+        let xs: Vec<f32> = s.points.iter().map(|p| p.x).collect();
+        assert_eq!(xs.len(), 5);
+        for (x, e) in xs.iter().zip(&[0.0, 3.0, 6.0, 9.0, 10.0]) {
+            assert!((x - e).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn adaptive_follows_the_bends() {
+        let line = |t: f32| pt2(t, 2.0 * t);
+        assert_eq!(adaptive(line, 0.0..1.0, 0.01).len(), START + 1);
+        let s = adaptive(circle, 0.0..1.0, 0.05);
+        for (w, t) in s.points.windows(2).zip(s.t.windows(2)) {
+            let m = circle((t[0] + t[1]) / 2.0);
+            assert!(segment_distance(m, w[0], w[1]) <= 0.05);
+        }
+        // Denser where the curve moves fast.
+        let late = s.t.iter().filter(|&&t| t > 0.5).count();
+        assert!(late > 2 * (s.len() - late));
+    }
+}
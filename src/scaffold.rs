@@ -0,0 +1,203 @@
+// Start a new sketch from one of the templates in `templates/`. The template is
+// written to `src/sketch/<name>.rs` and the sketch is added to the registry in
+// `src/sketch/mod.rs`, keeping both lists in alphabetical order.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::sketch;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Template {
+    /// Draws once in a window, `-p` saves a png.
+    Static,
+    /// Draws every frame in a window, `-p` saves the frames.
+    Animated,
+    /// Draws to a texture larger than the screen.
+    HiRes,
+    /// Saves a png without opening a window.
+    Headless,
+    /// Strokes only, `-p` saves an svg.
+    Plotter,
+}
+
+pub const TEMPLATES: &[(&str, Template)] = &[
+    ("static", Template::Static),
+    ("animated", Template::Animated),
+    ("hi-res", Template::HiRes),
+    ("headless", Template::Headless),
+    ("plotter", Template::Plotter),
+];
+
+impl Template {
+    pub fn from_name(name: &str) -> Option<Template> {
+        TEMPLATES.iter().find(|(n, _)| *n == name).map(|(_, t)| *t)
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Template::Static => include_str!("../templates/static.rs"),
+            Template::Animated => include_str!("../templates/animated.rs"),
+            Template::HiRes => include_str!("../templates/hires.rs"),
+            Template::Headless => include_str!("../templates/headless.rs"),
+            Template::Plotter => include_str!("../templates/plotter.rs"),
+        }
+    }
+
+    /// Canvas size used when none is given.
+    pub fn default_size(self) -> (u32, u32) {
+        match self {
+            Template::HiRes => (6000, 6000),
+            _ => (900, 600),
+        }
+    }
+}
+
+/// "my_sketch" becomes "My Sketch".
+pub fn title(name: &str) -> String {
+    name.split('_')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut cs = w.chars();
+            match cs.next() {
+                Some(c) => c.to_uppercase().chain(cs).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "try",
+    "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// Sketch names double as module names, so they must be lower case
+/// identifiers.
+pub fn check_name(name: &str) -> Result<(), String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid || KEYWORDS.contains(&name) {
+        return Err(format!(
+            "`{}` is not a valid sketch name, use lower case letters, digits and underscores",
+            name
+        ));
+    }
+    if sketch::find(name).is_some() {
+        return Err(format!("there is already a sketch named `{}`", name));
+    }
+    Ok(())
+}
+
+pub fn instantiate(template: Template, name: &str, width: u32, height: u32) -> String {
+    template
+        .source()
+        .replace("{{name}}", name)
+        .replace("{{title}}", &title(name))
+        .replace("{{width}}", &width.to_string())
+        .replace("{{height}}", &height.to_string())
+}
+
+// Insert `line` among the lines of `lines` that `key` picks out, before the
+// first one that sorts after it.
+fn insert_sorted(lines: &mut Vec<String>, line: String, key: impl Fn(&str) -> Option<&str>) {
+    let name = key(&line).unwrap().to_string();
+    let mut last = None;
+    for (i, l) in lines.iter().enumerate() {
+        if let Some(k) = key(l) {
+            if k > name.as_str() {
+                lines.insert(i, line);
+                return;
+            }
+            last = Some(i);
+        }
+    }
+    let i = last.expect("no entries found in the sketch registry");
+    lines.insert(i + 1, line);
+}
+
+/// Add the `pub mod` declaration and the `SKETCHES` entry for `name` to the
+/// source of the sketch registry.
+pub fn register(registry: &str, name: &str) -> String {
+    let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+    insert_sorted(&mut lines, format!("pub mod {};", name), |l| {
+        l.strip_prefix("pub mod ")?.strip_suffix(';')
+    });
+    insert_sorted(&mut lines, format!("    {}::SKETCH,", name), |l| {
+        l.strip_prefix("    ")?.strip_suffix("::SKETCH,")
+    });
+    let mut source = lines.join("\n");
+    source.push('\n');
+    source
+}
+
+/// Write a new sketch into the project at `root` and register it, returns the
+/// path of the new source file.
+pub fn create(
+    root: &Path,
+    template: Template,
+    name: &str,
+    width: u32,
+    height: u32,
+) -> Result<PathBuf, String> {
+    check_name(name)?;
+    let dir = root.join("src").join("sketch");
+    let path = dir.join(name).with_extension("rs");
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+    let registry_path = dir.join("mod.rs");
+    let registry = fs::read_to_string(&registry_path).expect("failed to read sketch registry");
+    fs::write(&path, instantiate(template, name, width, height))
+        .expect("failed to write sketch");
+    fs::write(&registry_path, register(&registry, name))
+        .expect("failed to update sketch registry");
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates_are_filled_in() {
+        for (_, t) in TEMPLATES {
+            let source = instantiate(*t, "my_sketch", 640, 480);
+            assert!(!source.contains("{{"), "{:?}", t);
+            assert!(source.contains("name: \"my_sketch\""));
+            assert!(source.contains("title: \"My Sketch\""));
+            assert!(source.contains("const WIDTH: f32 = 640.0;"));
+            assert!(source.contains("const HEIGHT: f32 = 480.0;"));
+        }
+    }
+
+    #[test]
+    fn names_are_checked() {
+        assert!(check_name("spiral_2").is_ok());
+        assert!(check_name("Spiral").is_err());
+        assert!(check_name("2d").is_err());
+        assert!(check_name("my-sketch").is_err());
+        assert!(check_name("loop").is_err());
+        assert!(check_name("worms").is_err());
+    }
+
+    #[test]
+    fn registers_in_order() {
+        let registry = include_str!("sketch/mod.rs");
+        let source = register(registry, "ladder");
+        let mods: Vec<&str> = source.lines().filter(|l| l.starts_with("pub mod ")).collect();
+        let entries: Vec<&str> = source.lines().filter(|l| l.ends_with("::SKETCH,")).collect();
+        assert_eq!(mods.len(), sketch::SKETCHES.len() + 1);
+        assert_eq!(entries.len(), sketch::SKETCHES.len() + 1);
+        let pos = mods.iter().position(|l| *l == "pub mod ladder;").unwrap();
+        assert!(mods[pos - 1] < mods[pos] && mods[pos] < mods[pos + 1]);
+        let pos = entries.iter().position(|l| *l == "    ladder::SKETCH,").unwrap();
+        assert!(entries[pos - 1] < entries[pos] && entries[pos] < entries[pos + 1]);
+        assert!(register(registry, "zebra").contains("pub mod worms;\npub mod zebra;\n"));
+    }
+}
@@ -0,0 +1,1182 @@
+use getopts::Options;
+use nannou::prelude::*;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::{fmt::Display, writeln};
+
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, gif_path};
+
+const SIZE: usize = 5;
+const WIDTH: u32 = 900;
+const HEIGHT: u32 = 600;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "azul",
+    title: "Azul",
+    description: "Tiles placed on an Azul wall one at a time in the order that scores the most, \
+        showing the runs each tile scores. `-g` shows the greedy order instead.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run();
+}
+
+// Print both placement orders and how they compare, returns the greedy and
+// optimal boards.
+fn report() -> (Board, Board) {
+    let now = std::time::Instant::now();
+    let greedy = Board::greedy(SIZE);
+    println!("Greedy: {} in {:.2?}", greedy.value(), now.elapsed());
+    println!("{}", greedy);
+
+    let now = std::time::Instant::now();
+    let mut solver = Solver::new(SIZE);
+    let solution = solver.solve();
+    println!(
+        "Optimal: {} in {:.2?}, {} positions searched",
+        solution.value,
+        now.elapsed(),
+        solver.memo.len()
+    );
+    let optimal = Board::from_order(SIZE, &solution.order);
+    println!("{}", optimal);
+    println!(
+        "Greedy falls short by {} ({:.1}%)",
+        solution.value - greedy.value(),
+        100.0 * (solution.value - greedy.value()) as f32 / solution.value as f32
+    );
+    (greedy, optimal)
+}
+
+// The optimal order is animated in the gallery.
+fn render(rec: &mut Recorder) {
+    let board = Board::from_order(SIZE, &Solver::new(SIZE).solve().order);
+    rec.delay = 80;
+    for frame in (0..frames(&board)).step_by(4) {
+        rec.frame(&scene(&board, frame));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Blue,
+    Yellow,
+    Red,
+    Black,
+    White,
+}
+
+impl Tile {
+    const ALL: [Tile; 5] = [
+        Tile::Blue,
+        Tile::Yellow,
+        Tile::Red,
+        Tile::Black,
+        Tile::White,
+    ];
+
+    #[cfg(test)]
+    fn index(self) -> usize {
+        Tile::ALL.iter().position(|&t| t == self).unwrap()
+    }
+}
+
+/// Which colour may go where on the wall.
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Wall {
+    /// The coloured side of the board, each row is the row above shifted one
+    /// to the right.
+    Standard,
+    /// The grey side, any colour may go anywhere as long as it is not
+    /// repeated in a row or column.
+    Free,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    order: Option<usize>,
+    value: usize,
+    influence: usize,
+    #[cfg(test)]
+    color: Option<Tile>,
+}
+
+impl Cell {
+    fn new() -> Self {
+        Self {
+            order: None,
+            value: 0,
+            influence: 0,
+            #[cfg(test)]
+            color: None,
+        }
+    }
+}
+
+fn scan(v: &[Cell], n: i32) -> usize {
+    let mut i = n - 1;
+    let mut j = n + 1;
+    let mut val = 0;
+    while i >= 0 && v[i as usize].order.is_some() {
+        val += 1;
+        i -= 1;
+    }
+    while j < v.len() as i32 && v[j as usize].order.is_some() {
+        val += 1;
+        j += 1;
+    }
+    val
+}
+
+#[derive(Debug, Clone)]
+struct Board {
+    size: usize,
+    cells: Vec<Cell>,
+    next: usize,
+    #[cfg(test)]
+    wall: Wall,
+}
+
+impl Board {
+    fn new(size: usize) -> Self {
+        let cells = vec![Cell::new(); size * size];
+        Self {
+            size,
+            cells,
+            next: 0,
+            #[cfg(test)]
+            wall: Wall::Standard,
+        }
+    }
+
+    fn taken(&self) -> usize {
+        let mut count = 0;
+        for r in 0..self.size {
+            for c in 0..self.size {
+                if self.get(r, c).order.is_some() {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut Cell {
+        &mut self.cells[row * self.size + col]
+    }
+
+    fn get(&self, row: usize, col: usize) -> Cell {
+        self.cells[row * self.size + col]
+    }
+
+    fn value(&self) -> usize {
+        self.cells.iter().fold(0, |acc, x| acc + x.value)
+    }
+
+    fn row(&self, i: usize) -> Vec<Cell> {
+        let mut r = vec![];
+        for j in 0..self.size {
+            r.push(self.get(i, j));
+        }
+        r
+    }
+
+    fn col(&self, i: usize) -> Vec<Cell> {
+        let mut c = vec![];
+        for j in 0..self.size {
+            c.push(self.get(j, i));
+        }
+        c
+    }
+
+    fn cell_value(&self, row: usize, col: usize) -> usize {
+        let r = self.row(row);
+        let c = self.col(col);
+        let val;
+        let r_val = scan(&r, col as i32);
+        let c_val = scan(&c, row as i32);
+        if r_val == 0 {
+            val = c_val + 1;
+        } else if c_val == 0 {
+            val = r_val + 1;
+        } else {
+            val = r_val + c_val + 2;
+        }
+        val
+    }
+
+    fn place(&self, row: usize, col: usize) -> Board {
+        let mut board = self.clone();
+        let cell = board.get_mut(row, col);
+        if cell.order.is_some() {
+            panic!("Cell ({},{}) is already taken", row, col);
+        }
+        cell.order = Some(self.next);
+        cell.value = self.cell_value(row, col);
+        board.next += 1;
+        board
+    }
+
+    fn influence(&self, row: usize, col: usize) -> i32 {
+        let board = self.clone().place(row, col);
+        let val = board.value();
+        let mut infl = 0;
+        for r in 0..board.size {
+            for c in 0..board.size {
+                if board.get(r, c).order.is_some() {
+                    continue;
+                }
+                let b = board.place(r, c);
+                infl += b.value() - val;
+            }
+        }
+        infl as i32 + board.get(row, col).value as i32
+    }
+
+    fn influences(&self) -> Board {
+        let mut board = self.clone();
+        for r in 0..board.size {
+            for c in 0..board.size {
+                if board.get(r, c).order.is_some() {
+                    continue;
+                }
+                board.get_mut(r, c).influence = board.influence(r, c) as usize;
+            }
+        }
+        board
+    }
+
+    fn max_score(&self) -> ((usize, usize), i32) {
+        let mut coords = (0, 0);
+        let mut max_infl = -1;
+        let board = self.influences();
+        for r in 0..self.size {
+            for c in 0..board.size {
+                if board.get(r, c).order.is_some() {
+                    continue;
+                }
+                let infl = board.get(r, c).influence as i32;
+                if infl > max_infl {
+                    max_infl = infl;
+                    coords = (r, c);
+                }
+            }
+        }
+        (coords, max_infl)
+    }
+
+    /// The colour printed on the standard wall at `(row, col)`.
+    fn layout(&self, row: usize, col: usize) -> Tile {
+        Tile::ALL[(col + self.size - row) % self.size]
+    }
+
+    /// Place every tile in turn at the cell with the highest influence.
+    fn greedy(size: usize) -> Board {
+        let mut board = Board::new(size);
+        for _ in 0..size * size {
+            let ((r, c), _infl) = board.max_score();
+            board = board.place(r, c);
+        }
+        board
+    }
+
+    fn from_order(size: usize, order: &[(usize, usize)]) -> Board {
+        order
+            .iter()
+            .fold(Board::new(size), |b, &(r, c)| b.place(r, c))
+    }
+}
+
+// Colours on the wall and the end of game bonus, for the game rules below.
+// The sketch only scores where tiles go, so these are built for the tests.
+#[cfg(test)]
+impl Board {
+    fn display_influence(&self) {
+        for r in 0..self.size {
+            for x in self.row(r) {
+                print!("{:3}", x.influence);
+            }
+            println!();
+        }
+    }
+
+    /// A full size 5x5 wall.
+    fn with_wall(wall: Wall) -> Self {
+        Self {
+            wall,
+            ..Board::new(Tile::ALL.len())
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        self.size * self.size - self.taken()
+    }
+
+    /// The column `tile` goes in on `row` of the standard wall.
+    fn layout_col(&self, row: usize, tile: Tile) -> usize {
+        (tile.index() + row) % self.size
+    }
+
+    fn color(&self, row: usize, col: usize) -> Option<Tile> {
+        self.get(row, col).color
+    }
+
+    fn row_has(&self, row: usize, tile: Tile) -> bool {
+        (0..self.size).any(|c| self.color(row, c) == Some(tile))
+    }
+
+    fn can_place(&self, row: usize, col: usize, tile: Tile) -> bool {
+        if self.get(row, col).order.is_some() {
+            return false;
+        }
+        match self.wall {
+            Wall::Standard => self.layout(row, col) == tile,
+            Wall::Free => {
+                !self.row_has(row, tile) && (0..self.size).all(|r| self.color(r, col) != Some(tile))
+            }
+        }
+    }
+
+    /// Columns of `row` where `tile` may be placed.
+    fn legal_cols(&self, row: usize, tile: Tile) -> Vec<usize> {
+        (0..self.size)
+            .filter(|&c| self.can_place(row, c, tile))
+            .collect()
+    }
+
+    fn place_tile(&self, row: usize, col: usize, tile: Tile) -> Board {
+        if !self.can_place(row, col, tile) {
+            panic!("{:?} can't be placed at ({},{})", tile, row, col);
+        }
+        let mut board = self.place(row, col);
+        board.get_mut(row, col).color = Some(tile);
+        board
+    }
+
+    fn complete_rows(&self) -> usize {
+        (0..self.size)
+            .filter(|&r| self.row(r).iter().all(|x| x.order.is_some()))
+            .count()
+    }
+
+    fn complete_cols(&self) -> usize {
+        (0..self.size)
+            .filter(|&c| self.col(c).iter().all(|x| x.order.is_some()))
+            .count()
+    }
+
+    fn complete_colors(&self) -> usize {
+        Tile::ALL
+            .iter()
+            .filter(|&&t| (0..self.size).all(|r| self.row_has(r, t)))
+            .count()
+    }
+
+    /// End of game bonus: 2 for each full row, 7 for each full column and 10
+    /// for each colour that has all of its tiles on the wall.
+    fn bonus(&self) -> usize {
+        2 * self.complete_rows() + 7 * self.complete_cols() + 10 * self.complete_colors()
+    }
+}
+
+// Optimal placement order ------------------------------------------------------
+
+// The value of placing a tile only depends on which cells are already taken,
+// so the best total from any position is a function of the occupancy bitmask,
+// bit `row * size + col`. `Solver` finds it by depth first search, memoising
+// every position and pruning positions that can't beat what's needed of them.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Solution {
+    value: usize,
+    order: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Bound {
+    Exact(usize),
+    /// Searched with a cut off, the true value is no more than this.
+    Upper(usize),
+}
+
+struct Solver {
+    size: usize,
+    memo: HashMap<u32, Bound>,
+    /// `line_bound[taken][left]`, the most a row or column with `taken` tiles
+    /// can add to the scores of its `left` remaining ones.
+    line_bound: Vec<Vec<usize>>,
+}
+
+impl Solver {
+    fn new(size: usize) -> Self {
+        assert!(size * size <= 32, "boards larger than 5x5 don't fit a u32");
+        let line_bound = (0..=size)
+            .map(|taken| {
+                (0..=size - taken)
+                    .map(|left| (1..=left).map(|k| taken + k).sum())
+                    .collect()
+            })
+            .collect();
+        Self {
+            size,
+            memo: HashMap::new(),
+            line_bound,
+        }
+    }
+
+    fn bit(&self, row: usize, col: usize) -> u32 {
+        1 << (row * self.size + col)
+    }
+
+    fn full(&self) -> u32 {
+        (((1u64) << (self.size * self.size)) - 1) as u32
+    }
+
+    // Tiles in a line with (row, col), stepping by (dr, dc).
+    fn run(&self, mask: u32, row: usize, col: usize, dr: i32, dc: i32) -> usize {
+        let mut count = 0;
+        let (mut r, mut c) = (row as i32 + dr, col as i32 + dc);
+        while r >= 0
+            && c >= 0
+            && (r as usize) < self.size
+            && (c as usize) < self.size
+            && mask & self.bit(r as usize, c as usize) != 0
+        {
+            count += 1;
+            r += dr;
+            c += dc;
+        }
+        count
+    }
+
+    /// Same as `Board::cell_value`.
+    fn gain(&self, mask: u32, row: usize, col: usize) -> usize {
+        let h = self.run(mask, row, col, 0, -1) + self.run(mask, row, col, 0, 1);
+        let v = self.run(mask, row, col, -1, 0) + self.run(mask, row, col, 1, 0);
+        match (h, v) {
+            (0, v) => v + 1,
+            (h, 0) => h + 1,
+            (h, v) => h + v + 2,
+        }
+    }
+
+    // A tile scores at most the length of its row run plus its column run, and
+    // the k-th tile still to go in a line makes a run no longer than the tiles
+    // already there plus k.
+    fn upper_bound(&self, mask: u32) -> usize {
+        let mut bound = 0;
+        for i in 0..self.size {
+            let row = (0..self.size)
+                .filter(|&j| mask & self.bit(i, j) != 0)
+                .count();
+            let col = (0..self.size)
+                .filter(|&j| mask & self.bit(j, i) != 0)
+                .count();
+            bound += self.line_bound[row][self.size - row];
+            bound += self.line_bound[col][self.size - col];
+        }
+        bound
+    }
+
+    // Candidate moves, best immediate gain first so good totals are found
+    // early and more of the tree is cut off.
+    fn moves(&self, mask: u32) -> Vec<(usize, usize, usize)> {
+        let mut moves = vec![];
+        for r in 0..self.size {
+            for c in 0..self.size {
+                if mask & self.bit(r, c) == 0 {
+                    moves.push((self.gain(mask, r, c), r, c));
+                }
+            }
+        }
+        moves.sort_by_key(|m| Reverse(m.0));
+        moves
+    }
+
+    /// The best total still to be scored from `mask`. If that is less than
+    /// `need` the search may stop early and the result is only an upper
+    /// bound, otherwise it is exact.
+    fn search(&mut self, mask: u32, need: usize) -> usize {
+        if mask == self.full() {
+            return 0;
+        }
+        match self.memo.get(&mask) {
+            Some(Bound::Exact(v)) => return *v,
+            Some(Bound::Upper(v)) if *v < need => return *v,
+            _ => {}
+        }
+        let bound = self.upper_bound(mask);
+        if bound < need {
+            return bound;
+        }
+        // `best` is the best exact total found, `cut` the most any move that
+        // was cut off could be worth.
+        let mut best = 0;
+        let mut cut = None;
+        for (gain, r, c) in self.moves(mask) {
+            let child = mask | self.bit(r, c);
+            let most = gain + self.upper_bound(child);
+            if most <= best.max(need.saturating_sub(1)) {
+                cut = cut.max(Some(most));
+                continue;
+            }
+            let target = (best + 1).max(need);
+            let v = gain + self.search(child, target.saturating_sub(gain));
+            if v < target {
+                cut = cut.max(Some(v));
+            } else {
+                best = best.max(v);
+            }
+        }
+        let entry = match cut {
+            Some(most) if best < need => Bound::Upper(best.max(most)),
+            _ => Bound::Exact(best),
+        };
+        self.memo.insert(mask, entry);
+        match entry {
+            Bound::Exact(v) | Bound::Upper(v) => v,
+        }
+    }
+
+    fn solve(&mut self) -> Solution {
+        let value = self.search(0, 0);
+        let mut order = vec![];
+        let mut mask = 0;
+        let mut left = value;
+        while mask != self.full() {
+            let (gain, r, c) = self
+                .moves(mask)
+                .into_iter()
+                .find(|&(gain, r, c)| {
+                    gain <= left && gain + self.search(mask | self.bit(r, c), left - gain) == left
+                })
+                .expect("no move reaches the optimum");
+            order.push((r, c));
+            mask |= self.bit(r, c);
+            left -= gain;
+        }
+        Solution { value, order }
+    }
+}
+
+/// Points lost for each space of the floor line, tiles beyond the last space
+/// cost nothing.
+#[cfg(test)]
+const FLOOR: [usize; 7] = [1, 1, 2, 2, 2, 3, 3];
+
+#[cfg(test)]
+fn floor_penalty(tiles: usize) -> usize {
+    FLOOR.iter().take(tiles).sum()
+}
+
+#[cfg(test)]
+#[derive(Debug, Clone, Copy)]
+struct PatternLine {
+    capacity: usize,
+    tile: Option<Tile>,
+    count: usize,
+}
+
+#[cfg(test)]
+impl PatternLine {
+    fn is_full(&self) -> bool {
+        self.count == self.capacity
+    }
+}
+
+/// A player's wall, pattern lines and floor line.
+#[cfg(test)]
+#[derive(Debug, Clone)]
+struct Player {
+    board: Board,
+    lines: Vec<PatternLine>,
+    floor: usize,
+    score: usize,
+}
+
+#[cfg(test)]
+impl Player {
+    fn new(wall: Wall) -> Self {
+        let board = Board::with_wall(wall);
+        let lines = (0..board.size)
+            .map(|i| PatternLine {
+                capacity: i + 1,
+                tile: None,
+                count: 0,
+            })
+            .collect();
+        Self {
+            board,
+            lines,
+            floor: 0,
+            score: 0,
+        }
+    }
+
+    /// A pattern line holds one colour, and not one that is already on that
+    /// row of the wall.
+    fn can_add(&self, row: usize, tile: Tile) -> bool {
+        let line = self.lines[row];
+        line.tile.is_none_or(|t| t == tile && !line.is_full())
+            && !self.board.legal_cols(row, tile).is_empty()
+    }
+
+    /// Add `count` tiles of `tile` to a pattern line, the ones that don't fit
+    /// drop to the floor.
+    fn add(&mut self, row: usize, tile: Tile, count: usize) {
+        if !self.can_add(row, tile) {
+            panic!("{:?} can't go on pattern line {}", tile, row);
+        }
+        let line = &mut self.lines[row];
+        let fits = count.min(line.capacity - line.count);
+        line.tile = Some(tile);
+        line.count += fits;
+        self.floor += count - fits;
+    }
+
+    /// Tiles, or the first player marker, taken straight to the floor.
+    fn drop(&mut self, count: usize) {
+        self.floor += count;
+    }
+
+    /// Move the tile from the full pattern line `row` to column `col` of the
+    /// wall and score it.
+    fn tile_line(&mut self, row: usize, col: usize) -> usize {
+        let line = self.lines[row];
+        let tile = match line.tile {
+            Some(tile) if line.is_full() => tile,
+            _ => panic!("pattern line {} is not full", row),
+        };
+        self.board = self.board.place_tile(row, col, tile);
+        self.lines[row].tile = None;
+        self.lines[row].count = 0;
+        let points = self.board.get(row, col).value;
+        self.score += points;
+        points
+    }
+
+    /// Wall tiling at the end of a round. Full lines move to the wall from top
+    /// to bottom, on the grey wall each goes in the leftmost column it is
+    /// allowed in, then the floor is scored and cleared. The score never drops
+    /// below zero.
+    fn end_round(&mut self) {
+        for row in 0..self.lines.len() {
+            let line = self.lines[row];
+            if let (Some(tile), true) = (line.tile, line.is_full()) {
+                let col = match self.board.wall {
+                    Wall::Standard => self.board.layout_col(row, tile),
+                    Wall::Free => self.board.legal_cols(row, tile)[0],
+                };
+                self.tile_line(row, col);
+            }
+        }
+        self.score = self.score.saturating_sub(floor_penalty(self.floor));
+        self.floor = 0;
+    }
+
+    /// The game ends once any row of the wall is complete.
+    fn game_over(&self) -> bool {
+        self.board.complete_rows() > 0
+    }
+
+    fn final_score(&self) -> usize {
+        self.score + self.board.bonus()
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for r in 0..self.size {
+            for x in self.row(r) {
+                match x.order {
+                    Some(o) => {
+                        write!(f, "{:3}", o)?;
+                    }
+                    None => {
+                        write!(f, " -")?;
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// Animation -------------------------------------------------------------------
+
+// Frames spent placing each tile, and beats the finished wall is held for.
+const BEAT: u64 = 24;
+const HOLD: u64 = 4;
+const CELL: f32 = 90.0;
+const BOARD_X: f32 = -170.0;
+
+fn frames(board: &Board) -> u64 {
+    (board.taken() as u64 + HOLD) * BEAT
+}
+
+fn tile_color(tile: Tile) -> Rgb<u8> {
+    match tile {
+        Tile::Blue => rgb(38, 105, 190),
+        Tile::Yellow => rgb(240, 188, 50),
+        Tile::Red => rgb(205, 55, 45),
+        Tile::Black => rgb(25, 25, 30),
+        Tile::White => rgb(232, 228, 214),
+    }
+}
+
+fn cell_center(board: &Board, row: usize, col: usize) -> Point2 {
+    let half = (board.size - 1) as f32 / 2.0;
+    pt2(
+        BOARD_X + (col as f32 - half) * CELL,
+        (half - row as f32) * CELL,
+    )
+}
+
+// The cells of the run through (row, col) in direction (dr, dc), among the
+// tiles placed up to and including `step`.
+fn run_cells(board: &Board, row: usize, col: usize, step: usize, dr: i32, dc: i32) -> Vec<Point2> {
+    let placed = |r: i32, c: i32| {
+        r >= 0
+            && c >= 0
+            && (r as usize) < board.size
+            && (c as usize) < board.size
+            && board
+                .get(r as usize, c as usize)
+                .order
+                .is_some_and(|o| o <= step)
+    };
+    let (mut r, mut c) = (row as i32, col as i32);
+    while placed(r - dr, c - dc) {
+        r -= dr;
+        c -= dc;
+    }
+    let mut cells = vec![];
+    while placed(r, c) {
+        cells.push(cell_center(board, r as usize, c as usize));
+        r += dr;
+        c += dc;
+    }
+    cells
+}
+
+// Seven segment digits, segments a to g.
+const DIGITS: [[bool; 7]; 10] = [
+    [true, true, true, true, true, true, false],
+    [false, true, true, false, false, false, false],
+    [true, true, false, true, true, false, true],
+    [true, true, true, true, false, false, true],
+    [false, true, true, false, false, true, true],
+    [true, false, true, true, false, true, true],
+    [true, false, true, true, true, true, true],
+    [true, true, true, false, false, false, false],
+    [true, true, true, true, true, true, true],
+    [true, true, true, true, false, true, true],
+];
+
+// Draw `n` with its right edge at `right`, digits `h` high.
+fn number(canvas: &mut Canvas, n: usize, right: Point2, h: f32, color: Rgba) {
+    let w = h / 2.0;
+    let t = h / 9.0;
+    let digits: Vec<usize> = n
+        .to_string()
+        .chars()
+        .map(|d| d.to_digit(10).unwrap() as usize)
+        .collect();
+    for (i, d) in digits.iter().rev().enumerate() {
+        let x = right.x - w / 2.0 - i as f32 * (w + 2.0 * t);
+        let y = right.y;
+        let segments = [
+            (pt2(x, y + h / 2.0), w, t),
+            (pt2(x + w / 2.0, y + h / 4.0), t, h / 2.0),
+            (pt2(x + w / 2.0, y - h / 4.0), t, h / 2.0),
+            (pt2(x, y - h / 2.0), w, t),
+            (pt2(x - w / 2.0, y - h / 4.0), t, h / 2.0),
+            (pt2(x - w / 2.0, y + h / 4.0), t, h / 2.0),
+            (pt2(x, y), w, t),
+        ];
+        for (on, (center, sw, sh)) in DIGITS[*d].iter().zip(segments.iter()) {
+            if *on {
+                canvas.rect(*center, *sw, *sh).color(color);
+            }
+        }
+    }
+}
+
+fn scene(board: &Board, frame: u64) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH as f32, HEIGHT as f32);
+    canvas.background(rgb8(40, 40, 48));
+
+    let frame = frame % frames(board);
+    let step = (frame / BEAT) as usize;
+    let t = (frame % BEAT) as f32 / BEAT as f32;
+    let placing = step < board.taken();
+    let landed = |o: usize| o < step || (o == step && t >= 0.5);
+
+    let mut total = 0;
+    let mut gain = None;
+    for r in 0..board.size {
+        for c in 0..board.size {
+            let center = cell_center(board, r, c);
+            let color = tile_color(board.layout(r, c));
+            canvas
+                .rect(center, CELL - 6.0, CELL - 6.0)
+                .color(rgba8(color.red, color.green, color.blue, 40))
+                .stroke(rgb8(70, 70, 82))
+                .weight(2.0);
+            let cell = board.get(r, c);
+            let size = match cell.order {
+                Some(o) if o < step => 1.0,
+                Some(o) if o == step && placing => ease_out(t.min(0.5) * 2.0),
+                _ => 0.0,
+            };
+            if size > 0.0 {
+                let side = (CELL - 14.0) * size;
+                canvas.rect(center, side, side).color(color);
+            }
+            match cell.order {
+                Some(o) if landed(o) => total += cell.value,
+                _ => {}
+            }
+            if cell.order == Some(step) && placing {
+                gain = Some(((r, c), cell.value));
+            }
+        }
+    }
+
+    // The runs the new tile scores, drawn once it lands.
+    if let Some(((r, c), _)) = gain {
+        if t >= 0.5 {
+            let alpha = 1.0 - (t - 0.5) * 1.4;
+            let gold = rgba(1.0, 0.85, 0.3, alpha);
+            let row = run_cells(board, r, c, step, 0, 1);
+            let col = run_cells(board, r, c, step, 1, 0);
+            for run in &[row, col] {
+                if run.len() > 1 {
+                    canvas
+                        .polyline(run.clone())
+                        .color(gold)
+                        .weight(CELL / 6.0)
+                        .caps_round();
+                }
+            }
+            canvas
+                .ellipse(cell_center(board, r, c), CELL / 3.0, CELL / 3.0)
+                .color(gold);
+        }
+    }
+
+    let right = pt2(WIDTH as f32 / 2.0 - 60.0, 120.0);
+    number(
+        &mut canvas,
+        total,
+        right,
+        110.0,
+        rgba(0.95, 0.95, 0.95, 1.0),
+    );
+    if let Some((_, value)) = gain {
+        if t >= 0.5 {
+            let gold = rgba(1.0, 0.85, 0.3, 1.0);
+            let right = pt2(right.x, -20.0);
+            number(&mut canvas, value, right, 60.0, gold);
+            // Digits 60 high are 30 wide with a gap of 2 * 60 / 9.
+            let digits = value.to_string().len() as f32;
+            let plus = pt2(right.x - digits * (30.0 + 120.0 / 9.0) - 14.0, right.y);
+            canvas.rect(plus, 26.0, 6.0).color(gold);
+            canvas.rect(plus, 6.0, 26.0).color(gold);
+        }
+    }
+
+    // Progress towards the final total.
+    let bar = 200.0;
+    let done = total as f32 / board.value() as f32;
+    let left = right.x - bar;
+    canvas
+        .rect(pt2(left + bar / 2.0, -120.0), bar, 8.0)
+        .color(rgb8(70, 70, 82));
+    canvas
+        .rect(pt2(left + bar * done / 2.0, -120.0), bar * done, 8.0)
+        .color(rgb8(240, 188, 50));
+    canvas
+}
+
+fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+struct Model {
+    board: Board,
+    png: bool,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(WIDTH, HEIGHT)
+        .view(view)
+        .build()
+        .unwrap();
+    let mut opts = Options::new();
+    opts.optflag("g", "greedy", "show the greedy placement order.");
+    let matches = cli::parse(opts);
+    let (greedy, optimal) = report();
+    Model {
+        board: if matches.opt_present("g") {
+            greedy
+        } else {
+            optimal
+        },
+        png: matches.opt_present("p"),
+    }
+}
+
+fn update(_app: &App, _model: &mut Model, _update: Update) {}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    scene(&model.board, frame.nth()).draw(&draw);
+
+    if model.png {
+        let file_path = gif_path(app, &frame);
+        app.main_window().capture_frame(file_path);
+        app.set_loop_mode(LoopMode::loop_ntimes(frames(&model.board) as usize));
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_test() {
+        let mut v = vec![Cell::new(); 5];
+        assert_eq!(scan(&v, 2), 0);
+        assert_eq!(scan(&v, 0), 0);
+        assert_eq!(scan(&v, 4), 0);
+        v[3].order = Some(0);
+        assert_eq!(scan(&v, 2), 1);
+        assert_eq!(scan(&v, 4), 1);
+        assert_eq!(scan(&v, 1), 0);
+        v[2].order = Some(0);
+        assert_eq!(scan(&v, 1), 2);
+        assert_eq!(scan(&v, 0), 0);
+        assert_eq!(scan(&v, 4), 2);
+        v[0].order = Some(0);
+        assert_eq!(scan(&v, 1), 3);
+        assert_eq!(scan(&v, 4), 2);
+    }
+
+    #[test]
+    fn infl_test() {
+        // let board = Board::new(4);
+        // assert_eq!(board.influence(1, 1), 19);
+        // assert_eq!(board.influence(0, 0), 17);
+        let board = Board::new(4);
+        let board = board.place(1, 1);
+        let board = board.place(2, 2);
+        // board.get_mut(1, 1).order = Some(0);
+        // let board = board.place(0, 1);
+        let board = board.influences();
+        board.display_influence();
+        dbg!(board.max_score());
+        // board.get_mut(0, 0).order = Some(1);
+        // board.get_mut(2, 2).order = Some(2);
+        // dbg!(board.influence(0, 2));
+        // dbg!(board.influence(0, 1));
+    }
+
+    // Place the standard wall's own colour at each of `cells`.
+    fn fill(board: Board, cells: &[(usize, usize)]) -> Board {
+        cells
+            .iter()
+            .fold(board, |b, &(r, c)| b.place_tile(r, c, b.layout(r, c)))
+    }
+
+    #[test]
+    fn rulebook_wall_tiling() {
+        // A tile with no neighbours is worth 1.
+        let board = fill(Board::with_wall(Wall::Standard), &[(0, 0)]);
+        assert_eq!(board.get(0, 0).value, 1);
+        // Horizontally linked to 2 tiles, 3 points.
+        let board = fill(Board::with_wall(Wall::Standard), &[(1, 0), (1, 1), (1, 2)]);
+        assert_eq!(board.get(1, 2).value, 3);
+        // Linked to 2 tiles in its row and 2 in its column, 3 + 3 points.
+        let board = fill(
+            Board::with_wall(Wall::Standard),
+            &[(2, 0), (2, 1), (0, 2), (1, 2), (2, 2)],
+        );
+        assert_eq!(board.get(2, 2).value, 6);
+        // Tiles that aren't in a line with it don't count.
+        let board = fill(
+            Board::with_wall(Wall::Standard),
+            &[(0, 0), (1, 1), (3, 3), (1, 3), (2, 3)],
+        );
+        assert_eq!(board.get(2, 3).value, 3);
+        assert_eq!(board.value(), 1 + 1 + 1 + 1 + 3);
+    }
+
+    #[test]
+    fn standard_layout() {
+        let board = Board::with_wall(Wall::Standard);
+        for r in 0..5 {
+            for t in Tile::ALL.iter() {
+                let c = board.layout_col(r, *t);
+                assert_eq!(board.layout(r, c), *t);
+                assert_eq!(board.legal_cols(r, *t), vec![c]);
+            }
+        }
+        assert_eq!(board.layout(0, 0), Tile::Blue);
+        assert_eq!(board.layout(1, 0), Tile::White);
+        assert_eq!(board.layout(4, 4), Tile::Blue);
+    }
+
+    #[test]
+    fn rulebook_floor_line() {
+        assert_eq!(floor_penalty(0), 0);
+        assert_eq!(floor_penalty(3), 4);
+        assert_eq!(floor_penalty(7), 14);
+        assert_eq!(floor_penalty(9), 14);
+        let mut player = Player::new(Wall::Standard);
+        player.add(0, Tile::Red, 1);
+        player.drop(3);
+        player.end_round();
+        assert_eq!(player.score, 0);
+    }
+
+    #[test]
+    fn rulebook_end_of_game() {
+        // 2 complete rows, 1 complete column and all five blue tiles:
+        // 2 * 2 + 7 + 10 points.
+        let mut cells = vec![];
+        for c in 0..5 {
+            cells.push((0, c));
+            cells.push((1, c));
+        }
+        cells.extend(&[(2, 0), (3, 0), (4, 0), (2, 2), (3, 3), (4, 4)]);
+        let board = fill(Board::with_wall(Wall::Standard), &cells);
+        assert_eq!(board.complete_rows(), 2);
+        assert_eq!(board.complete_cols(), 1);
+        assert_eq!(board.complete_colors(), 1);
+        assert_eq!(board.bonus(), 21);
+    }
+
+    #[test]
+    fn pattern_lines() {
+        let mut player = Player::new(Wall::Standard);
+        player.add(2, Tile::Red, 4);
+        player.add(0, Tile::Blue, 1);
+        player.add(1, Tile::Blue, 1);
+        assert_eq!(player.floor, 1);
+        assert!(!player.can_add(2, Tile::Red));
+        assert!(!player.can_add(1, Tile::Yellow));
+        player.end_round();
+        // Two isolated tiles, less one for the floor.
+        assert_eq!(player.score, 1);
+        assert_eq!(player.board.color(0, 0), Some(Tile::Blue));
+        assert_eq!(player.board.color(2, 4), Some(Tile::Red));
+        assert_eq!(player.lines[1].count, 1);
+        assert!(!player.can_add(0, Tile::Blue));
+        assert!(player.can_add(0, Tile::Red));
+        assert!(!player.game_over());
+    }
+
+    #[test]
+    fn grey_wall() {
+        let mut player = Player::new(Wall::Free);
+        player.add(0, Tile::Red, 1);
+        assert_eq!(player.tile_line(0, 3), 1);
+        assert_eq!(player.board.legal_cols(1, Tile::Red), vec![0, 1, 2, 4]);
+        assert_eq!(player.board.legal_cols(0, Tile::Blue), vec![0, 1, 2, 4]);
+        assert!(!player.can_add(0, Tile::Red));
+        player.add(1, Tile::Blue, 2);
+        player.end_round();
+        assert_eq!(player.board.color(1, 0), Some(Tile::Blue));
+        assert_eq!(player.score, 2);
+    }
+
+    #[test]
+    fn complete_game() {
+        let mut player = Player::new(Wall::Standard);
+        for round in 0..5 {
+            for row in 0..5 {
+                let tile = Tile::ALL[(row + round) % 5];
+                player.add(row, tile, row + 1);
+            }
+            player.end_round();
+        }
+        assert!(player.game_over());
+        assert_eq!(player.board.remaining(), 0);
+        assert_eq!(
+            player.final_score(),
+            player.board.value() + 5 * 2 + 5 * 7 + 5 * 10
+        );
+    }
+
+    #[test]
+    fn runs_only_count_earlier_tiles() {
+        let board = Board::from_order(3, &[(1, 1), (1, 0), (0, 1), (1, 2), (2, 1)]);
+        assert_eq!(run_cells(&board, 1, 0, 1, 0, 1).len(), 2);
+        assert_eq!(run_cells(&board, 1, 2, 3, 0, 1).len(), 3);
+        assert_eq!(run_cells(&board, 1, 2, 3, 1, 0).len(), 1);
+        assert_eq!(run_cells(&board, 2, 1, 4, 1, 0).len(), 3);
+        assert_eq!(frames(&board), (5 + HOLD) * BEAT);
+    }
+
+    // Every order of a small board.
+    fn brute_force(board: &Board) -> usize {
+        let mut best = board.value();
+        for r in 0..board.size {
+            for c in 0..board.size {
+                if board.get(r, c).order.is_none() {
+                    best = best.max(brute_force(&board.place(r, c)));
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn solver_matches_brute_force() {
+        for size in 1..=3 {
+            let solution = Solver::new(size).solve();
+            assert_eq!(solution.value, brute_force(&Board::new(size)));
+            assert_eq!(
+                Board::from_order(size, &solution.order).value(),
+                solution.value
+            );
+        }
+    }
+
+    #[test]
+    fn solver_beats_greedy() {
+        let mut solver = Solver::new(4);
+        let solution = solver.solve();
+        let board = Board::from_order(4, &solution.order);
+        assert_eq!(board.remaining(), 0);
+        assert_eq!(board.value(), solution.value);
+        assert!(solution.value >= Board::greedy(4).value());
+        // Searching again with a cut off above the optimum finds nothing.
+        assert!(solver.search(0, solution.value + 1) <= solution.value);
+    }
+
+    #[test]
+    fn solver_scales_to_a_full_wall() {
+        let solution = Solver::new(SIZE).solve();
+        assert_eq!(solution.value, 145);
+        let board = Board::from_order(SIZE, &solution.order);
+        assert_eq!(board.remaining(), 0);
+        assert_eq!(board.value(), solution.value);
+        let greedy = Board::greedy(SIZE).value();
+        assert_eq!(greedy, 142);
+        assert!(solution.value >= greedy);
+    }
+}
@@ -0,0 +1,88 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sampling::evenly;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, img_path};
+
+const WIDTH: f32 = 1200.0;
+const HEIGHT: f32 = 700.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "bird",
+    title: "Bird",
+    description: "Lines joining two parametric curves into the shape of a bird.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).run();
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+struct Model {
+    canvas: Canvas,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(WIDTH as u32, HEIGHT as u32)
+        .view(view)
+        .build()
+        .unwrap();
+
+    Model { canvas: scene() }
+}
+
+fn sx(t: f32) -> f32 {
+    1.5 * (TAU * t + PI / 2.5).sin().pow(3.0)
+}
+
+fn sy(t: f32) -> f32 {
+    0.35 * (3.0 * TAU * t).cos().pow(4.0)
+}
+
+fn ex(t: f32) -> f32 {
+    0.2 * (3.0 * TAU * t).sin()
+}
+
+fn ey(t: f32) -> f32 {
+    -0.8 * (TAU * t - PI / 3.0).sin().pow(2.0)
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
+
+    let k = 350.0;
+    // Pair the curves by the fraction of their length, so the lines spread
+    // evenly along both rather than bunching where either one slows down.
+    let start_pts = evenly(|t| pt2(sx(t), sy(t)) * k, 0.0..1.0, 500).points;
+    let end_pts = evenly(|t| pt2(ex(t), ey(t)) * k, 0.0..1.0, 500).points;
+
+    for (s, e) in start_pts.iter().zip(end_pts) {
+        canvas.line(*s, e).color(DARKKHAKI).weight(0.5);
+    }
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let png = cli::png();
+
+    let draw = app.draw();
+    model.canvas.draw(&draw);
+
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+    app.set_loop_mode(LoopMode::loop_once());
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,67 @@
+use nannou::app::LoopMode;
+use nannou::prelude::*;
+
+use crate::canvas::{Affine, Canvas};
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, img_path};
+
+const WIDTH: u32 = 900;
+const HEIGHT: u32 = 900;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "bloob",
+    title: "Bloob",
+    description: "Rotating rings of translucent circles with a red eye.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH, HEIGHT).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH as f32, HEIGHT as f32);
+    canvas.background(BLACK);
+
+    for i in 0..65 {
+        let theta = i as f32 / 64.0 * TAU;
+        let r = 10.0 * (75.0 - i as f32) * theta.sin();
+        let mut color = srgba(1.0, 1.0, 1.0, i as f32 / 100.0);
+        if i == 10 {
+            color = srgba(1.0, 0.0, 0.0, 1.0);
+        }
+        canvas.with_transform(Affine::rotate(theta), |c| {
+            c.ellipse(pt2(i as f32 * 3.0, 0.0), r, r)
+                .no_fill()
+                .stroke(color)
+                .weight(1.0);
+        });
+    }
+    let x = 64.0 * 3.0;
+    canvas.ellipse(pt2(x, 0.0), 75.0, 75.0).color(RED);
+    canvas.ellipse(pt2(x, 0.0), 50.0, 50.0).color(BLACK);
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    scene().draw(&draw);
+
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
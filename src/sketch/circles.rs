@@ -1,8 +1,9 @@
-use getopts::Options;
 use nannou::prelude::*;
-use std::env;
 
-use sketches::gif_path;
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, gif_path};
 
 const WIDTH: f32 = 700.0;
 const HEIGHT: f32 = 700.0;
@@ -11,10 +12,28 @@ const VERTICES: usize = 30;
 const SCALE: f32 = 300.0;
 const WEIGHT: f32 = 2.0;
 
-fn main() {
+pub const SKETCH: Sketch = Sketch {
+    name: "circles",
+    title: "Circles",
+    description: "Thousands of translucent circles orbiting in a ring.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
     nannou::app(model).update(update).run();
 }
 
+// Time advances by 1/100 a frame, so 628 frames is a full 2 PI loop.
+fn render(rec: &mut Recorder) {
+    rec.delay = 60;
+    for frame in (0..628).step_by(6) {
+        rec.frame(&scene(frame as f32 / 100.0));
+    }
+}
+
 struct Model {
     time: f32,
 }
@@ -32,24 +51,15 @@ fn update(app: &App, model: &mut Model, _update: Update) {
     model.time = app.elapsed_frames() as f32 / 100.0;
 }
 
-fn view(app: &App, model: &Model, frame: Frame) {
-    let args: Vec<String> = env::args().collect();
-    let mut opts = Options::new();
-    opts.optflag("p", "png", "save frames to file as png.");
-    let matches = match opts.parse(&args[1..]) {
-        Ok(m) => m,
-        Err(f) => panic!(f.to_string()),
-    };
-    let png = matches.opt_present("p");
-
-    let draw = app.draw();
-    draw.background().color(BLACK);
+fn scene(time: f32) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
 
     for c in 0..CIRCLES {
         let theta = map_range(c as f32, 0.0, CIRCLES as f32, 0.0, TAU);
-        let center = center(theta, model.time);
-        let size = size(theta, model.time);
-        let color = color(theta, model.time);
+        let center = center(theta, time);
+        let size = size(theta, time);
+        let color = color(theta, time);
         let mut vertices = vec![];
 
         for i in 0..VERTICES {
@@ -58,12 +68,20 @@ fn view(app: &App, model: &Model, frame: Frame) {
             let y = center.y + gamma.sin() * size;
             vertices.push(pt2(x, y));
         }
-        draw.polygon()
+        canvas
+            .polygon(vertices)
             .no_fill()
-            .stroke_color(color)
-            .stroke_weight(WEIGHT)
-            .points(vertices);
+            .stroke(color)
+            .weight(WEIGHT);
     }
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let png = cli::png();
+
+    let draw = app.draw();
+    scene(model.time).draw(&draw);
 
     if png {
         let file_path = gif_path(app, &frame);
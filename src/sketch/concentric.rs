@@ -0,0 +1,76 @@
+use nannou::prelude::*;
+
+use crate::canvas::{Affine, Canvas};
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, img_path};
+
+const WIDTH: f32 = 1200.0;
+const HEIGHT: f32 = 900.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "concentric",
+    title: "Concentric",
+    description: "Concentric ellipses and squares with oscillating transparency.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
+
+    canvas.with_transform(Affine::translate(100.0, -50.0), |canvas| {
+        let rings = 150;
+        for i in 0..rings {
+            let r = (rings - i) as f32 * 5.0;
+            let alpha = 1.0 - i as f32 / rings as f32;
+            let alpha = (alpha * 8.0 * TAU).sin() / (8.0 * alpha);
+            let alpha = map_range(alpha, 0.0, 1.0, 0.0, 0.4);
+            canvas
+                .ellipse(pt2(0.0, 0.0), 1.4 * r, r)
+                .color(srgba(1.0 - alpha, alpha, 1.0 - alpha, alpha));
+            canvas
+                .ellipse(pt2(0.0, 0.0), 60.0, 40.0)
+                .color(srgba(0.0, 0.0, 0.0, 0.6));
+            let corners = [
+                pt2(-WIDTH / 2.0 + 100.0, HEIGHT / 2.0 - 150.0),
+                pt2(-WIDTH / 2.0 + 1000.0, HEIGHT / 2.0 - 850.0),
+            ];
+            for &xy in corners.iter() {
+                canvas
+                    .rect(xy, 1.25 * r, 1.25 * r)
+                    .color(srgba(alpha, alpha, alpha, alpha));
+                canvas
+                    .ellipse(xy, 50.0, 50.0)
+                    .color(srgba(0.0, 0.0, 0.0, 0.6));
+            }
+        }
+    });
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    scene().draw(&draw);
+
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -1,36 +1,42 @@
-use getopts::Options;
 use nannou::app::LoopMode;
 use nannou::prelude::*;
-use std::env;
 
-use sketches::img_path;
+use crate::canvas::{Canvas, Join};
+use crate::raster::Recorder;
+use crate::rng::random_range;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, img_path};
 
 const WIDTH: u32 = 1200;
 const HEIGHT: u32 = 400;
 
-fn main() {
+pub const SKETCH: Sketch = Sketch {
+    name: "cubes",
+    title: "Staircase",
+    description: "Staircase of randomly sized steps.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
     nannou::sketch(view).size(WIDTH, HEIGHT).run()
 }
 
-fn view(app: &App, frame: Frame) {
-    let args: Vec<String> = env::args().collect();
-    let mut opts = Options::new();
-    opts.optflag("p", "png", "save frames to file as png.");
-    let matches = match opts.parse(&args[1..]) {
-        Ok(m) => m,
-        Err(f) => panic!(f.to_string()),
-    };
-    let png = matches.opt_present("p");
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
 
-    app.set_loop_mode(LoopMode::loop_once());
-    let draw = app.draw();
-    draw.background().color(BLACK);
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH as f32, HEIGHT as f32);
+    canvas.background(BLACK);
 
     let p = pt2(-(WIDTH as f32) / 2.0 - 10.0, 50.0);
     let mut points = vec![p];
     let mut i = 0;
     let r0 = 25;
-    let r1 = 90; 
+    let r1 = 90;
     loop {
         let q = random_range(r0, r1) as f32;
         let s = if i % 2 == 0 { 1.0 } else { -1.0 };
@@ -58,25 +64,25 @@ fn view(app: &App, frame: Frame) {
 
     let c = CORNSILK;
     let w = 1.5;
-    draw.polygon()
-        .color(c)
-        .join_miter()
-        .points(points0);
-    draw.polyline()
+    canvas.polygon(points0).color(c);
+    canvas
+        .polyline(points2.clone())
         .color(c)
         .weight(w)
-        .join_miter()
-        .points(points2.clone());
-    draw.polygon()
-        .color(c)
-        .join_miter()
-        .points(points3);
+        .join(Join::Miter);
+    canvas.polygon(points3).color(c);
     for j in 0..=i {
-        draw.line()
-            .points(points[j], points2[j])
-            .weight(w)
-            .color(c);
+        canvas.line(points[j], points2[j]).weight(w).color(c);
     }
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    scene().draw(&draw);
 
     if png {
         let file_path = img_path(app);
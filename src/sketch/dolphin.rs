@@ -0,0 +1,123 @@
+use nannou::color::IntoLinSrgba;
+use nannou::prelude::*;
+
+use crate::canvas::{Canvas, Cap};
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{rotate_pt, with_opacity};
+
+const SIZE: f32 = 603.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "dolphin",
+    title: "Dolphin",
+    description: "Two rings of circles turning over and under a grid.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run()
+}
+
+// The rings have six fold symmetry so 120 frames make a loop.
+fn render(rec: &mut Recorder) {
+    let mut m = Model {
+        bg_angle: 0.,
+        sm_angle: 0.,
+    };
+    for frame in 0..120 {
+        if frame % 2 == 0 {
+            rec.frame(&scene(&m));
+        }
+        step(&mut m);
+    }
+}
+
+#[derive(Debug)]
+struct Model {
+    bg_angle: f32,
+    sm_angle: f32,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(SIZE as u32, SIZE as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    Model {
+        bg_angle: 0.,
+        sm_angle: 0.,
+    }
+}
+
+fn step(m: &mut Model) {
+    m.bg_angle += 1. / 720.;
+    m.sm_angle += 1. / 360.;
+}
+
+fn update(_app: &App, m: &mut Model, _update: Update) {
+    step(m);
+}
+
+fn scene(m: &Model) -> Canvas {
+    let mut canvas = Canvas::new(SIZE, SIZE);
+    canvas.background(LIGHTGRAY);
+    circles(&mut canvas, m.sm_angle + 1. / 12., INDIGO, 70.);
+    grid(&mut canvas);
+    circles(&mut canvas, m.bg_angle, with_opacity(PURPLE, 0.98), 80.);
+    canvas
+}
+
+fn view(app: &App, m: &Model, frame: Frame) {
+    let draw = app.draw();
+    scene(m).draw(&draw);
+    draw.to_frame(app, &frame).unwrap();
+}
+
+fn circles<C>(canvas: &mut Canvas, angle: f32, c: C, s: f32)
+where
+    C: Copy + IntoLinSrgba<f32>,
+{
+    let angles = 0..6;
+    let angles = angles.map(|a| a as f32 / 6.);
+    let angles = angles.map(|x| x + angle);
+    let pts = angles.map(|a| rotate_pt(pt2(0., 200.), a));
+    for p in pts {
+        canvas.ellipse(p, s, s).color(c).stroke(WHITE).weight(2.);
+    }
+}
+
+fn grid(canvas: &mut Canvas) {
+    let spacing = 50.;
+    let sz = 6;
+    let end = sz as f32 * spacing;
+    let xs = -sz..=sz;
+    let xs = xs.map(|x| x as f32 * spacing);
+    let gray = rgb(0.2, 0.2, 0.2);
+    for x in xs {
+        canvas
+            .line(pt2(x + 3., -end), pt2(x + 3., end))
+            .color(gray)
+            .weight(5.)
+            .cap(Cap::Square);
+        canvas
+            .line(pt2(x, -end), pt2(x, end))
+            .color(DIMGRAY)
+            .weight(5.)
+            .cap(Cap::Square);
+        canvas
+            .line(pt2(-end, x - 3.), pt2(end, x - 3.))
+            .color(gray)
+            .weight(5.)
+            .cap(Cap::Square);
+        canvas
+            .line(pt2(-end, x), pt2(end, x))
+            .color(DIMGRAY)
+            .weight(5.)
+            .cap(Cap::Square);
+    }
+}
@@ -1,11 +1,13 @@
-use getopts::Options;
 use itertools::interleave;
 use nannou::app::LoopMode;
 use nannou::noise::NoiseFn;
 use nannou::prelude::*;
-use std::env;
 
-use sketches::{img_path, random_rgb, random_rgba, Grid};
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::rng::random_range;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, img_path, Grid};
 
 const WIDTH: f32 = 1200.0;
 const HEIGHT: f32 = 900.0;
@@ -16,23 +18,27 @@ const K: f64 = 0.002;
 const K2: f64 = 0.004;
 const LINES: usize = 1;
 
-fn main() {
+pub const SKETCH: Sketch = Sketch {
+    name: "double",
+    title: "Double",
+    description: "A pair of flow lines through different noise fields, interleaved.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
     nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
 }
 
-fn view(app: &App, frame: Frame) {
-    let args: Vec<String> = env::args().collect();
-    let mut opts = Options::new();
-    opts.optflag("p", "png", "save frames to file as png.");
-    let matches = match opts.parse(&args[1..]) {
-        Ok(m) => m,
-        Err(f) => panic!(f.to_string()),
-    };
-    let png = matches.opt_present("p");
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
 
-    app.set_loop_mode(LoopMode::loop_once());
-    let draw = app.draw();
-    draw.background().color(BLACK);
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
 
     let nn = nannou::noise::BasicMulti::new();
     let mm = nannou::noise::BasicMulti::new();
@@ -40,7 +46,7 @@ fn view(app: &App, frame: Frame) {
     let grid1 = Grid::new(1.1 * WIDTH, 1.1 * HEIGHT, GRID_SPACING, |x, y| {
         TAU * nn.get([K * x as f64, K * y as f64]) as f32
     });
-    
+
     let grid2 = Grid::new(1.1 * WIDTH, 1.1 * HEIGHT, GRID_SPACING, |x, y| {
         TAU * mm.get([K2 * x as f64, K2 * y as f64]) as f32
     });
@@ -50,7 +56,7 @@ fn view(app: &App, frame: Frame) {
             random_range(-WIDTH / 2.0, WIDTH / 2.0),
             random_range(-HEIGHT / 2.0, HEIGHT / 2.0),
         );
-        let mut loc2 = loc1.clone();
+        let mut loc2 = loc1;
         let mut points1 = vec![];
         let mut points2 = vec![];
 
@@ -62,16 +68,20 @@ fn view(app: &App, frame: Frame) {
             loc1.x += STEP_SIZE * angle1.cos();
             loc1.y += STEP_SIZE * angle1.sin();
             loc2.x += STEP_SIZE * angle2.cos();
-            loc2.y += STEP_SIZE *  angle2.sin();
+            loc2.y += STEP_SIZE * angle2.sin();
         }
-        // points2.reverse();
-        // points1.extend(points2);
-        // draw.polygon()
-        //     .points(points1)
-        //     .color(random_rgba());
         let points = interleave(points1, points2);
-        draw.polyline().weight(2.0).color(WHITE).points(points);
+        canvas.polyline(points).weight(2.0).color(WHITE);
     }
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    scene().draw(&draw);
 
     if png {
         let file_path = img_path(app);
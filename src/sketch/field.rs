@@ -0,0 +1,111 @@
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::hatch::{Hatch, Pattern};
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::vector::save_svg;
+use crate::{cli, img_path, output_path, random_rgb, Grid};
+
+const WIDTH: f32 = 1200.0;
+const HEIGHT: f32 = 900.0;
+const GRID_SPACING: f32 = 2.0;
+const LENGTH: usize = 500;
+const K: f64 = 0.003;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "field",
+    title: "Field",
+    description: "Bands between neighbouring flow lines filled with random colors, also saved as svg and hatched for a plotter.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+struct Model {
+    canvas: Canvas,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(WIDTH as u32, HEIGHT as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    Model { canvas: scene() }
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(CORNSILK);
+
+    let nn = nannou::noise::BasicMulti::new();
+
+    let grid = Grid::new(1.1 * WIDTH, 1.1 * HEIGHT, GRID_SPACING, |x, y| {
+        TAU * nn.get([K * x as f64, K * y as f64]) as f32
+    });
+
+    for i in 0..(grid.cols() / 4) {
+        let mut l1 = pt2(-WIDTH / 2.0 + GRID_SPACING * i as f32, 0.0);
+        let mut l2 = pt2(-WIDTH / 2.0 + GRID_SPACING * (i + 1) as f32, 0.0);
+        let mut up = vec![];
+        for _i in 0..LENGTH {
+            up.push(l1);
+            let angle = &grid.get(l1.x, l1.y);
+            l1.x += 10.0 * angle.cos();
+            l1.y += 10.0 * angle.sin();
+        }
+        let mut dn = vec![];
+        for _i in 0..LENGTH {
+            dn.push(l2);
+            let angle = &grid.get(l2.x, l2.y);
+            l2.x += 10.0 * angle.cos();
+            l2.y += 10.0 * angle.sin();
+        }
+        dn.reverse();
+        up.extend(dn);
+
+        canvas.polygon(up).color(random_rgb());
+    }
+    canvas
+}
+
+// Tonal hatching across the bands in black.
+fn plot(canvas: &Canvas) -> Canvas {
+    Hatch::new(Pattern::Parallel)
+        .spacing(1.5)
+        .tone(8.0)
+        .angle(PI / 3.0)
+        .pen(BLACK)
+        .canvas(canvas)
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    model.canvas.draw(&draw);
+
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+        save_svg(&model.canvas, output_path(SKETCH.name, "svg"));
+        save_svg(
+            &plot(&model.canvas),
+            output_path(&format!("{}-hatched", SKETCH.name), "svg"),
+        );
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,71 @@
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::polyline::catmull_rom;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{hires, random_rgba, Grid};
+
+const WIDTH: f32 = 15_000.0;
+const HEIGHT: f32 = 12_500.0;
+const GRID_SPACING: f32 = 50.0;
+const LENGTH: usize = 200;
+const STEP: f32 = 250.0;
+const K: f64 = 0.0015;
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(CORNSILK);
+
+    let nn = nannou::noise::BasicMulti::new();
+
+    let grid = Grid::new(1.1 * WIDTH, 1.1 * HEIGHT, GRID_SPACING, |x, y| {
+        TAU * nn.get([K * x as f64, K * y as f64]) as f32
+    });
+
+    for i in 0..(grid.cols() / 4) {
+        let mut l1 = pt2(-WIDTH / 2.0 + GRID_SPACING * i as f32, 0.0);
+        let mut l2 = pt2(-WIDTH / 2.0 + GRID_SPACING * (i + 1) as f32, 0.0);
+        let mut up = vec![];
+        for _i in 0..LENGTH {
+            up.push(l1);
+            let angle = &grid.get(l1.x, l1.y);
+            l1.x += STEP * angle.cos();
+            l1.y += STEP * angle.sin();
+        }
+        let mut dn = vec![];
+        for _i in 0..LENGTH {
+            dn.push(l2);
+            let angle = &grid.get(l2.x, l2.y);
+            l2.x += STEP * angle.cos();
+            l2.y += STEP * angle.sin();
+        }
+        // The long steps leave corners, smooth them through the traced points.
+        let mut up = catmull_rom(&up, 8, false);
+        let mut dn = catmull_rom(&dn, 8, false);
+        dn.reverse();
+        up.append(&mut dn);
+
+        canvas.polygon(up).color(random_rgba());
+    }
+    canvas
+}
+
+pub const SKETCH: Sketch = Sketch {
+    name: "field_lg",
+    title: "Field (high resolution)",
+    description: "High resolution version of field rendered to a texture.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    hires::run(scene);
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
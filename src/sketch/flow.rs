@@ -0,0 +1,92 @@
+use nannou::app::LoopMode;
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::hatch::{Hatch, Pattern};
+use crate::raster::Recorder;
+use crate::rng::random_range;
+use crate::sketch::{Kind, Sketch};
+use crate::vector::save_svg;
+use crate::{cli, img_path, output_path, random_rgb, Grid};
+
+const WIDTH: f32 = 1200.0;
+const HEIGHT: f32 = 900.0;
+const GRID_SPACING: f32 = 50.0;
+const LENGTH: usize = 1000;
+const K: f64 = 0.002;
+const LINES: usize = 50;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "flow",
+    title: "Flow",
+    description: "Flow lines through a noise field filled as polygons.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(CORNSILK);
+
+    let nn = nannou::noise::BasicMulti::new();
+
+    let grid = Grid::new(1.1 * WIDTH, 1.1 * HEIGHT, GRID_SPACING, |x, y| {
+        TAU * nn.get([K * x as f64, K * y as f64]) as f32
+    });
+
+    for _ in 0..LINES {
+        let mut loc = pt2(
+            random_range(-WIDTH / 2.0, WIDTH / 2.0),
+            random_range(-HEIGHT / 2.0, HEIGHT / 2.0),
+        );
+        let mut points = vec![];
+        for _i in 0..LENGTH {
+            points.push(loc);
+            let angle = &grid.get(loc.x, loc.y);
+            loc.x += angle.cos();
+            loc.y += angle.sin();
+        }
+        canvas.polygon(points).color(random_rgb());
+    }
+    canvas
+}
+
+// Cross hatching in black with the density of each fill, with `-p`.
+fn plot(canvas: &Canvas) -> Canvas {
+    Hatch::new(Pattern::Cross)
+        .spacing(4.0)
+        .tone(16.0)
+        .pen(BLACK)
+        .canvas(canvas)
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    let canvas = scene();
+    canvas.draw(&draw);
+
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+        save_svg(
+            &plot(&canvas),
+            output_path(&format!("{}-hatched", SKETCH.name), "svg"),
+        );
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,75 @@
+use nannou::app::LoopMode;
+use nannou::geom::Rect;
+use nannou::prelude::*;
+
+use crate::canvas::{Affine, Canvas};
+use crate::hexgrid::Layout;
+use crate::img_path;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::truchet::{kolmyjec, Grid, Truchet};
+
+const SIZE: f32 = 35.0;
+const WIDTH: f32 = 500.0;
+const HEIGHT: f32 = 900.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "hex",
+    title: "HexO",
+    description: "Hexagonal tiling of lines and circles in random orientations.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let bounds = Rect::from_w_h(WIDTH, HEIGHT);
+    let layout = Layout::flat_in(SIZE, bounds);
+    let h = layout.spacing().y / 2.0;
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
+    // Only the placement comes from the tiling, the tiles are drawn as
+    // circles that the tiles drawn after them cover up.
+    let truchet = Truchet::random(Grid::hex(layout, bounds), kolmyjec());
+    for (cell, tile) in truchet.cells() {
+        let Point2 { x, y } = cell.center;
+        let angle = tile.turns as f32 * PI / 3.0;
+        let t = Affine::rotate(angle).then(&Affine::translate(x, y));
+        canvas.with_transform(t, |c| {
+            c.line(pt2(0.0, -h), pt2(0.0, h)).color(WHITE).weight(2.0);
+            c.ellipse(pt2(-SIZE, 0.0), SIZE, SIZE)
+                .color(BLACK)
+                .stroke(WHITE)
+                .weight(2.0);
+            c.rect(pt2(-SIZE, 0.0), SIZE - 1.0, SIZE - 1.0).color(BLACK);
+            c.ellipse(pt2(SIZE, 0.0), SIZE, SIZE)
+                .color(BLACK)
+                .stroke(WHITE)
+                .weight(2.0);
+        });
+    }
+    canvas
+        .rect(pt2(0.0, 0.0), WIDTH, HEIGHT)
+        .no_fill()
+        .stroke(BLACK)
+        .weight(350.0);
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    scene().draw(&draw);
+    let file_path = img_path(app);
+    app.main_window().capture_frame(file_path);
+    draw.to_frame(app, &frame).unwrap();
+}
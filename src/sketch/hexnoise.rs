@@ -0,0 +1,105 @@
+use nannou::app::LoopMode;
+use nannou::geom::Rect;
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::hexgrid::Layout;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::truchet::{kolmyjec, Grid, Truchet};
+use crate::{cli, img_path};
+
+const SIZE: f32 = 25.0;
+const WIDTH: f32 = 900.0;
+const HEIGHT: f32 = 900.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "hexnoise",
+    title: "Hex Noise",
+    description: "Hex Variation with line widths thinning over time.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run()
+}
+
+// The lines stop thinning after 420 frames.
+fn render(rec: &mut Recorder) {
+    let mut model = new_model();
+    for frame in 0..=420 {
+        if frame % 6 == 0 {
+            rec.frame(&scene(&model));
+        }
+        step(&mut model);
+    }
+}
+
+struct Model {
+    line_width: f32,
+    lines: Vec<Vec<Point2>>,
+}
+
+fn new_model() -> Model {
+    let bounds = Rect::from_w_h(WIDTH, HEIGHT);
+    let grid = Grid::hex(Layout::flat_in(SIZE, bounds), bounds);
+    let lines = Truchet::random(grid, kolmyjec()).polylines();
+
+    Model {
+        line_width: 25.0,
+        lines,
+    }
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(WIDTH as u32, HEIGHT as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    new_model()
+}
+
+fn step(model: &mut Model) {
+    if model.line_width > 4.0 {
+        model.line_width -= 0.05;
+    }
+}
+
+fn update(_app: &App, model: &mut Model, _update: Update) {
+    step(model);
+}
+
+fn scene(model: &Model) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
+    for line in &model.lines {
+        canvas
+            .polyline(line.iter().copied())
+            .join_round()
+            .color(GRAY)
+            .weight(model.line_width);
+    }
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let png = cli::png();
+
+    let draw = app.draw();
+    scene(model).draw(&draw);
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+
+    // Turn off loop when done to save cpu cycles.
+    if model.line_width < 4.1 {
+        app.set_loop_mode(LoopMode::loop_once());
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
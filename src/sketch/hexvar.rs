@@ -0,0 +1,59 @@
+use nannou::geom::Rect;
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::hexgrid::Layout;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::truchet::{kolmyjec, Grid, Truchet};
+use crate::{cli, img_path};
+
+const SIZE: f32 = 25.0;
+const WIDTH: f32 = 900.0;
+const HEIGHT: f32 = 900.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "hexvar",
+    title: "Hex Variations",
+    description: "Hexagonal Truchet tiles of a line and two arcs.",
+    credits: Some("Based on \"Hex Variation\" by William Kolmyjec, originally published in \"Computer Graphics and Art\" vol3 no4, 1978."),
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view)
+        .size(WIDTH as u32, HEIGHT as u32)
+        .run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let bounds = Rect::from_w_h(WIDTH, HEIGHT);
+    let grid = Grid::hex(Layout::flat_in(SIZE, bounds), bounds);
+    let truchet = Truchet::random(grid, kolmyjec());
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
+    for line in truchet.polylines() {
+        canvas.polyline(line).join_round().color(GRAY).weight(5.0);
+    }
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+
+    let draw = app.draw();
+    scene().draw(&draw);
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,31 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::hires;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(6_000.0, 6_000.0);
+    canvas.background(BLACK);
+    canvas.ellipse(pt2(0.0, 0.0), 1000.0, 1000.0).color(ORANGE);
+    canvas
+}
+
+pub const SKETCH: Sketch = Sketch {
+    name: "hi_res",
+    title: "Hi-Res",
+    description: "Template for rendering a high resolution image to a texture.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    hires::run(scene);
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
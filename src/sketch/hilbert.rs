@@ -0,0 +1,108 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::curves::{swerve, Curve};
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, img_path};
+
+const WIDTH: f32 = 700.0;
+const SIZE: f32 = WIDTH + 100.0;
+
+const ORDER: usize = 6;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "hilbert",
+    title: "Hilbert Swerve",
+    description: "Hilbert's space filling curve twisted with simplex noise.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run()
+}
+
+// One segment is drawn a frame, capture every 64th to keep the gif small.
+fn render(rec: &mut Recorder) {
+    let mut model = new_model();
+    let mut canvas = Canvas::new(SIZE, SIZE);
+    canvas.background(BLACK);
+    rec.draw(&canvas);
+    while model.index < model.path.len() - 1 {
+        rec.draw(&scene(&model));
+        if model.index.is_multiple_of(64) {
+            rec.capture();
+        }
+        model.index += 1;
+    }
+    rec.frame(&scene(&model));
+}
+
+#[derive(Debug)]
+struct Model {
+    path: Vec<Point2>,
+    index: usize,
+}
+
+fn new_model() -> Model {
+    let nn = nannou::noise::OpenSimplex::new();
+    let square = Rect::from_corners(pt2(0.0, 0.0), pt2(WIDTH, WIDTH));
+    let path = Curve::Hilbert.fit(ORDER, square);
+    let path = swerve(&path, &nn, 0.01, 0.04 * WIDTH)
+        .into_iter()
+        .map(|p| p - vec2(WIDTH / 2.0, WIDTH / 2.0))
+        .collect();
+
+    Model { path, index: 1 }
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(SIZE as u32, SIZE as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    new_model()
+}
+
+fn update(_app: &App, model: &mut Model, _update: Update) {
+    if model.index < model.path.len() - 1 {
+        model.index += 1;
+    }
+}
+
+// Only the newest segment, earlier ones stay on screen.
+fn scene(model: &Model) -> Canvas {
+    let mut canvas = Canvas::new(SIZE, SIZE);
+    canvas
+        .line(model.path[model.index - 1], model.path[model.index])
+        .weight(2.0)
+        .caps_round()
+        .color(WHITE);
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let png = cli::png();
+
+    let draw = app.draw();
+    if frame.nth() == 0 {
+        frame.clear(BLACK);
+    }
+
+    scene(model).draw(&draw);
+
+    if png && frame.nth() == pow(2, ORDER) * pow(2, ORDER) - 1 {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+
+    if model.index >= pow(2, ORDER) * pow(2, ORDER) {
+        app.set_loop_mode(LoopMode::loop_once());
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,44 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::curves::{swerve, Curve};
+use crate::hires;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+
+const SIZE: f32 = 6_000.0;
+const ORDER: usize = 6;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "hilbert_lg",
+    title: "Hilbert Swerve (high resolution)",
+    description: "High resolution version of Hilbert Swerve rendered to a texture.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    hires::run(scene);
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(SIZE, SIZE);
+    canvas.background(BLACK);
+    let nn = nannou::noise::OpenSimplex::new();
+
+    let width = SIZE * 0.8;
+    let square = Rect::from_corners(pt2(0.0, 0.0), pt2(width, width));
+    let path = Curve::Hilbert.fit(ORDER, square);
+    let path: Vec<Point2> = swerve(&path, &nn, 0.0025, 0.04 * width)
+        .into_iter()
+        .map(|p| p - vec2(width / 2.0, width / 2.0))
+        .collect();
+    canvas.polyline(path).weight(10.0).join_round().color(WHITE);
+    canvas
+}
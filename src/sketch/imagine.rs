@@ -0,0 +1,421 @@
+use getopts::{Matches, Options};
+use nannou::image::RgbaImage;
+use nannou::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::canvas::Canvas;
+use crate::cli;
+use crate::raster;
+use crate::sampler::{self, Along, Ray, Rule, Sampler};
+use crate::sketch::{Kind, Sketch};
+
+pub const SKETCH: Sketch = Sketch {
+    name: "imagine",
+    title: "Imagine",
+    description: "Streaks of color pulled from an image, style chosen by key, or every style of many images with `--batch`.",
+    credits: None,
+    kind: Kind::Interactive,
+    run,
+    render: None,
+};
+
+// The contact sheet tiles are at most this many pixels on a side.
+const TILE_SIZE: u32 = 400;
+const TILE_GAP: u32 = 8;
+
+const EXTENSIONS: [&str; 8] = ["png", "jpg", "jpeg", "gif", "bmp", "tif", "tiff", "webp"];
+
+fn options() -> Matches {
+    let mut opts = Options::new();
+    opts.optflag("b", "batch", "render every style without a window.");
+    opts.optmulti(
+        "",
+        "style",
+        "styles to render in batch mode, comma separated, or `all`.",
+        "STYLE",
+    );
+    opts.optopt("o", "out", "directory to save renders in.", "DIR");
+    cli::parse(opts)
+}
+
+fn run() {
+    if options().opt_present("b") {
+        batch();
+    } else {
+        nannou::app(model).run();
+    }
+}
+
+// Where renders are saved, `img/imagine` unless `--out` is given.
+fn out_dir(matches: &Matches) -> PathBuf {
+    match matches.opt_str("o") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("img")
+            .join("imagine"),
+    }
+}
+
+// `<image>-<style>.png` in `dir`.
+fn render_path(dir: &Path, image: &Path, name: &str) -> PathBuf {
+    let stem = image
+        .file_stem()
+        .unwrap_or_else(|| panic!("no file name in {}", image.display()))
+        .to_string_lossy();
+    dir.join(format!("{}-{}.png", stem, name))
+}
+
+fn save(image: &RgbaImage, path: &Path) {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).expect("failed to create output directory");
+    }
+    image.save(path).expect("failed to save png image");
+    println!("{}", path.display());
+}
+
+// The images named by `paths`, each a file or a directory whose images are
+// taken in name order.
+fn inputs(paths: &[String]) -> Vec<PathBuf> {
+    let mut images = vec![];
+    for path in paths.iter().map(PathBuf::from) {
+        if path.is_dir() {
+            let mut found: Vec<PathBuf> = fs::read_dir(&path)
+                .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e))
+                .map(|entry| entry.unwrap().path())
+                .filter(|p| p.is_file() && is_image(p))
+                .collect();
+            found.sort();
+            images.extend(found);
+        } else if path.is_file() {
+            images.push(path);
+        } else {
+            panic!("no image or directory at {}", path.display());
+        }
+    }
+    images
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .map(|e| EXTENSIONS.contains(&e.to_string_lossy().to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+// The styles asked for with `--style`, all of them if none are.
+fn styles(matches: &Matches) -> Vec<Style> {
+    let names: Vec<String> = matches
+        .opt_strs("style")
+        .iter()
+        .flat_map(|s| s.split(','))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if names.is_empty() || names.iter().any(|n| n == "all") {
+        return Style::ALL.to_vec();
+    }
+    names
+        .iter()
+        .map(|n| {
+            Style::from_name(n).unwrap_or_else(|| {
+                let known: Vec<&str> = Style::ALL.iter().map(|s| s.name()).collect();
+                panic!(
+                    "unknown style `{}`, expected one of {}",
+                    n,
+                    known.join(", ")
+                )
+            })
+        })
+        .collect()
+}
+
+// Renders are named by the image's stem, so two images with the same stem,
+// like `a/photo.jpg` and `b/photo.png`, would overwrite each other. The first
+// two images that clash and the file they would share.
+fn collision<'a>(
+    images: &'a [PathBuf],
+    styles: &[Style],
+    dir: &Path,
+) -> Option<(&'a Path, &'a Path, PathBuf)> {
+    let mut names: Vec<&str> = styles.iter().map(|s| s.name()).collect();
+    if styles.len() > 1 {
+        names.push("sheet");
+    }
+    let mut outputs: HashMap<PathBuf, &Path> = HashMap::new();
+    for image in images {
+        for name in &names {
+            let path = render_path(dir, image, name);
+            if let Some(other) = outputs.insert(path.clone(), image) {
+                return Some((other, image, path));
+            }
+        }
+    }
+    None
+}
+
+fn batch() {
+    let matches = options();
+    let images = inputs(&matches.free);
+    if images.is_empty() {
+        panic!("Must provide images or directories of images");
+    }
+    let styles = styles(&matches);
+    let dir = out_dir(&matches);
+    if let Some((a, b, path)) = collision(&images, &styles, &dir) {
+        panic!(
+            "{} and {} would both be saved as {}, rename one of them",
+            a.display(),
+            b.display(),
+            path.display()
+        );
+    }
+    for path in &images {
+        let sampler = Sampler::open(path);
+        let mut tiles = vec![];
+        for style in &styles {
+            let image = sampler::render(&style.rules(), &sampler);
+            save(&image, &render_path(&dir, path, style.name()));
+            tiles.push(raster::thumbnail(&image, TILE_SIZE));
+        }
+        if tiles.len() > 1 {
+            let columns = (tiles.len() as f32).sqrt().ceil() as u32;
+            let sheet = raster::contact_sheet(&tiles, columns, TILE_GAP);
+            save(&sheet, &render_path(&dir, path, "sheet"));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    NegY,
+    NegYup,
+    NegYright,
+    NegYleft,
+    PosY,
+    PosYup,
+    PosYright,
+    PosYleft,
+    Line,
+    Miter,
+    CircleIn,
+    CircleOut,
+}
+
+impl Style {
+    const ALL: [Style; 12] = [
+        Style::NegY,
+        Style::NegYup,
+        Style::NegYright,
+        Style::NegYleft,
+        Style::PosY,
+        Style::PosYup,
+        Style::PosYright,
+        Style::PosYleft,
+        Style::Line,
+        Style::Miter,
+        Style::CircleIn,
+        Style::CircleOut,
+    ];
+
+    // The name used by `--style` and in file names.
+    fn name(self) -> &'static str {
+        match self {
+            Style::NegY => "neg-y",
+            Style::NegYup => "neg-y-up",
+            Style::NegYright => "neg-y-right",
+            Style::NegYleft => "neg-y-left",
+            Style::PosY => "pos-y",
+            Style::PosYup => "pos-y-up",
+            Style::PosYright => "pos-y-right",
+            Style::PosYleft => "pos-y-left",
+            Style::Line => "line",
+            Style::Miter => "miter",
+            Style::CircleIn => "circle-in",
+            Style::CircleOut => "circle-out",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Style> {
+        Style::ALL.iter().copied().find(|s| s.name() == name)
+    }
+
+    // Streaks from the falling diagonal for the `NegY` styles and the rising
+    // one for the `PosY` styles.
+    fn rules(self) -> Vec<Rule> {
+        let falling = Rule::new(Along::Falling);
+        let rising = Rule::new(Along::Rising);
+        let circle = Rule::new(Along::Circle(1.0 / 3.0)).weight(2.0);
+        vec![match self {
+            Style::NegY => falling.ray(Ray::Down),
+            Style::NegYup => falling.ray(Ray::Up),
+            Style::NegYright => falling.ray(Ray::Right),
+            Style::NegYleft => falling.ray(Ray::Left),
+            Style::PosY => rising.ray(Ray::Down),
+            Style::PosYup => rising.ray(Ray::Up),
+            Style::PosYright => rising.ray(Ray::Right),
+            Style::PosYleft => rising.ray(Ray::Left),
+            Style::Line => falling.ray(Ray::Down).ray(Ray::Up),
+            Style::Miter => falling.ray(Ray::Down).ray(Ray::Right),
+            Style::CircleIn => circle.ray(Ray::In),
+            Style::CircleOut => circle.ray(Ray::Out),
+        }]
+    }
+}
+
+struct Model {
+    path: PathBuf,
+    out: PathBuf,
+    sampler: Sampler,
+    texture: wgpu::Texture,
+    style: Style,
+}
+
+fn model(app: &App) -> Model {
+    let matches = options();
+    if matches.free.len() != 1 {
+        panic!("Must provide a filename argument");
+    }
+    // A path to the image, or its name in the assets folder.
+    let name = &matches.free[0];
+    let mut path = PathBuf::from(name);
+    if !path.exists() {
+        path = app.assets_path().unwrap().join(name);
+    }
+    let sampler = Sampler::open(&path);
+    let (w, h) = sampler.image.dimensions();
+    app.new_window()
+        .size(w, h)
+        .view(view)
+        .key_pressed(key_pressed)
+        .key_released(key_released)
+        .build()
+        .unwrap();
+    app.set_loop_mode(LoopMode::Wait);
+    let texture = wgpu::Texture::from_path(app, &path).unwrap();
+    Model {
+        path,
+        out: out_dir(&matches),
+        texture,
+        sampler,
+        style: Style::NegY,
+    }
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    frame.clear(BLACK);
+    draw.texture(&model.texture);
+
+    let (w, h) = model.sampler.image.dimensions();
+    let mut canvas = Canvas::new(w as f32, h as f32);
+    sampler::draw(&model.style.rules(), &model.sampler, &mut canvas);
+    canvas.draw(&draw);
+
+    draw.to_frame(app, &frame).unwrap();
+}
+
+fn key_pressed(_app: &App, model: &mut Model, key: Key) {
+    match key {
+        Key::Key1 => {
+            model.style = Style::NegY;
+        }
+        Key::Key2 => {
+            model.style = Style::NegYup;
+        }
+        Key::Key3 => {
+            model.style = Style::NegYright;
+        }
+        Key::Key4 => {
+            model.style = Style::NegYleft;
+        }
+        Key::Key5 => {
+            model.style = Style::PosY;
+        }
+        Key::Key6 => {
+            model.style = Style::PosYup;
+        }
+        Key::Key7 => {
+            model.style = Style::PosYright;
+        }
+        Key::Key8 => {
+            model.style = Style::PosYleft;
+        }
+        Key::Key9 => {
+            model.style = Style::Line;
+        }
+        Key::Key0 => {
+            model.style = Style::Miter;
+        }
+        Key::Q => {
+            model.style = Style::CircleIn;
+        }
+        Key::W => {
+            model.style = Style::CircleOut;
+        }
+        _otherkey => (),
+    }
+}
+
+// Saves the current style at the image's own size, whatever the window's.
+fn key_released(_app: &App, model: &mut Model, key: Key) {
+    match key {
+        Key::S => {
+            let image = sampler::render(&model.style.rules(), &model.sampler);
+            save(
+                &image,
+                &render_path(&model.out, &model.path, model.style.name()),
+            );
+        }
+        _otherkey => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(args: &[&str]) -> Matches {
+        let mut opts = Options::new();
+        opts.optmulti("", "style", "", "STYLE");
+        opts.parse(args).unwrap()
+    }
+
+    #[test]
+    fn styles_by_name() {
+        for style in Style::ALL.iter() {
+            assert_eq!(Style::from_name(style.name()), Some(*style));
+        }
+        assert_eq!(styles(&matches(&[])).len(), 12);
+        assert_eq!(styles(&matches(&["--style", "miter,all"])).len(), 12);
+        assert_eq!(
+            styles(&matches(&["--style", "line, circle-in", "--style=pos-y"])),
+            vec![Style::Line, Style::CircleIn, Style::PosY]
+        );
+    }
+
+    #[test]
+    fn renders_are_named_by_stem_and_style() {
+        let path = render_path(Path::new("out"), Path::new("a/photo.jpg"), "neg-y-up");
+        assert_eq!(path, Path::new("out/photo-neg-y-up.png"));
+    }
+
+    #[test]
+    fn collisions_are_found() {
+        let images = |paths: &[&str]| paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+        let dir = Path::new("out");
+        let apart = images(&["a/photo.jpg", "a/still.jpg"]);
+        assert_eq!(collision(&apart, &Style::ALL, dir), None);
+        for clash in [
+            ["a/photo.jpg", "b/photo.png"],
+            ["a/photo.jpg", "a/photo.png"],
+        ]
+        .iter()
+        {
+            let clash = images(clash);
+            let (x, y, path) = collision(&clash, &[Style::Line], dir).unwrap();
+            assert_eq!((x, y), (clash[0].as_path(), clash[1].as_path()));
+            assert_eq!(path, Path::new("out/photo-line.png"));
+        }
+    }
+}
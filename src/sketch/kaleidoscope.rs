@@ -0,0 +1,132 @@
+use nannou::color::white_point::D65;
+use nannou::color::{Alpha, Lab};
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::particles::{Bounds, Particle, Policy, System};
+use crate::random_color;
+use crate::raster::Recorder;
+use crate::rng::{random, random_range};
+use crate::sketch::{Kind, Sketch};
+use crate::symmetry::{Group, Symmetry};
+
+pub const SKETCH: Sketch = Sketch {
+    name: "kaleidoscope",
+    title: "Kaleidoscope",
+    description: "Drifting ellipses mirrored into a kaleidoscope by a dihedral symmetry group.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run()
+}
+
+fn render(rec: &mut Recorder) {
+    let mut m = new_model(&Settings::new());
+    for frame in 0..360 {
+        if frame % 3 == 0 {
+            rec.frame(&scene(&m));
+        }
+        step(&mut m);
+    }
+}
+
+struct Settings {
+    size: u32,
+    dots: u32,
+    mirrors: usize,
+}
+
+impl Settings {
+    fn new() -> Self {
+        let size = 900;
+        let dots = 75;
+        let mirrors = 5;
+        Settings {
+            size,
+            dots,
+            mirrors,
+        }
+    }
+}
+
+struct Model {
+    size: f32,
+    balls: System<Look>,
+    mirrors: usize,
+}
+
+#[derive(Clone)]
+struct Look {
+    a: f32,
+    b: f32,
+    color: Alpha<Lab<D65, f32>, f32>,
+}
+
+fn display(ball: &Particle<Look>, canvas: &mut Canvas) {
+    let Look { a, b, color } = ball.data;
+    canvas.ellipse(ball.position, a, b).color(color);
+}
+
+fn random_ball() -> Particle<Look> {
+    let a = random_range(20.0, 100.0);
+    let circle: bool = random();
+    let b = if circle { a } else { random_range(20.0, 100.0) };
+    let look = Look {
+        a,
+        b,
+        color: random_color(),
+    };
+    let position = pt2(random_range(-200.0, 200.0), random_range(-200.0, 200.0));
+    Particle::with_data(position, look).velocity(vec2(random(), random()).normalize_to(0.5))
+}
+
+fn new_model(settings: &Settings) -> Model {
+    let mut balls = System::new().bounds(Bounds::Circle(pt2(0.0, 0.0), 350.0), Policy::Bounce);
+    for _ in 0..settings.dots {
+        balls.add(random_ball());
+    }
+    Model {
+        size: settings.size as f32,
+        balls,
+        mirrors: settings.mirrors,
+    }
+}
+
+fn model(app: &App) -> Model {
+    let settings = Settings::new();
+    app.new_window()
+        .size(settings.size, settings.size)
+        .view(view)
+        .build()
+        .unwrap();
+    new_model(&settings)
+}
+
+fn step(m: &mut Model) {
+    m.balls.step();
+}
+
+fn update(_app: &App, m: &mut Model, _update: Update) {
+    step(m);
+}
+
+fn scene(m: &Model) -> Canvas {
+    let mut canvas = Canvas::new(m.size, m.size);
+    canvas.background(BLACK);
+    Symmetry::new(Group::Dihedral(m.mirrors)).draw(&mut canvas, |c| {
+        for b in m.balls.particles.iter() {
+            display(b, c)
+        }
+    });
+    canvas
+}
+
+fn view(app: &App, m: &Model, frame: Frame) {
+    let draw = app.draw();
+    scene(m).draw(&draw);
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,155 @@
+// The sketch registry. Every sketch lives in its own module and registers
+// itself with a `SKETCH` constant describing it; the `sketches` launcher uses
+// this list to find, describe and run them.
+
+use std::fmt;
+
+use crate::raster::Recorder;
+
+pub mod azul;
+pub mod bird;
+pub mod bloob;
+pub mod circles;
+pub mod concentric;
+pub mod cubes;
+pub mod dolphin;
+pub mod double;
+pub mod field;
+pub mod field_lg;
+pub mod flow;
+pub mod hex;
+pub mod hexnoise;
+pub mod hexvar;
+pub mod hi_res;
+pub mod hilbert;
+pub mod hilbert_lg;
+pub mod imagine;
+pub mod kaleidoscope;
+pub mod paper;
+pub mod parastichy;
+pub mod pendulum;
+pub mod plants;
+pub mod plumbing;
+pub mod race;
+pub mod race_lg;
+pub mod reveal;
+pub mod rings;
+pub mod skia_test;
+pub mod sparks;
+pub mod squares;
+pub mod sunflower;
+pub mod trig;
+pub mod truchet;
+pub mod twisted;
+pub mod wheel;
+pub mod wicker;
+pub mod worms;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Draws a single image.
+    Static,
+    /// Plays an animation.
+    Animated,
+    /// Responds to mouse or keyboard input.
+    Interactive,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Kind::Static => "static",
+            Kind::Animated => "animated",
+            Kind::Interactive => "interactive",
+        };
+        f.pad(s)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sketch {
+    pub name: &'static str,
+    /// Heading used for the sketch in the gallery.
+    pub title: &'static str,
+    pub description: &'static str,
+    pub credits: Option<&'static str>,
+    pub kind: Kind,
+    pub run: fn(),
+    /// Draw the sketch without a window, one canvas per frame. Sketches that
+    /// need input to draw anything have none.
+    pub render: Option<fn(&mut Recorder)>,
+}
+
+impl Sketch {
+    /// Run the sketch with `args` as its command line arguments.
+    pub fn launch(&self, args: Vec<String>) {
+        crate::cli::set_invocation(self.name, args);
+        if let Some(seed) = crate::cli::seed() {
+            crate::rng::seed(seed);
+        }
+        (self.run)()
+    }
+}
+
+pub const SKETCHES: &[Sketch] = &[
+    azul::SKETCH,
+    bird::SKETCH,
+    bloob::SKETCH,
+    circles::SKETCH,
+    concentric::SKETCH,
+    cubes::SKETCH,
+    dolphin::SKETCH,
+    double::SKETCH,
+    field::SKETCH,
+    field_lg::SKETCH,
+    flow::SKETCH,
+    hex::SKETCH,
+    hexnoise::SKETCH,
+    hexvar::SKETCH,
+    hi_res::SKETCH,
+    hilbert::SKETCH,
+    hilbert_lg::SKETCH,
+    imagine::SKETCH,
+    kaleidoscope::SKETCH,
+    paper::SKETCH,
+    parastichy::SKETCH,
+    pendulum::SKETCH,
+    plants::SKETCH,
+    plumbing::SKETCH,
+    race::SKETCH,
+    race_lg::SKETCH,
+    reveal::SKETCH,
+    rings::SKETCH,
+    skia_test::SKETCH,
+    sparks::SKETCH,
+    squares::SKETCH,
+    sunflower::SKETCH,
+    trig::SKETCH,
+    truchet::SKETCH,
+    twisted::SKETCH,
+    wheel::SKETCH,
+    wicker::SKETCH,
+    worms::SKETCH,
+];
+
+pub fn find(name: &str) -> Option<&'static Sketch> {
+    SKETCHES.iter().find(|s| s.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_unique_and_match_modules() {
+        for (i, s) in SKETCHES.iter().enumerate() {
+            assert!(
+                SKETCHES[i + 1..].iter().all(|t| t.name != s.name),
+                "duplicate sketch name {}",
+                s.name
+            );
+            assert_eq!(find(s.name).unwrap().name, s.name);
+        }
+        assert!(find("no_such_sketch").is_none());
+    }
+}
@@ -1,17 +1,40 @@
-use getopts::Options;
 use nannou::noise::NoiseFn;
 use nannou::prelude::*;
-use std::env;
 
-use sketches::{border, img_path};
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{border, cli, img_path};
 
 const WIDTH: u32 = 900;
 const HEIGHT: u32 = 600;
 
-fn main() {
+pub const SKETCH: Sketch = Sketch {
+    name: "paper",
+    title: "Paper",
+    description: "Vertical lines slowly distorted by noise like crumpled paper.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
     nannou::app(model).update(update).run();
 }
 
+// The distortion runs its course in 6000 frames.
+fn render(rec: &mut Recorder) {
+    rec.delay = 80;
+    let mut model = Model { distortion: 0.03 };
+    for frame in 0..6000 {
+        if frame % 100 == 0 {
+            rec.frame(&scene(&model));
+        }
+        step(&mut model);
+    }
+}
+
 struct Model {
     distortion: f64,
 }
@@ -25,26 +48,19 @@ fn model(app: &App) -> Model {
     Model { distortion: 0.03 }
 }
 
-fn update(_app: &App, model: &mut Model, _update: Update) {
+fn step(model: &mut Model) {
     if model.distortion >= -0.03 {
         model.distortion -= 0.00001;
     }
 }
 
-fn view(app: &App, model: &Model, frame: Frame) {
-    let args: Vec<String> = env::args().collect();
-    let mut opts = Options::new();
-    opts.optflag("p", "png", "save frames to file as png.");
-    let matches = match opts.parse(&args[1..]) {
-        Ok(m) => m,
-        Err(f) => panic!(f.to_string()),
-    };
-    let png = matches.opt_present("p");
+fn update(_app: &App, model: &mut Model, _update: Update) {
+    step(model);
+}
 
-    let draw = app.draw();
-    // if frame.nth() == 0 {
-    draw.background().color(BLACK);
-    // }
+fn scene(model: &Model) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH as f32, HEIGHT as f32);
+    canvas.background(BLACK);
     let y0 = -(HEIGHT as f32) / 2.0;
     let y1 = HEIGHT as f32 / 2.0;
     let mut ys = vec![];
@@ -64,10 +80,18 @@ fn view(app: &App, model: &Model, frame: Frame) {
             let delta = x * nn.get([k * x as f64, k * *y as f64]) as f32;
             ps.push(pt2(x + delta, *y))
         }
-        draw.polyline().weight(1.0).color(WHITE).points(ps);
+        canvas.polyline(ps).weight(1.0).color(WHITE);
     }
 
-    border(app, &draw, 50.0);
+    border(&mut canvas, 50.0);
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let png = cli::png();
+
+    let draw = app.draw();
+    scene(model).draw(&draw);
 
     if png && (model.distortion * 100.0) as i32 == 1 {
         let file_path = img_path(app);
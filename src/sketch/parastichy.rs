@@ -0,0 +1,77 @@
+use nannou::color::IntoLinSrgba;
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::phyllotaxis::Phyllotaxis;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, img_path};
+
+const WIDTH: f32 = 800.0;
+const HEIGHT: f32 = 800.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "parastichy",
+    title: "Parastichy",
+    description: "Vogel's sunflower with the two families of spiral arms through its florets drawn in.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(rgb8(20, 24, 33));
+    let head = Phyllotaxis::new(600, 15.0)
+        .size(|f| 6.0 + 12.0 * f.t)
+        .color(|f| {
+            let c: LinSrgba = Hsl::new(40.0 - 30.0 * f.t, 0.9, 0.45 + 0.2 * f.t).into_lin_srgba();
+            c
+        });
+    // The arms through nearest neighbours at the rim run nearly straight out,
+    // the Fibonacci numbers two below them curl further round.
+    let (a, b) = head.parastichies().expect("no spiral arms at the rim");
+    let (a, b) = if 2 * a > b {
+        (a - (b - a), b - a)
+    } else {
+        (a, b)
+    };
+    for (k, color) in [
+        (a, rgba(0.3, 0.6, 0.9, 0.7)),
+        (b, rgba(0.9, 0.35, 0.4, 0.7)),
+    ]
+    .iter()
+    {
+        for arm in head.arms(*k) {
+            canvas.polyline(arm).join_round().color(*color).weight(2.0);
+        }
+    }
+    for f in head.florets() {
+        canvas.ellipse(f.position, f.size, f.size).color(f.color);
+    }
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    scene().draw(&draw);
+
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
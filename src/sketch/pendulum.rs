@@ -0,0 +1,98 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+
+const SIZE: f32 = 600.0;
+
+fn clock(frame: u64) -> f32 {
+    let rate = 360;
+    (frame % rate) as f32 / rate as f32
+}
+
+pub const SKETCH: Sketch = Sketch {
+    name: "pendulum",
+    title: "Pendulum",
+    description: "A pendulum swinging in front of and behind a row of stripes.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run()
+}
+
+fn render(rec: &mut Recorder) {
+    let mut m = Model { x: 0., y: 0. };
+    for frame in (0..360).step_by(4) {
+        step(&mut m, frame);
+        rec.frame(&scene(&m, frame));
+    }
+}
+
+fn step(m: &mut Model, frame: u64) {
+    let t = TAU * clock(frame);
+    m.x = 340. * t.cos();
+    m.y = 375. / 2. * t.sin();
+}
+
+fn update(app: &App, m: &mut Model, _update: Update) {
+    step(m, app.elapsed_frames());
+}
+
+struct Model {
+    x: f32,
+    y: f32,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(SIZE as u32, SIZE as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    Model { x: 0., y: 0. }
+}
+
+fn scene(m: &Model, frame: u64) -> Canvas {
+    let mut canvas = Canvas::new(SIZE, SIZE);
+    canvas.background(BLACK);
+    let frames = frame % 360;
+    let size = 2.5 * (300. - m.y).sqrt();
+    if frames < 180 {
+        bob(&mut canvas, m.x, m.y, size)
+    };
+    stripe(&mut canvas, -290., 20., SIZE);
+    stripe(&mut canvas, 290., 20., SIZE);
+    stripe(&mut canvas, 100., 20., SIZE);
+    stripe(&mut canvas, -100., 20., SIZE);
+    if frames >= 180 {
+        bob(&mut canvas, m.x, m.y, size)
+    };
+    canvas
+        .ellipse(pt2(0., 300.), 50., 25.)
+        .color(DARKGOLDENROD);
+    canvas
+}
+
+fn view(app: &App, m: &Model, frame: Frame) {
+    let draw = app.draw();
+    scene(m, app.elapsed_frames()).draw(&draw);
+    draw.to_frame(app, &frame).unwrap();
+}
+
+fn stripe(canvas: &mut Canvas, x: f32, w: f32, h: f32) {
+    canvas.rect(pt2(x + 3., 0.), w, h).color(DIMGRAY);
+    canvas.rect(pt2(x, 0.), w, h).color(WHITE);
+}
+
+fn bob(canvas: &mut Canvas, x: f32, y: f32, size: f32) {
+    canvas
+        .line(pt2(0., 300.), pt2(x, y))
+        .color(DARKGOLDENROD)
+        .weight(3.);
+    canvas.ellipse(pt2(x, y), size, size).color(ORANGE);
+}
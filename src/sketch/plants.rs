@@ -0,0 +1,73 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::lsystem::{self, stochastic_plant};
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, img_path};
+
+const WIDTH: f32 = 1200.0;
+const HEIGHT: f32 = 800.0;
+const PLANTS: usize = 5;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "plants",
+    title: "Plants",
+    description: "A row of plants grown from a stochastic L-system, a different garden every run.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(rgb8(246, 241, 226));
+    let (system, turtle) = stochastic_plant();
+    let greens = [rgb8(52, 94, 58), rgb8(84, 122, 64), rgb8(38, 70, 60)];
+    let w = WIDTH / PLANTS as f32;
+    let ground = -HEIGHT / 2.0 + 60.0;
+    for i in 0..PLANTS {
+        let lines = turtle.interpret(&system.iterate(5));
+        let b = lsystem::bounds(&lines).unwrap();
+        // Stand each plant on the ground, as tall as will fit.
+        let h = HEIGHT - 120.0;
+        let scale = (0.9 * w / b.w()).min(h / b.h());
+        let x = -WIDTH / 2.0 + w * (i as f32 + 0.5);
+        let rect = Rect::from_x_y_w_h(
+            x,
+            ground + b.h() * scale / 2.0,
+            b.w() * scale,
+            b.h() * scale,
+        );
+        let lines = lsystem::fit(&lines, rect);
+        lsystem::draw(&mut canvas, &lines, greens[i % greens.len()]);
+    }
+    canvas
+        .rect(pt2(0.0, ground - 30.0), WIDTH, 60.0)
+        .color(rgb8(120, 96, 70));
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    scene().draw(&draw);
+
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
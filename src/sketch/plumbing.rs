@@ -0,0 +1,88 @@
+use nannou::geom::Rect;
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::hexgrid::{Layout, Orientation};
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::truchet::{hex_pipes, Grid, Truchet};
+use crate::wfc::{Rules, Wfc};
+use crate::{cli, img_path};
+
+const SIZE: f32 = 25.0;
+const WIDTH: f32 = 900.0;
+const HEIGHT: f32 = 900.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "plumbing",
+    title: "Plumbing",
+    description: "Hex pipe tiles placed by wave function collapse, with an empty border so every pipe closes into a loop.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+// Tiles in the order `hex_pipes` makes them: empty, 3 straights, 6 tight and
+// 6 wide bends.
+fn weights() -> Vec<f32> {
+    let mut weights = vec![0.4];
+    weights.extend(vec![1.0; 3]);
+    weights.extend(vec![0.6; 6]);
+    weights.extend(vec![1.2; 6]);
+    weights
+}
+
+fn scene() -> Canvas {
+    let layout = Layout::new(Orientation::Flat, SIZE, pt2(0.0, 0.0));
+    let grid = Grid::hex(layout, Rect::from_w_h(WIDTH - 80.0, HEIGHT - 80.0));
+    let tiles = hex_pipes();
+    let rules = Rules::from_tile_set(&tiles).weights(weights());
+    let mut wfc = Wfc::new(&grid, rules);
+    for (i, ns) in grid.neighbors().iter().enumerate() {
+        if ns.contains(&None) {
+            wfc.fix(i, 0);
+        }
+    }
+    let placed = wfc.solve().expect("no tiling found");
+    let truchet = Truchet {
+        grid,
+        tiles,
+        placed,
+    };
+
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
+    for line in truchet.polylines() {
+        canvas
+            .polyline(line)
+            .join_round()
+            .caps_round()
+            .color(GRAY)
+            .weight(6.0);
+    }
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    scene().draw(&draw);
+
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
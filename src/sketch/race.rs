@@ -1,12 +1,15 @@
-use colorous;
-use getopts::Options;
 use nannou::app::LoopMode;
 use nannou::noise::NoiseFn;
 use nannou::prelude::*;
 use rand_distr::{Distribution, Geometric};
-use std::env;
 
-use sketches::{img_path, Grid};
+use crate::canvas::Canvas;
+use crate::hatch::{Hatch, Pattern};
+use crate::raster::Recorder;
+use crate::rng::{random_range, with_rng};
+use crate::sketch::{Kind, Sketch};
+use crate::vector::save_svg;
+use crate::{cli, img_path, output_path, Grid};
 
 const WIDTH: f32 = 1200.0;
 const HEIGHT: f32 = 900.0;
@@ -16,27 +19,31 @@ const K: f64 = 0.025;
 const LINES: usize = 700;
 const STEP: f32 = 8.0;
 
-fn main() {
+pub const SKETCH: Sketch = Sketch {
+    name: "race",
+    title: "Race",
+    description: "Race lines through a noise field colored from a red palette.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
     nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
 }
 
-fn view(app: &App, frame: Frame) {
-    let geo = Geometric::new(0.5).unwrap();
-    let args: Vec<String> = env::args().collect();
-    let mut opts = Options::new();
-    opts.optflag("p", "png", "save frames to file as png.");
-    let matches = match opts.parse(&args[1..]) {
-        Ok(m) => m,
-        Err(f) => panic!(f.to_string()),
-    };
-    let png = matches.opt_present("p");
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
 
-    app.set_loop_mode(LoopMode::loop_once());
-    let draw = app.draw();
+fn scene() -> Canvas {
+    let geo = Geometric::new(0.5).unwrap();
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
     let colors = colorous::REDS;
     let c = colors.eval_rational(2, 10);
     let kolor = srgb8(c.r, c.g, c.b);
-    draw.background().color(kolor);
+    canvas.background(kolor);
 
     let nn = nannou::noise::BasicMulti::new();
 
@@ -54,15 +61,40 @@ fn view(app: &App, frame: Frame) {
             loc.x += STEP * angle.cos();
             loc.y += STEP * angle.sin();
         }
-        let w = geo.sample(&mut rand::thread_rng()) as f32;
+        let w = with_rng(|rng| geo.sample(rng)) as f32;
         let c = colors.eval_rational(l % 100, 100);
         let kolor = srgb8(c.r, c.g, c.b);
-        draw.polygon().stroke_weight(w).color(kolor).points(points);
+        canvas.polygon(points).color(kolor).stroke(BLACK).weight(w);
     }
+    canvas
+}
+
+// Wavy hatching in each line's own red, with `-p`.
+fn plot(canvas: &Canvas) -> Canvas {
+    Hatch::new(Pattern::Noise {
+        amplitude: 2.0,
+        wavelength: 40.0,
+    })
+    .spacing(3.0)
+    .angle(0.0)
+    .canvas(canvas)
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    let canvas = scene();
+    canvas.draw(&draw);
 
     if png {
         let file_path = img_path(app);
         app.main_window().capture_frame(file_path);
+        save_svg(
+            &plot(&canvas),
+            output_path(&format!("{}-hatched", SKETCH.name), "svg"),
+        );
     }
 
     draw.to_frame(app, &frame).unwrap();
@@ -0,0 +1,81 @@
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+use rand_distr::{Distribution, Geometric};
+
+use crate::canvas::Canvas;
+use crate::polyline::rdp;
+use crate::raster::Recorder;
+use crate::rng::{random_range, with_rng};
+use crate::sketch::{Kind, Sketch};
+use crate::stroke::Brush;
+use crate::{hires, Grid};
+
+const WIDTH: f32 = 7_200.0;
+const HEIGHT: f32 = 5_400.0;
+const GRID_SPACING: f32 = 6.0;
+const LENGTH: usize = 1000;
+const K: f64 = 0.025;
+const LINES: usize = 700;
+const STEP: f32 = 100.0;
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    let geo = Geometric::new(0.5).unwrap();
+    let colors = colorous::REDS;
+    let c = colors.eval_rational(2, 10);
+    let kolor = srgb8(c.r, c.g, c.b);
+    canvas.background(kolor);
+
+    let nn = nannou::noise::BasicMulti::new();
+
+    let grid = Grid::new(1.1 * WIDTH, 1.1 * HEIGHT, GRID_SPACING, |x, y| {
+        TAU * nn.get([K * x as f64, K * y as f64]) as f32
+    });
+
+    for l in 0..LINES {
+        let mut loc = pt2(-WIDTH / 2.0, random_range(-HEIGHT / 2.0, HEIGHT / 2.0));
+        let mut points = vec![];
+        for _i in 0..LENGTH {
+            points.push(loc);
+            let angle = &grid.get(loc.x, loc.y);
+            let angle = map_range(*angle, 0.0, TAU, 0.0, PI);
+            loc.x += STEP * angle.cos();
+            loc.y += STEP * angle.sin();
+            if loc.x > WIDTH {
+                break;
+            };
+        }
+        let mut w = 6.0 * with_rng(|rng| geo.sample(rng)) as f32;
+        if w > 96.0 {
+            w = 96.0
+        }
+        let c = colors.eval_rational(l % 100, 100);
+        let kolor = srgb8(c.r, c.g, c.b);
+        // Drop points that bend the line by less than two pixels.
+        let mut points = rdp(&points, 2.0);
+        canvas.polygon(points.clone()).color(kolor);
+        // A brush line round the edge, swelling and thinning along it.
+        points.push(points[0]);
+        let brush = Brush::new(w).noise(0.6, 600.0, l as u32);
+        canvas.add(brush.shape(&points)).color(BLACK);
+    }
+    canvas
+}
+
+pub const SKETCH: Sketch = Sketch {
+    name: "race_lg",
+    title: "Race (high resolution)",
+    description: "High resolution version of race rendered to a texture, outlined with brush strokes that swell and thin.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    hires::run(scene);
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
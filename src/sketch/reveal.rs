@@ -0,0 +1,127 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::geometry::Polygon;
+use crate::packing::Packing;
+use crate::particles::Bounds;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::with_opacity;
+
+const SIZE: f32 = 800.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "reveal",
+    title: "Reveal",
+    description: "A white sheet with packed square holes fading to reveal a color grid.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run();
+}
+
+// The sheet has faded away after 360 frames.
+fn render(rec: &mut Recorder) {
+    let mut m = new_model();
+    for frame in 0..360 {
+        step(&mut m);
+        if frame % 4 == 0 {
+            rec.frame(&scene(&m));
+        }
+    }
+}
+
+struct Model {
+    rects: Vec<Rect>,
+    shown: usize,
+    // What is left of the sheet once the shown holes are cut out.
+    sheet: Polygon,
+    alpha: f32,
+}
+
+// Square holes that don't overlap, opened one a frame.
+fn new_model() -> Model {
+    let square = vec![pt2(-1., -1.), pt2(1., -1.), pt2(1., 1.), pt2(-1., 1.)];
+    let rects = Packing::new(Bounds::Rect(Rect::from_w_h(SIZE, SIZE)))
+        .shape(square)
+        .rotate(false)
+        .radius(10., 50.)
+        .padding(4.)
+        .count(360)
+        .growth()
+        .iter()
+        .map(|p| Rect::from_xy_wh(p.center, vec2(p.radius, p.radius) * 2.))
+        .collect();
+    Model {
+        rects,
+        shown: 0,
+        sheet: Polygon::rect(Rect::from_w_h(SIZE, SIZE)),
+        alpha: 1.0,
+    }
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(SIZE as u32, SIZE as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    new_model()
+}
+
+fn step(m: &mut Model) {
+    if m.shown < m.rects.len() {
+        // The holes are packed apart inside the sheet, so each can be cut
+        // as a ring of its own.
+        let hole = Polygon::rect(m.rects[m.shown]).rings.remove(0);
+        m.sheet = std::mem::take(&mut m.sheet).hole(hole);
+        m.shown += 1;
+    }
+    m.alpha -= 1. / 360.;
+    m.alpha = if m.alpha <= 0.0 { 0.0 } else { m.alpha };
+}
+
+fn update(_app: &App, m: &mut Model, _update: Update) {
+    step(m);
+}
+
+fn scene(model: &Model) -> Canvas {
+    let mut canvas = Canvas::new(SIZE, SIZE);
+    let rect = canvas.bounds();
+    let step_x = 5.;
+    let step_y = 5.;
+
+    let size = vec2(step_x, step_y);
+    let r = nannou::geom::Rect::from_wh(size)
+        .align_left_of(rect)
+        .align_top_of(rect);
+    let mut grid_y = 0.0;
+    while grid_y < rect.h() {
+        let mut grid_x = 0.0;
+        while grid_x < rect.w() {
+            let r = r.shift_x(grid_x).shift_y(-grid_y);
+            let hue = grid_x / rect.w();
+            let saturation = 1.0 - (grid_y / rect.h());
+            canvas
+                .rect(r.xy(), r.w(), r.h())
+                .color(hsl(hue, saturation, 0.5));
+            grid_x += step_x;
+        }
+        grid_y += step_y;
+    }
+
+    canvas
+        .path(model.sheet.segments())
+        .color(with_opacity(WHITE, model.alpha));
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    scene(model).draw(&draw);
+    draw.to_frame(app, &frame).unwrap();
+}
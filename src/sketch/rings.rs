@@ -0,0 +1,64 @@
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::with_opacity;
+
+const WIDTH: f32 = 1024.0;
+const HEIGHT: f32 = 768.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "rings",
+    title: "Rings",
+    description: "Rings deformed by an OpenSimplex noise loop.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
+}
+
+// The noise loop takes 360 frames.
+fn render(rec: &mut Recorder) {
+    for frame in (0..360).step_by(4) {
+        rec.frame(&scene(frame));
+    }
+}
+
+fn scene(frame: u64) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    let nn = nannou::noise::OpenSimplex::new();
+    canvas.background(BLACK);
+    let rings = 50;
+    let r = WIDTH * 0.35;
+    for j in 0..rings {
+        let mut ps: Vec<Point2> = vec![];
+        let rad = (r / rings as f32) * (rings - j) as f32;
+        for i in 0..150 {
+            let mut x = (i as f32 / 100. * TAU).cos();
+            let mut y = (i as f32 / 100. * TAU).sin();
+            let a = frame % 360;
+            let a = (a as f64) * PI_F64 / 180.;
+            let u = 0.3 * a.cos();
+            let v = 0.3 * a.sin();
+            let offset = nn.get([x as f64, y as f64 + j as f64 * 0.03, u, v]) as f32;
+            x *= rad + 2. * offset * rad;
+            y *= rad + 2. * offset * rad;
+            ps.push(pt2(x, y));
+        }
+        let alpha = j as f32 / rings as f32;
+        canvas.polyline(ps).color(with_opacity(ORANGE, alpha));
+    }
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    let draw = app.draw();
+    scene(app.elapsed_frames()).draw(&draw);
+    draw.to_frame(app, &frame).unwrap();
+}
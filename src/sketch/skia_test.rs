@@ -0,0 +1,62 @@
+use nannou::prelude::*;
+
+use crate::canvas::{Canvas, FillRule, Segment};
+use crate::raster::{self, Recorder};
+use crate::sketch::{Kind, Sketch};
+
+const SIZE: f32 = 1000.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "skia_test",
+    title: "Skia Test",
+    description: "Two overlapping filled curves rendered with tiny-skia.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    let now = std::time::Instant::now();
+    raster::save_png(&scene(), "image.png", 1.0);
+    println!("Rendered in {:.2}ms", now.elapsed().as_micros() as f64 / 1000.0);
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+// The curves are laid out with the origin at the top left and y pointing down.
+fn pt(x: f32, y: f32) -> Point2 {
+    pt2(x - SIZE / 2.0, SIZE / 2.0 - y)
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(SIZE, SIZE);
+
+    let path1 = vec![
+        Segment::MoveTo(pt(60.0, 60.0)),
+        Segment::LineTo(pt(160.0, 940.0)),
+        Segment::CubicTo(pt(380.0, 840.0), pt(660.0, 800.0), pt(940.0, 800.0)),
+        Segment::CubicTo(pt(740.0, 460.0), pt(440.0, 160.0), pt(60.0, 60.0)),
+        Segment::Close,
+    ];
+
+    let path2 = vec![
+        Segment::MoveTo(pt(940.0, 60.0)),
+        Segment::LineTo(pt(840.0, 940.0)),
+        Segment::CubicTo(pt(620.0, 840.0), pt(340.0, 800.0), pt(60.0, 800.0)),
+        Segment::CubicTo(pt(260.0, 460.0), pt(560.0, 160.0), pt(940.0, 60.0)),
+        Segment::Close,
+    ];
+
+    canvas
+        .path(path1)
+        .rule(FillRule::NonZero)
+        .color(srgba8(50, 127, 150, 200));
+    canvas
+        .path(path2)
+        .rule(FillRule::NonZero)
+        .color(srgba8(220, 140, 75, 180));
+    canvas
+}
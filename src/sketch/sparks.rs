@@ -0,0 +1,110 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::particles::{Bounds, Drag, Emitter, Gravity, Policy, System};
+use crate::raster::Recorder;
+use crate::rng::random_range;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, gif_path};
+
+const WIDTH: f32 = 800.0;
+const HEIGHT: f32 = 800.0;
+const FRAMES: u64 = 360;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "sparks",
+    title: "Sparks",
+    description: "Two fountains of sparks falling under gravity, fading as they age.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run();
+}
+
+// Let the fountains fill up before capturing.
+fn render(rec: &mut Recorder) {
+    let mut model = new_model();
+    for frame in 0..FRAMES {
+        step(&mut model);
+        if frame >= 120 && frame % 4 == 0 {
+            rec.frame(&scene(&model));
+        }
+    }
+}
+
+struct Model {
+    sparks: System<f32>,
+}
+
+// Each spark carries its hue.
+fn fountain(x: f32, angle: f32, hue: f32) -> Emitter<f32> {
+    Emitter::new(pt2(x, -HEIGHT / 2.0 + 60.0), 3.0, move || {
+        hue + random_range(-0.05, 0.05)
+    })
+    .speed(8.0, 13.0)
+    .direction(angle, 0.35)
+    .lifespan(150)
+}
+
+fn new_model() -> Model {
+    let sparks = System::new()
+        .force(Gravity(vec2(0.0, -0.15)))
+        .force(Drag(0.01))
+        .bounds(Bounds::Rect(Rect::from_w_h(WIDTH, HEIGHT)), Policy::Kill)
+        .emitter(fountain(-150.0, PI / 2.0 - 0.25, 0.08))
+        .emitter(fountain(150.0, PI / 2.0 + 0.25, 0.55))
+        .trail(12);
+    Model { sparks }
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(WIDTH as u32, HEIGHT as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    new_model()
+}
+
+fn step(model: &mut Model) {
+    model.sparks.step();
+}
+
+fn update(_app: &App, model: &mut Model, _update: Update) {
+    step(model);
+}
+
+fn scene(model: &Model) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
+    for p in &model.sparks.particles {
+        let life = p.life();
+        canvas
+            .polyline(p.trail.iter().cloned())
+            .stroke(hsla(p.data, 0.9, 0.6, 0.5 * life))
+            .weight(1.5);
+        canvas
+            .ellipse(p.position, 3.0, 3.0)
+            .color(hsla(p.data, 0.9, 0.5 + 0.4 * life, life));
+    }
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let png = cli::png();
+
+    let draw = app.draw();
+    scene(model).draw(&draw);
+
+    if png {
+        let file_path = gif_path(app, &frame);
+        app.main_window().capture_frame(file_path);
+        app.set_loop_mode(LoopMode::loop_ntimes(FRAMES as usize));
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -1,34 +1,50 @@
 // Inspired by Bees and Bombs:
 // https://beesandbombs.tumblr.com/post/178493871934/squares-turning#notes
 
-use nannou::ease::cubic::ease_in_out;
 use nannou::prelude::*;
-use sketches::img_path;
 
-fn main() {
+use crate::canvas::{Affine, Canvas};
+use crate::clock;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+
+pub const SKETCH: Sketch = Sketch {
+    name: "squares",
+    title: "Squares",
+    description: "Grid of squares turning and swapping colors with the background.",
+    credits: Some("Inspired by Bees and Bombs: https://beesandbombs.tumblr.com/post/178493871934/squares-turning"),
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
     nannou::app(model).update(update).run()
 }
 
+// The colors swap twice in a 360 frame loop.
+fn render(rec: &mut Recorder) {
+    let mut m = new_model();
+    for frame in 0..360 {
+        step(&mut m, frame);
+        if frame % 3 == 0 {
+            rec.frame(&scene(&m));
+        }
+    }
+}
+
 const SZ: u32 = 520;
 
 #[derive(Debug)]
 struct Model {
     position: [[Point2; 11]; 11],
     rotation: [[f32; 11]; 11],
-    direction: [[Point2; 11]; 11],
     sq_color: Rgb<u8>,
     bg_color: Rgb<u8>,
 }
 
-fn clock(frame: u64) -> f32 {
-    let t = (frame % 180) as f32 / 180.;
-    ease_in_out(t, 0., 1., 1.)
-}
-
-fn model(app: &App) -> Model {
-    app.new_window().size(SZ, SZ).view(view).build().unwrap();
+fn new_model() -> Model {
     let mut position = [[pt2(0., 0.); 11]; 11];
-    let mut direction = [[pt2(0., 0.); 11]; 11];
     let mut rotation = [[0.; 11]; 11];
     let xs = -5..=5;
     let xs = xs.map(|x| x as f32 * 56.57);
@@ -37,11 +53,6 @@ fn model(app: &App) -> Model {
         for (j, y) in ys.clone().enumerate() {
             position[i][j] = pt2(x, y);
             rotation[i][j] = 0.;
-            direction[i][j] = if i % 2 == 0 {
-                pt2(0., 1.)
-            } else {
-                pt2(0., -1.)
-            }
         }
     }
     let bg_color = BLACK;
@@ -49,16 +60,20 @@ fn model(app: &App) -> Model {
     Model {
         position,
         rotation,
-        direction,
         sq_color,
         bg_color,
     }
 }
 
-fn update(app: &App, m: &mut Model, _update: Update) {
-    let mut position = m.position.clone();
-    let mut rotation = m.rotation.clone();
-    let t = app.elapsed_frames() % 180;
+fn model(app: &App) -> Model {
+    app.new_window().size(SZ, SZ).view(view).build().unwrap();
+    new_model()
+}
+
+fn step(m: &mut Model, frame: u64) {
+    let mut position = m.position;
+    let mut rotation = m.rotation;
+    let t = frame % 180;
     if t == 90 {
         for (i, row) in m.position.iter().enumerate() {
             for (j, p) in row.iter().enumerate() {
@@ -76,7 +91,7 @@ fn update(app: &App, m: &mut Model, _update: Update) {
     }
     for (i, col) in m.position.iter().enumerate() {
         for (j, _p) in col.iter().enumerate() {
-            let t = clock(app.elapsed_frames());
+            let t = clock(frame);
             rotation[i][j] = PI / 2. * t;
         }
     }
@@ -84,25 +99,30 @@ fn update(app: &App, m: &mut Model, _update: Update) {
     m.rotation = rotation;
 }
 
-fn view(app: &App, m: &Model, frame: Frame) {
-    let draw = app.draw();
-    draw.background().color(m.bg_color);
+fn update(app: &App, m: &mut Model, _update: Update) {
+    step(m, app.elapsed_frames());
+}
+
+fn scene(m: &Model) -> Canvas {
+    let mut canvas = Canvas::new(SZ as f32, SZ as f32);
+    canvas.background(m.bg_color);
     for (i, row) in m.position.iter().enumerate() {
         for (j, p) in row.iter().enumerate() {
-            square(&draw, *p, m.rotation[i][j], m.sq_color);
+            square(&mut canvas, *p, m.rotation[i][j], m.sq_color);
         }
     }
+    canvas
+}
+
+fn view(app: &App, m: &Model, frame: Frame) {
+    let draw = app.draw();
+    scene(m).draw(&draw);
     draw.to_frame(app, &frame).unwrap();
-    // if app.elapsed_frames() < 360 {
-    //     let file_path = captured_frame_path(app, &frame);
-    //     app.main_window().capture_frame(file_path);
-    // }
 }
 
-fn square(draw: &Draw, position: Point2, rot: f32, col: Rgb<u8>) {
-    draw.rect()
-        .w_h(40., 40.)
-        .xy(position)
-        .rotate(rot)
-        .color(col);
+fn square(canvas: &mut Canvas, position: Point2, rot: f32, col: Rgb<u8>) {
+    let t = Affine::rotate(rot).then(&Affine::translate(position.x, position.y));
+    canvas.with_transform(t, |c| {
+        c.rect(pt2(0., 0.), 40., 40.).color(col);
+    });
 }
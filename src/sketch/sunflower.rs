@@ -0,0 +1,103 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::phyllotaxis::Phyllotaxis;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, img_path};
+
+const WIDTH: u32 = 700;
+const HEIGHT: u32 = 700;
+const POINTS: u32 = 2000;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "sunflower",
+    title: "Sunflower",
+    description: "Vogel's model for the floret pattern of a sunflower head.",
+    credits: Some("Vogel, H (1979). \"A better way to construct the sunflower head\". Mathematical Biosciences 44 (44): 179-189. doi:10.1016/0025-5564(79)90080-4."),
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run();
+}
+
+// One floret is added a frame, capture every 25th.
+fn render(rec: &mut Recorder) {
+    let mut model = Model { loc: 1 };
+    let mut canvas = Canvas::new(WIDTH as f32, HEIGHT as f32);
+    canvas.background(BLACK);
+    rec.draw(&canvas);
+    while model.loc < POINTS {
+        rec.draw(&scene(&model));
+        if model.loc.is_multiple_of(25) {
+            rec.capture();
+        }
+        model.loc += 1;
+    }
+    rec.frame(&scene(&model));
+}
+
+struct Model {
+    loc: u32,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(WIDTH, HEIGHT)
+        .view(view)
+        .build()
+        .unwrap();
+    Model { loc: 1 }
+}
+
+fn update(_app: &App, model: &mut Model, _update: Update) {
+    if model.loc < POINTS {
+        model.loc += 1;
+    }
+}
+
+// Only the newest floret, earlier ones stay on screen.
+fn scene(model: &Model) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH as f32, HEIGHT as f32);
+
+    // The divergence is rounded from the golden angle.
+    let head = Phyllotaxis::new(POINTS as usize, 6.0).divergence(2.4);
+    let p = head.position(model.loc as usize);
+    canvas.ellipse(p, 9.0, 9.0).color(INDIGO);
+    let r = p.magnitude();
+    canvas
+        .ellipse(p * (r - 2.0) / r, 5.0, 5.0)
+        .color(WHITE)
+        .stroke(GRAY)
+        .weight(2.0);
+
+    let (w, h) = (canvas.width, canvas.height);
+    canvas
+        .rect(pt2(0.0, 0.0), w, h)
+        .color(srgba(0.0, 0.0, 0.0, 0.001));
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let png = cli::png();
+
+    let draw = app.draw();
+    if frame.nth() == 0 {
+        draw.background().color(BLACK);
+    }
+
+    scene(model).draw(&draw);
+
+    if png && model.loc == POINTS {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+    if model.loc >= POINTS {
+        app.set_loop_mode(LoopMode::loop_once());
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -1,16 +1,39 @@
-use getopts::Options;
 use nannou::prelude::*;
-use std::env;
 
-use sketches::{gen_points, gif_path};
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, gen_points, gif_path};
 
 const WIDTH: f32 = 1000.0;
 const HEIGHT: f32 = 1000.0;
 
-fn main() {
+pub const SKETCH: Sketch = Sketch {
+    name: "trig",
+    title: "Trig",
+    description: "Lines joining a square to itself shifted around its perimeter.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
     nannou::app(model).update(update).run();
 }
 
+// The shift goes once around the square in 720 frames.
+fn render(rec: &mut Recorder) {
+    let mut model = Model {
+        start_pts: vec![],
+        end_pts: vec![],
+    };
+    for frame in (0..720).step_by(8) {
+        step(&mut model, frame);
+        rec.frame(&scene(&model));
+    }
+}
+
 struct Model {
     start_pts: Vec<Point2>,
     end_pts: Vec<Point2>,
@@ -42,8 +65,7 @@ fn sq(x: f32) -> Point2 {
     }
 }
 
-fn update(app: &App, model: &mut Model, _update: Update) {
-    let n = app.elapsed_frames();
+fn step(model: &mut Model, n: u64) {
     let k = 800.0;
     let w = k / 2.0;
     let f = |p| k * sq(p).x - w;
@@ -57,23 +79,25 @@ fn update(app: &App, model: &mut Model, _update: Update) {
     model.end_pts = end_points;
 }
 
-fn view(app: &App, model: &Model, frame: Frame) {
-    let args: Vec<String> = env::args().collect();
-    let mut opts = Options::new();
-    opts.optflag("p", "png", "save frames to file as png.");
-    let matches = match opts.parse(&args[1..]) {
-        Ok(m) => m,
-        Err(f) => panic!(f.to_string()),
-    };
-    let png = matches.opt_present("p");
+fn update(app: &App, model: &mut Model, _update: Update) {
+    step(model, app.elapsed_frames());
+}
 
-    let draw = app.draw();
-    draw.background().color(BLACK);
+fn scene(model: &Model) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
 
-    for (i, s) in model.start_pts.iter().enumerate() {
-        let e = model.end_pts[i];
-        draw.line().color(WHITE).weight(0.5).points(*s, e);
+    for (s, e) in model.start_pts.iter().zip(&model.end_pts) {
+        canvas.line(*s, *e).color(WHITE).weight(0.5);
     }
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let png = cli::png();
+
+    let draw = app.draw();
+    scene(model).draw(&draw);
 
     if png {
         let file_path = gif_path(app, &frame);
@@ -0,0 +1,65 @@
+use nannou::geom::Rect;
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::truchet::{weave, Grid, Truchet};
+use crate::vector::save_svg;
+use crate::{cli, output_path};
+
+const WIDTH: f32 = 900.0;
+const HEIGHT: f32 = 600.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "truchet",
+    title: "Truchet",
+    description: "Smith's quarter circles and crossings picked by a noise field, as one stroke per path for the plotter.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+// Strokes only, so the svg can go straight to a pen plotter. Low noise picks
+// one orientation of the arcs, high noise the other and the crossings.
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(WHITE);
+    let nn = nannou::noise::Perlin::new();
+    let grid = Grid::square(Rect::from_w_h(WIDTH - 60.0, HEIGHT - 60.0), 30.0);
+    let truchet = Truchet::noise(grid, weave(), &nn, 0.012);
+    for line in truchet.polylines() {
+        canvas
+            .polyline(line)
+            .color(BLACK)
+            .caps_round()
+            .join_round()
+            .weight(3.0);
+    }
+    canvas
+}
+
+// `-p` saves the drawing to `img/truchet.svg`.
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    let canvas = scene();
+    canvas.draw(&draw);
+
+    if png {
+        save_svg(&canvas, output_path(SKETCH.name, "svg"));
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,80 @@
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+
+use crate::canvas::{Affine, Canvas};
+use crate::img_path;
+use crate::raster::Recorder;
+use crate::rng::random;
+use crate::sketch::{Kind, Sketch};
+
+const WIDTH: f32 = 1024.0;
+const HEIGHT: f32 = 768.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "twisted",
+    title: "Twisted",
+    description: "A grid of lines each rotated by noise.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).run();
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+struct Model {
+    canvas: Canvas,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(WIDTH as u32, HEIGHT as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    Model { canvas: scene() }
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    let rect = canvas.bounds();
+    let nn = nannou::noise::OpenSimplex::new();
+    canvas.background(BLACK);
+    let x0 = rect.left();
+    let x1 = rect.right();
+    let delta = (x1 - x0) / 500.0;
+    let y0 = rect.bottom();
+    let y1 = rect.top();
+    let mut x = x0;
+    let mut y = y0;
+    let z = random();
+    while x < x1 {
+        let angle = nn.get([0.01 * x as f64, z]) as f32;
+        canvas.with_transform(Affine::rotate(angle), |c| {
+            c.line(pt2(x, y0), pt2(x, y1)).weight(1.0).color(WHITE);
+            c.line(pt2(x0, y), pt2(x1, y)).weight(1.0).color(WHITE);
+        });
+        x += delta;
+        y += delta;
+    }
+    canvas
+        .rect(pt2(0.0, 0.0), WIDTH, HEIGHT)
+        .color(srgba(0.0, 0.0, 0.0, 0.75));
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    if frame.nth() == 0 {
+        model.canvas.draw(&draw);
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+    draw.to_frame(app, &frame).unwrap();
+}
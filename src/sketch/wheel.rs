@@ -0,0 +1,116 @@
+use nannou::prelude::*;
+use nannou::{app::LoopMode, color::IntoLinSrgba};
+
+use crate::canvas::Canvas;
+use crate::packing::Packing;
+use crate::particles::Bounds;
+use crate::raster::Recorder;
+use crate::rng::random_range;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, img_path, set_opacity, with_opacity};
+
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 900;
+const WHEELS: usize = 30;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "wheel",
+    title: "Wheel",
+    description: "Packed wheels with spokes and hubs.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH, HEIGHT).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH as f32, HEIGHT as f32);
+    canvas.background(WHITE);
+
+    let cs = ["3e1618", "ddefb3", "3e3731", "a4b3c5", "ab616e"];
+
+    let mut rgbs = vec![];
+    for s in &cs {
+        let q = hex::decode(s).unwrap();
+        rgbs.push(rgb8(q[0], q[1], q[2]));
+    }
+
+    // Draw the 10 wide rims inside the packed circles.
+    let bounds = Bounds::Rect(Rect::from_w_h(WIDTH as f32, HEIGHT as f32));
+    let wheels = Packing::new(bounds)
+        .radius(30.0, 200.0)
+        .padding(15.0)
+        .count(WHEELS)
+        .growth();
+    for (i, w) in wheels.iter().enumerate() {
+        let h = random_range(0.20, 0.4);
+        let s = random_range(4.0, 27.0);
+        let c = rgbs[i % rgbs.len()];
+        wheel(
+            &mut canvas,
+            w.radius - 5.0,
+            h,
+            s,
+            w.center,
+            c.into_lin_srgba(),
+        );
+    }
+    canvas
+}
+
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    scene().draw(&draw);
+
+    if png {
+        let file_path = img_path(app);
+        app.main_window().capture_frame(file_path);
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
+
+fn wheel(canvas: &mut Canvas, radius: f32, hub: f32, spokes: f32, pos: Point2, c: LinSrgba) {
+    let a = set_opacity(c, 0.4);
+    let diameter = 2.0 * radius;
+    let d = hub * radius;
+    let mut angle = 0.0;
+    let sw = random_range(1.0, 4.0);
+    while angle < TAU {
+        canvas
+            .line(
+                pt2(
+                    pos.x + hub * radius / 2.0 * angle.cos(),
+                    pos.y + hub * radius / 2.0 * angle.sin(),
+                ),
+                pt2(pos.x + radius * angle.cos(), pos.y + radius * angle.sin()),
+            )
+            .color(c)
+            .weight(sw);
+        angle += TAU / spokes;
+    }
+    canvas
+        .ellipse(pos, diameter, diameter)
+        .no_fill()
+        .stroke(c)
+        .weight(10.0);
+    canvas
+        .ellipse(pos, diameter - 12.0, diameter - 12.0)
+        .no_fill()
+        .stroke(GRAY)
+        .weight(4.0);
+    canvas.ellipse(pos, d, d).color(a);
+    let c = with_opacity(BLACK, 0.75);
+    canvas.ellipse(pos, 10.0, 10.0).color(c);
+}
@@ -0,0 +1,103 @@
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+use primes::is_prime;
+
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::with_opacity;
+
+const H: f32 = 900.0;
+const W: f32 = 1200.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "wicker",
+    title: "Wicker",
+    description: "Noisy lines sweeping down the screen, woven into wicker.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run();
+}
+
+// One line is drawn a frame, capture every 16th.
+fn render(rec: &mut Recorder) {
+    let mut m = new_model();
+    for frame in 0..1440 {
+        step(&mut m, frame);
+        rec.draw(&scene(&m, frame));
+        if frame % 16 == 0 {
+            rec.capture();
+        }
+    }
+}
+
+struct Model {
+    slope: f32,
+    b: f32,
+    thickness: f32,
+}
+
+fn new_model() -> Model {
+    Model {
+        slope: 0.0,
+        b: -H / 2.0,
+        thickness: 10.0,
+    }
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(W as u32, H as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    new_model()
+}
+
+fn step(m: &mut Model, frame: u64) {
+    let nn = nannou::noise::Perlin::new();
+    let offset = nn.get([frame as f64 / 70., 0.0]) as f32;
+    m.b += 10.0;
+    if m.b > H / 2.0 {
+        m.b = -H / 2.0;
+    }
+    m.slope = offset;
+    m.thickness = 5.0 + (nn.get([frame as f64 / 70., 0.137]) as f32 + 1.0) * 7.0;
+}
+
+fn update(app: &App, m: &mut Model, _update: Update) {
+    step(m, app.elapsed_frames());
+}
+
+// Only the newest line, earlier ones stay on screen.
+fn scene(model: &Model, frame: u64) -> Canvas {
+    let mut canvas = Canvas::new(W, H);
+    let x0 = -W / 2.0;
+    let x1 = W / 2.0;
+    let y0 = model.b + model.slope * x0;
+    let y1 = model.b + model.slope * x1;
+    if frame == 0 {
+        canvas.background(CORNSILK);
+    }
+    let c = if is_prime(frame) {
+        with_opacity(GOLDENROD, 0.075)
+    } else {
+        with_opacity(BLACK, 0.1)
+    };
+    canvas
+        .line(pt2(x0, y0), pt2(x1, y1))
+        .weight(model.thickness)
+        .color(c);
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    scene(model, frame.nth()).draw(&draw);
+    draw.to_frame(app, &frame).unwrap();
+}
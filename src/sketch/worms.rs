@@ -0,0 +1,122 @@
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::particles::{Bounds, Force, Particle, Policy, System};
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+
+const SIZE: f32 = 800.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "worms",
+    title: "Worms",
+    description: "Particles crawling through a noise field leaving trails.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run()
+}
+
+// The trails build up over 720 frames, capture every 8th.
+fn render(rec: &mut Recorder) {
+    let mut m = new_model();
+    let mut canvas = Canvas::new(SIZE, SIZE);
+    canvas.background(WHITE);
+    rec.draw(&canvas);
+    for frame in 0..720 {
+        step(&mut m);
+        rec.draw(&scene(&m));
+        if frame % 8 == 0 {
+            rec.capture();
+        }
+    }
+}
+
+fn noise(p: Point2, scale: f64) -> f32 {
+    let qx = p.x as f64 / scale;
+    let qy = p.y as f64 / scale;
+    let nn = nannou::noise::HybridMulti::new();
+    let r = nn.get([qx, qy]);
+    ((r + 1.) / 2. * scale * TAU as f64) as f32
+}
+
+// Turns particles to crawl at `speed` in the direction of the noise.
+fn crawl(speed: f32) -> impl Force {
+    move |p: Point2, v: Vector2, _m: f32| {
+        let angle = noise(p, 100.);
+        vec2(angle.cos(), angle.sin()) * speed - v
+    }
+}
+
+struct Model {
+    a: System,
+    b: System,
+    c: System,
+    n: usize,
+}
+
+fn new_model() -> Model {
+    let win = Rect::from_w_h(SIZE, SIZE);
+    let bounds = Bounds::Rect(win);
+    let speed = 2.;
+    let n = 200;
+    let worms = || {
+        System::new()
+            .force(crawl(speed))
+            .bounds(bounds, Policy::Respawn)
+    };
+    let (mut a, mut b, mut c) = (worms(), worms(), worms());
+    for _ in 0..n {
+        a.add(Particle::new(bounds.random_point()));
+        b.add(Particle::new(bounds.random_point()));
+        c.add(Particle::new(bounds.random_point()));
+    }
+    Model { a, b, c, n }
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(SIZE as u32, SIZE as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    new_model()
+}
+
+fn step(m: &mut Model) {
+    m.a.step();
+    m.b.step();
+    m.c.step();
+}
+
+fn update(_app: &App, m: &mut Model, _update: Update) {
+    step(m);
+}
+
+// Only the particles' current positions, the trails stay on screen.
+fn scene(m: &Model) -> Canvas {
+    let mut canvas = Canvas::new(SIZE, SIZE);
+    for i in 0..m.n {
+        let r = map_range(i as f32, 0., m.n as f32, 1.0, 2.0);
+        canvas.ellipse(m.a.particles[i].position, r, r).color(BLUE);
+        canvas
+            .ellipse(m.b.particles[i].position, r, r)
+            .color(CORNFLOWERBLUE);
+        canvas.ellipse(m.c.particles[i].position, r, r).color(WHITE);
+    }
+    canvas
+}
+
+fn view(app: &App, m: &Model, frame: Frame) {
+    let draw = app.draw();
+    if frame.nth() == 0 {
+        frame.clear(WHITE);
+    }
+    scene(m).draw(&draw);
+    draw.to_frame(app, &frame).unwrap();
+}
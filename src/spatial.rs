@@ -0,0 +1,585 @@
+// Spatial indexes for neighbour queries over points, in place of scanning
+// every pair. A `HashGrid` buckets points into square cells and suits points
+// spread evenly with queries of about one cell; a `Quadtree` subdivides where
+// the points are and copes with clusters and queries of any size.
+//
+// Both hand out an id for each inserted point, the ids are handed out in
+// order from 0 so they can index a parallel `Vec` of whatever the points
+// stand for.
+
+use nannou::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+// The points by id, `None` once removed.
+#[derive(Debug, Clone, Default)]
+struct Points {
+    slots: Vec<Option<Point2>>,
+    len: usize,
+}
+
+impl Points {
+    fn insert(&mut self, p: Point2) -> usize {
+        self.slots.push(Some(p));
+        self.len += 1;
+        self.slots.len() - 1
+    }
+
+    fn remove(&mut self, id: usize) -> Option<Point2> {
+        let p = self.slots.get_mut(id)?.take()?;
+        self.len -= 1;
+        Some(p)
+    }
+
+    fn get(&self, id: usize) -> Option<Point2> {
+        self.slots.get(id).copied().flatten()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (usize, Point2)> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(id, p)| p.map(|p| (id, p)))
+    }
+}
+
+// Closest first, for the k nearest searches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Near<T> {
+    d2: f32,
+    item: T,
+}
+
+impl<T: PartialEq> Eq for Near<T> {}
+
+impl<T: PartialEq> PartialOrd for Near<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialEq> Ord for Near<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.d2.partial_cmp(&self.d2).unwrap_or(Ordering::Equal)
+    }
+}
+
+// Squared distance from `p` to the nearest point of `r`, 0 inside.
+fn rect_distance2(r: &Rect, p: Point2) -> f32 {
+    let dx = (r.left() - p.x).max(p.x - r.right()).max(0.0);
+    let dy = (r.bottom() - p.y).max(p.y - r.top()).max(0.0);
+    dx * dx + dy * dy
+}
+
+fn overlaps(a: &Rect, b: &Rect) -> bool {
+    a.left() <= b.right() && b.left() <= a.right() && a.bottom() <= b.top() && b.bottom() <= a.top()
+}
+
+// Inclusive of the edges, unlike `Rect::contains`.
+fn inside(r: &Rect, p: Point2) -> bool {
+    p.x >= r.left() && p.x <= r.right() && p.y >= r.bottom() && p.y <= r.top()
+}
+
+/// Points bucketed into square cells of side `size`, unbounded.
+#[derive(Debug, Clone)]
+pub struct HashGrid {
+    pub size: f32,
+    points: Points,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    // The corners of the occupied cells, so searches know when to stop.
+    min: (i32, i32),
+    max: (i32, i32),
+}
+
+impl HashGrid {
+    /// For radius queries `size` is best about the radius.
+    pub fn new(size: f32) -> Self {
+        assert!(size > 0.0, "cell size must be positive");
+        HashGrid {
+            size,
+            points: Points::default(),
+            cells: HashMap::new(),
+            min: (i32::MAX, i32::MAX),
+            max: (i32::MIN, i32::MIN),
+        }
+    }
+
+    fn cell(&self, p: Point2) -> (i32, i32) {
+        (
+            (p.x / self.size).floor() as i32,
+            (p.y / self.size).floor() as i32,
+        )
+    }
+
+    pub fn insert(&mut self, p: Point2) -> usize {
+        let id = self.points.insert(p);
+        let c = self.cell(p);
+        self.cells.entry(c).or_default().push(id);
+        self.min = (self.min.0.min(c.0), self.min.1.min(c.1));
+        self.max = (self.max.0.max(c.0), self.max.1.max(c.1));
+        id
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<Point2> {
+        let p = self.points.remove(id)?;
+        let c = self.cell(p);
+        let ids = self.cells.get_mut(&c).expect("point missing from its cell");
+        let i = ids.iter().position(|&j| j == id).unwrap();
+        ids.swap_remove(i);
+        if ids.is_empty() {
+            self.cells.remove(&c);
+        }
+        Some(p)
+    }
+
+    /// Move point `id` to `p`, keeping its id.
+    pub fn update(&mut self, id: usize, p: Point2) {
+        let old = self.remove(id).expect("no point with that id");
+        let (from, to) = (self.cell(old), self.cell(p));
+        self.points.slots[id] = Some(p);
+        self.points.len += 1;
+        self.cells.entry(to).or_default().push(id);
+        if from != to {
+            self.min = (self.min.0.min(to.0), self.min.1.min(to.1));
+            self.max = (self.max.0.max(to.0), self.max.1.max(to.1));
+        }
+    }
+
+    pub fn get(&self, id: usize) -> Option<Point2> {
+        self.points.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, Point2)> + '_ {
+        self.points.iter()
+    }
+
+    // The ids in the cells from `lo` to `hi` inclusive.
+    fn cells_between(&self, lo: (i32, i32), hi: (i32, i32)) -> impl Iterator<Item = usize> + '_ {
+        let lo = (lo.0.max(self.min.0), lo.1.max(self.min.1));
+        let hi = (hi.0.min(self.max.0), hi.1.min(self.max.1));
+        (lo.0..=hi.0)
+            .flat_map(move |i| (lo.1..=hi.1).map(move |j| (i, j)))
+            .filter_map(move |c| self.cells.get(&c))
+            .flatten()
+            .copied()
+    }
+
+    /// The ids of the points within `radius` of `center`.
+    pub fn within(&self, center: Point2, radius: f32) -> Vec<usize> {
+        let lo = self.cell(center - vec2(radius, radius));
+        let hi = self.cell(center + vec2(radius, radius));
+        let r2 = radius * radius;
+        self.cells_between(lo, hi)
+            .filter(|&id| self.points.slots[id].unwrap().distance2(center) <= r2)
+            .collect()
+    }
+
+    /// The ids of the points in `rect`, edges included.
+    pub fn in_rect(&self, rect: Rect) -> Vec<usize> {
+        let lo = self.cell(pt2(rect.left(), rect.bottom()));
+        let hi = self.cell(pt2(rect.right(), rect.top()));
+        self.cells_between(lo, hi)
+            .filter(|&id| inside(&rect, self.points.slots[id].unwrap()))
+            .collect()
+    }
+
+    // The occupied part of the square ring of cells `n` out from `c`.
+    fn ring(&self, c: (i32, i32), n: i32) -> Vec<(i32, i32)> {
+        let (lo, hi) = (self.min, self.max);
+        let mut cells = vec![];
+        let xs = (c.0 - n).max(lo.0)..=(c.0 + n).min(hi.0);
+        let ys = (c.1 - n + 1).max(lo.1)..=(c.1 + n - 1).min(hi.1);
+        for &j in &[c.1 - n, c.1 + n] {
+            if (lo.1..=hi.1).contains(&j) {
+                cells.extend(xs.clone().map(|i| (i, j)));
+            }
+            if n == 0 {
+                return cells;
+            }
+        }
+        for &i in &[c.0 - n, c.0 + n] {
+            if (lo.0..=hi.0).contains(&i) {
+                cells.extend(ys.clone().map(|j| (i, j)));
+            }
+        }
+        cells
+    }
+
+    /// The ids of the `k` points nearest `p`, nearest first.
+    pub fn nearest(&self, p: Point2, k: usize) -> Vec<usize> {
+        if k == 0 || self.is_empty() {
+            return vec![];
+        }
+        let c = self.cell(p);
+        let mut found: Vec<Near<usize>> = vec![];
+        // Search rings of cells around `p`, points outside ring `n` are at
+        // least `n` cells away.
+        for n in 0.. {
+            for cell in self.ring(c, n) {
+                if let Some(ids) = self.cells.get(&cell) {
+                    found.extend(ids.iter().map(|&id| Near {
+                        d2: self.points.slots[id].unwrap().distance2(p),
+                        item: id,
+                    }));
+                }
+            }
+            found.sort_by(|a, b| b.cmp(a));
+            let reach = n as f32 * self.size;
+            let done = found.len() >= k && found[k - 1].d2 <= reach * reach;
+            let past = c.0 - n <= self.min.0
+                && c.1 - n <= self.min.1
+                && c.0 + n >= self.max.0
+                && c.1 + n >= self.max.1;
+            if done || past {
+                break;
+            }
+        }
+        found.into_iter().take(k).map(|n| n.item).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    rect: Rect,
+    depth: usize,
+    ids: Vec<usize>,
+    children: Option<[usize; 4]>,
+}
+
+/// A region quadtree over `bounds`, nodes split when they hold more than
+/// `capacity` points.
+#[derive(Debug, Clone)]
+pub struct Quadtree {
+    pub bounds: Rect,
+    pub capacity: usize,
+    points: Points,
+    nodes: Vec<Node>,
+}
+
+// Deep enough for any sketch, and stops coincident points splitting forever.
+const MAX_DEPTH: usize = 24;
+
+impl Quadtree {
+    pub fn new(bounds: Rect) -> Self {
+        Quadtree {
+            bounds,
+            capacity: 8,
+            points: Points::default(),
+            nodes: vec![Node {
+                rect: bounds,
+                depth: 0,
+                ids: vec![],
+                children: None,
+            }],
+        }
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        self.capacity = capacity;
+        self
+    }
+
+    // The child of `node` whose quarter holds `p`, edges go up and right.
+    fn child(&self, node: usize, p: Point2) -> usize {
+        let n = &self.nodes[node];
+        let children = n.children.unwrap();
+        let (x, y) = (n.rect.x(), n.rect.y());
+        let i = (p.x >= x) as usize + 2 * (p.y >= y) as usize;
+        children[i]
+    }
+
+    fn leaf(&self, p: Point2) -> usize {
+        let mut node = 0;
+        while self.nodes[node].children.is_some() {
+            node = self.child(node, p);
+        }
+        node
+    }
+
+    fn split(&mut self, node: usize) {
+        let Node { rect, depth, .. } = self.nodes[node];
+        let w = rect.w() / 2.0;
+        let h = rect.h() / 2.0;
+        let mut children = [0; 4];
+        for (i, child) in children.iter_mut().enumerate() {
+            let x = rect.left() + w * (i % 2) as f32;
+            let y = rect.bottom() + h * (i / 2) as f32;
+            *child = self.nodes.len();
+            self.nodes.push(Node {
+                rect: Rect::from_corners(pt2(x, y), pt2(x + w, y + h)),
+                depth: depth + 1,
+                ids: vec![],
+                children: None,
+            });
+        }
+        self.nodes[node].children = Some(children);
+        for id in std::mem::take(&mut self.nodes[node].ids) {
+            let c = self.child(node, self.points.slots[id].unwrap());
+            self.nodes[c].ids.push(id);
+        }
+    }
+
+    /// Panics if `p` is outside the bounds.
+    pub fn insert(&mut self, p: Point2) -> usize {
+        assert!(
+            inside(&self.bounds, p),
+            "point {:?} outside the quadtree",
+            p
+        );
+        let id = self.points.insert(p);
+        self.place(id, p);
+        id
+    }
+
+    // Add `id` at `p` to its leaf, splitting full leaves.
+    fn place(&mut self, id: usize, p: Point2) {
+        let mut node = self.leaf(p);
+        while self.nodes[node].ids.len() >= self.capacity && self.nodes[node].depth < MAX_DEPTH {
+            self.split(node);
+            node = self.child(node, p);
+        }
+        self.nodes[node].ids.push(id);
+    }
+
+    /// Nodes are left in place, a tree emptied out is as deep as it was.
+    pub fn remove(&mut self, id: usize) -> Option<Point2> {
+        let p = self.points.remove(id)?;
+        let node = self.leaf(p);
+        let ids = &mut self.nodes[node].ids;
+        let i = ids.iter().position(|&j| j == id).unwrap();
+        ids.swap_remove(i);
+        Some(p)
+    }
+
+    /// Move point `id` to `p`, keeping its id.
+    pub fn update(&mut self, id: usize, p: Point2) {
+        assert!(
+            inside(&self.bounds, p),
+            "point {:?} outside the quadtree",
+            p
+        );
+        self.remove(id).expect("no point with that id");
+        self.points.slots[id] = Some(p);
+        self.points.len += 1;
+        self.place(id, p);
+    }
+
+    pub fn get(&self, id: usize) -> Option<Point2> {
+        self.points.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, Point2)> + '_ {
+        self.points.iter()
+    }
+
+    /// The rectangles of the leaves, for drawing the tree.
+    pub fn leaves(&self) -> Vec<Rect> {
+        self.nodes
+            .iter()
+            .filter(|n| n.children.is_none())
+            .map(|n| n.rect)
+            .collect()
+    }
+
+    // The ids in the nodes that `visit` lets through, `keep` picks the points.
+    fn search(&self, visit: impl Fn(&Rect) -> bool, keep: impl Fn(Point2) -> bool) -> Vec<usize> {
+        let mut found = vec![];
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let n = &self.nodes[node];
+            if !visit(&n.rect) {
+                continue;
+            }
+            match n.children {
+                Some(children) => stack.extend_from_slice(&children),
+                None => found.extend(
+                    n.ids
+                        .iter()
+                        .copied()
+                        .filter(|&id| keep(self.points.slots[id].unwrap())),
+                ),
+            }
+        }
+        found
+    }
+
+    /// The ids of the points within `radius` of `center`.
+    pub fn within(&self, center: Point2, radius: f32) -> Vec<usize> {
+        let r2 = radius * radius;
+        self.search(
+            |r| rect_distance2(r, center) <= r2,
+            |p| p.distance2(center) <= r2,
+        )
+    }
+
+    /// The ids of the points in `rect`, edges included.
+    pub fn in_rect(&self, rect: Rect) -> Vec<usize> {
+        self.search(|r| overlaps(r, &rect), |p| inside(&rect, p))
+    }
+
+    /// The ids of the `k` points nearest `p`, nearest first.
+    pub fn nearest(&self, p: Point2, k: usize) -> Vec<usize> {
+        // Best first, a point comes off the heap before anything farther.
+        let mut heap = BinaryHeap::new();
+        heap.push(Near {
+            d2: rect_distance2(&self.nodes[0].rect, p),
+            item: (true, 0),
+        });
+        let mut found = vec![];
+        while let Some(Near {
+            item: (is_node, i), ..
+        }) = heap.pop()
+        {
+            if found.len() == k {
+                break;
+            }
+            if !is_node {
+                found.push(i);
+                continue;
+            }
+            let n = &self.nodes[i];
+            match n.children {
+                Some(children) => heap.extend(children.iter().map(|&c| Near {
+                    d2: rect_distance2(&self.nodes[c].rect, p),
+                    item: (true, c),
+                })),
+                None => heap.extend(n.ids.iter().map(|&id| Near {
+                    d2: self.points.slots[id].unwrap().distance2(p),
+                    item: (false, id),
+                })),
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+
+    fn random_points(n: usize) -> Vec<Point2> {
+        rng::seed(3);
+        (0..n)
+            .map(|_| {
+                pt2(
+                    rng::random_range(-100.0, 100.0),
+                    rng::random_range(-100.0, 100.0),
+                )
+            })
+            .collect()
+    }
+
+    fn brute_within(points: &[Point2], c: Point2, r: f32) -> Vec<usize> {
+        (0..points.len())
+            .filter(|&i| points[i].distance(c) <= r)
+            .collect()
+    }
+
+    fn brute_nearest(points: &[Point2], c: Point2, k: usize) -> Vec<usize> {
+        let mut ids: Vec<usize> = (0..points.len()).collect();
+        ids.sort_by(|&a, &b| {
+            points[a]
+                .distance2(c)
+                .partial_cmp(&points[b].distance2(c))
+                .unwrap()
+        });
+        ids.truncate(k);
+        ids
+    }
+
+    fn sorted(mut ids: Vec<usize>) -> Vec<usize> {
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let points = random_points(2000);
+        let mut grid = HashGrid::new(10.0);
+        let mut tree = Quadtree::new(Rect::from_w_h(200.0, 200.0));
+        for &p in &points {
+            grid.insert(p);
+            tree.insert(p);
+        }
+        assert_eq!(grid.len(), 2000);
+        assert_eq!(tree.len(), 2000);
+        for &(c, r) in &[
+            (pt2(0.0, 0.0), 15.0),
+            (pt2(95.0, -80.0), 30.0),
+            (pt2(300.0, 0.0), 5.0),
+        ] {
+            let expected = brute_within(&points, c, r);
+            assert_eq!(sorted(grid.within(c, r)), expected);
+            assert_eq!(sorted(tree.within(c, r)), expected);
+        }
+        for &c in &[pt2(3.0, 4.0), pt2(-99.0, 99.0), pt2(500.0, 500.0)] {
+            let expected = brute_nearest(&points, c, 7);
+            assert_eq!(grid.nearest(c, 7), expected);
+            assert_eq!(tree.nearest(c, 7), expected);
+        }
+        let rect = Rect::from_corners(pt2(-20.0, 10.0), pt2(35.0, 60.0));
+        let expected: Vec<usize> = (0..points.len())
+            .filter(|&i| inside(&rect, points[i]))
+            .collect();
+        assert_eq!(sorted(grid.in_rect(rect)), expected);
+        assert_eq!(sorted(tree.in_rect(rect)), expected);
+    }
+
+    #[test]
+    fn remove() {
+        let points = random_points(500);
+        let mut grid = HashGrid::new(25.0);
+        let mut tree = Quadtree::new(Rect::from_w_h(200.0, 200.0)).capacity(4);
+        for &p in &points {
+            grid.insert(p);
+            tree.insert(p);
+        }
+        for id in (0..500).step_by(2) {
+            assert_eq!(grid.remove(id), Some(points[id]));
+            assert_eq!(tree.remove(id), Some(points[id]));
+        }
+        assert_eq!(grid.remove(0), None);
+        assert_eq!(tree.get(0), None);
+        assert_eq!(grid.len(), 250);
+        let all = grid.within(pt2(0.0, 0.0), 1000.0);
+        assert!(sorted(all).iter().all(|id| id % 2 == 1));
+        let near = tree.nearest(pt2(0.0, 0.0), 250);
+        assert_eq!(sorted(near), (1..500).step_by(2).collect::<Vec<_>>());
+        // Ids keep counting after a removal.
+        assert_eq!(grid.insert(pt2(0.0, 0.0)), 500);
+        grid.update(1, pt2(150.0, 150.0));
+        tree.update(1, pt2(99.0, 99.0));
+        assert_eq!(grid.nearest(pt2(200.0, 200.0), 1), vec![1]);
+        assert_eq!(tree.nearest(pt2(100.0, 100.0), 1), vec![1]);
+        assert_eq!(grid.len(), 251);
+    }
+
+    #[test]
+    fn coincident_points() {
+        let mut tree = Quadtree::new(Rect::from_w_h(10.0, 10.0)).capacity(2);
+        for _ in 0..50 {
+            tree.insert(pt2(1.0, 1.0));
+        }
+        assert_eq!(tree.within(pt2(1.0, 1.0), 0.0).len(), 50);
+        assert_eq!(tree.nearest(pt2(0.0, 0.0), 3).len(), 3);
+        assert!(tree.leaves().len() < 200);
+    }
+}
@@ -0,0 +1,341 @@
+// Strokes whose width changes along the line, for brush and calligraphic
+// marks. A canvas stroke has one weight from end to end, so instead the
+// centreline is turned into an outline that is filled.
+//
+// The width at each point is the brush width scaled by a profile of the
+// distance along the line, a pressure curve over the whole stroke and Perlin
+// noise, then narrowed to a point at tapered tips. The outline offsets each
+// side by half the width, joining outer corners like an offset and running
+// through the centreline at sharp inner corners. Those inner loops and any
+// place the stroke crosses itself overlap the same way round, so a nonzero
+// fill of the outline covers the stroke without holes, and `outline`
+// resolves it into a clean region for booleans and hatching.
+
+use nannou::noise::{NoiseFn, Perlin, Seedable};
+use nannou::prelude::*;
+
+use crate::arc::Arc;
+use crate::canvas::{polyline_segments, FillRule, Join, Segment, Shape};
+use crate::geometry::Polygon;
+use crate::polyline::arc_lengths;
+
+/// How the ends of a stroke finish.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tip {
+    /// Cut square across the end of the line.
+    Butt,
+    Round,
+    /// Narrowing to a point over this distance from the end.
+    Taper(f32),
+}
+
+pub struct Brush {
+    pub width: f32,
+    pub join: Join,
+    pub start: Tip,
+    pub end: Tip,
+    profile: Option<Box<dyn Fn(f32) -> f32>>,
+    pressure: Vec<f32>,
+    noise: Option<(Perlin, f32, f32)>,
+}
+
+// Round joins and tips, `turn` round from the direction `from`.
+fn arc(center: Point2, from: Vector2, turn: f32, radius: f32) -> Vec<Point2> {
+    Arc::new(center, radius, from.y.atan2(from.x), turn).points(0.1)
+}
+
+// Eases from 0 at the tip to 1 at `length` along, pointed at the tip.
+fn taper(tip: Tip, distance: f32) -> f32 {
+    match tip {
+        Tip::Taper(length) if length > 0.0 => {
+            let f = (distance / length).min(1.0);
+            1.0 - (1.0 - f) * (1.0 - f)
+        }
+        _ => 1.0,
+    }
+}
+
+impl Brush {
+    /// A brush `width` pixels wide with round tips and joins.
+    pub fn new(width: f32) -> Self {
+        Brush {
+            width,
+            join: Join::Round,
+            start: Tip::Round,
+            end: Tip::Round,
+            profile: None,
+            pressure: vec![],
+            noise: None,
+        }
+    }
+
+    pub fn join(mut self, join: Join) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Finish both ends with `tip`.
+    pub fn tips(mut self, tip: Tip) -> Self {
+        self.start = tip;
+        self.end = tip;
+        self
+    }
+
+    pub fn start(mut self, tip: Tip) -> Self {
+        self.start = tip;
+        self
+    }
+
+    pub fn end(mut self, tip: Tip) -> Self {
+        self.end = tip;
+        self
+    }
+
+    /// Scale the width by `f` of the distance along the line.
+    pub fn profile(mut self, f: impl Fn(f32) -> f32 + 'static) -> Self {
+        self.profile = Some(Box::new(f));
+        self
+    }
+
+    /// Scale the width by `levels` spread evenly from the start of the stroke
+    /// to the end, and linearly between them, like pen pressure.
+    pub fn pressure(mut self, levels: &[f32]) -> Self {
+        self.pressure = levels.to_vec();
+        self
+    }
+
+    /// Scale the width by 1 plus Perlin noise up to `amount`, with bumps about
+    /// `wavelength` apart along the line.
+    pub fn noise(mut self, amount: f32, wavelength: f32, seed: u32) -> Self {
+        self.noise = Some((Perlin::new().set_seed(seed), amount, wavelength));
+        self
+    }
+
+    /// The width `distance` along a line `length` long.
+    pub fn width_at(&self, distance: f32, length: f32) -> f32 {
+        let mut w = self.width;
+        if let Some(f) = &self.profile {
+            w *= f(distance);
+        }
+        match self.pressure.len() {
+            0 => {}
+            1 => w *= self.pressure[0],
+            n => {
+                let t = if length > 0.0 { distance / length } else { 0.0 };
+                let x = t.clamp(0.0, 1.0) * (n - 1) as f32;
+                let i = (x.floor() as usize).min(n - 2);
+                let f = x - i as f32;
+                w *= self.pressure[i] * (1.0 - f) + self.pressure[i + 1] * f;
+            }
+        }
+        if let Some((noise, amount, wavelength)) = &self.noise {
+            // Off the integer lattice, where Perlin noise is always 0.
+            let x = (distance / wavelength) as f64 + 0.5;
+            w *= 1.0 + amount * noise.get([x, 0.5]) as f32;
+        }
+        w *= taper(self.start, distance) * taper(self.end, length - distance);
+        w.max(0.0)
+    }
+
+    // Distance between points fine enough for the width to follow the
+    // profile, pressure, tapers and noise.
+    fn detail(&self) -> f32 {
+        let mut d = if self.profile.is_some() || self.pressure.len() > 1 {
+            self.width.max(1.0)
+        } else {
+            f32::MAX
+        };
+        for tip in &[self.start, self.end] {
+            if let Tip::Taper(length) = tip {
+                d = d.min(length / 8.0);
+            }
+        }
+        if let Some((_, _, wavelength)) = self.noise {
+            d = d.min(wavelength / 8.0);
+        }
+        d.max(0.5)
+    }
+
+    // The centreline without repeated points, with long edges split so no
+    // point is more than `detail` from the next.
+    fn centreline(&self, line: &[Point2]) -> Vec<Point2> {
+        let detail = self.detail();
+        let mut points: Vec<Point2> = vec![];
+        for &p in line {
+            let last = match points.last() {
+                Some(&last) if last.distance(p) > 1e-4 => last,
+                Some(_) => continue,
+                None => {
+                    points.push(p);
+                    continue;
+                }
+            };
+            let steps = (last.distance(p) / detail).ceil() as usize;
+            for k in 1..=steps {
+                points.push(last.lerp(p, k as f32 / steps as f32));
+            }
+        }
+        points
+    }
+
+    // One side of the outline, offset left by the half widths `w`, from the
+    // start to the end of the line.
+    fn side(&self, points: &[Point2], w: &[f32], out: &mut Vec<Point2>) {
+        let n = points.len();
+        let normal = |i: usize| {
+            let d = (points[i + 1] - points[i]).normalize();
+            vec2(-d.y, d.x)
+        };
+        out.push(points[0] + normal(0) * w[0]);
+        for i in 1..n - 1 {
+            let (a, b, p) = (normal(i - 1), normal(i), points[i]);
+            let turn = a.perp_dot(b).atan2(a.dot(b));
+            let half = (turn.abs() / 2.0).cos();
+            if turn > 0.0 {
+                // Inside the corner the sides cross. Where they cross
+                // within both edges cut the corner there, otherwise loop
+                // round through the centreline.
+                let reach = w[i] * (turn.abs() / 2.0).tan();
+                let room = points[i - 1].distance(p).min(points[i + 1].distance(p));
+                if reach <= room {
+                    out.push(p + (a + b).normalize() * w[i] / half);
+                } else {
+                    out.extend_from_slice(&[p + a * w[i], p, p + b * w[i]]);
+                }
+                continue;
+            }
+            match self.join {
+                Join::Round if w[i] > 0.0 => out.extend(arc(p, a, turn, w[i])),
+                Join::Miter if half > 0.25 => out.push(p + (a + b).normalize() * w[i] / half),
+                _ => out.extend_from_slice(&[p + a * w[i], p + b * w[i]]),
+            }
+        }
+        out.push(points[n - 1] + normal(n - 2) * w[n - 1]);
+    }
+
+    // Carry the outline round the end of the line at `p` from the left side,
+    // along the normal `n`, to the right.
+    fn tip(tip: Tip, p: Point2, n: Vector2, w: f32, out: &mut Vec<Point2>) {
+        if tip == Tip::Round && w > 0.0 {
+            let mut points = arc(p, n, -PI, w);
+            points.remove(0);
+            points.pop();
+            out.extend(points);
+        }
+    }
+
+    /// The closed outline of `line` drawn with the brush, to be filled with
+    /// the nonzero rule. Empty for lines with no length.
+    pub fn ring(&self, line: &[Point2]) -> Vec<Point2> {
+        let mut points = self.centreline(line);
+        let n = points.len();
+        if n < 2 {
+            return vec![];
+        }
+        let lengths = arc_lengths(&points);
+        let length = lengths[n - 1];
+        let mut w: Vec<f32> = lengths
+            .iter()
+            .map(|&s| self.width_at(s, length) / 2.0)
+            .collect();
+        let mut out = vec![];
+        self.side(&points, &w, &mut out);
+        let d = (points[n - 1] - points[n - 2]).normalize();
+        Brush::tip(self.end, points[n - 1], vec2(-d.y, d.x), w[n - 1], &mut out);
+        points.reverse();
+        w.reverse();
+        self.side(&points, &w, &mut out);
+        let d = (points[n - 1] - points[n - 2]).normalize();
+        Brush::tip(
+            self.start,
+            points[n - 1],
+            vec2(-d.y, d.x),
+            w[n - 1],
+            &mut out,
+        );
+        out
+    }
+
+    /// The outline of `line` as path segments, fill them with the nonzero
+    /// rule.
+    pub fn segments(&self, line: &[Point2]) -> Vec<Segment> {
+        polyline_segments(self.ring(line), true)
+    }
+
+    /// `line` drawn with the brush as a shape filled in white, for
+    /// `Canvas::add`.
+    pub fn shape(&self, line: &[Point2]) -> Shape {
+        let mut shape = Shape::path(self.segments(line));
+        shape.rule(FillRule::NonZero);
+        shape
+    }
+
+    /// The region `line` drawn with the brush covers, with the overlaps
+    /// resolved.
+    pub fn outline(&self, line: &[Point2]) -> Polygon {
+        Polygon::nonzero(vec![self.ring(line)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-2 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn tips_add_to_the_area() {
+        let line = [pt2(0.0, 0.0), pt2(50.0, 0.0), pt2(100.0, 0.0)];
+        let butt = Brush::new(10.0).tips(Tip::Butt).outline(&line);
+        assert!(close(butt.area(), 1000.0));
+        let round = Brush::new(10.0).outline(&line);
+        assert!(close(round.area(), 1000.0 + PI * 25.0));
+        assert!(round.contains(pt2(104.0, 0.0)) && !butt.contains(pt2(104.0, 0.0)));
+    }
+
+    #[test]
+    fn widths_follow_pressure_and_tapers() {
+        let brush = Brush::new(10.0)
+            .pressure(&[1.0, 2.0, 1.0])
+            .start(Tip::Taper(20.0))
+            .end(Tip::Butt);
+        assert_eq!(brush.width_at(0.0, 100.0), 0.0);
+        assert!(close(brush.width_at(10.0, 100.0), 10.0 * 1.2 * 0.75));
+        assert!(close(brush.width_at(50.0, 100.0), 20.0));
+        assert!(close(brush.width_at(100.0, 100.0), 10.0));
+        let outline = brush.outline(&[pt2(0.0, 0.0), pt2(100.0, 0.0)]);
+        assert!(outline.contains(pt2(50.0, 9.0)) && !outline.contains(pt2(90.0, 9.0)));
+        assert!(!outline.contains(pt2(1.0, 2.0)));
+    }
+
+    #[test]
+    fn sharp_corners_leave_no_holes() {
+        // A zigzag much narrower than the brush.
+        let line = [
+            pt2(0.0, 0.0),
+            pt2(10.0, 30.0),
+            pt2(20.0, 0.0),
+            pt2(30.0, 30.0),
+        ];
+        for &join in &[Join::Round, Join::Miter, Join::Bevel] {
+            let outline = Brush::new(16.0).join(join).outline(&line);
+            assert_eq!(outline.rings.len(), 1);
+            for &p in &line {
+                assert!(outline.contains(p));
+            }
+            assert!(outline.contains(pt2(10.0, 20.0)) && outline.contains(pt2(20.0, 10.0)));
+        }
+    }
+
+    #[test]
+    fn noise_varies_the_width() {
+        let brush = Brush::new(10.0).noise(0.5, 20.0, 3);
+        let widths: Vec<f32> = (0..50)
+            .map(|i| brush.width_at(i as f32 * 4.0, 200.0))
+            .collect();
+        assert!(widths.iter().all(|&w| (5.0..=15.0).contains(&w)));
+        assert!(widths.iter().any(|&w| w < 9.0) && widths.iter().any(|&w| w > 11.0));
+    }
+}
@@ -0,0 +1,579 @@
+// Drawing under a symmetry group. A scene closure draws one motif and
+// `Symmetry::draw` replays it once for every transform in the group, so the
+// copies stay exact however the motif is drawn.
+//
+// - Rosettes, the cyclic and dihedral groups, turn the motif about the
+//   origin, the dihedral ones mirroring every other copy.
+// - The 7 frieze groups repeat it along the x axis in a strip.
+// - The 17 wallpaper groups repeat it over a lattice across the canvas.
+//
+// The group operations are written in lattice coordinates as in the
+// International Tables, hexagonal lattices with 120 degrees between the
+// axes, and moved onto the canvas with the lattice. A motif drawn across the
+// whole plane overlaps its copies; clipping keeps each copy to its image of
+// the fundamental domain, which tile the plane once over.
+
+use nannou::prelude::*;
+
+use crate::canvas::{Affine, Canvas, Mask};
+use crate::geometry::Polygon;
+
+/// The frieze groups, named as in the International Tables, with Conway's
+/// names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frieze {
+    /// Hop, translations only.
+    P1,
+    /// Step, a glide along the axis.
+    P11g,
+    /// Sidle, mirrors across the axis.
+    P1m1,
+    /// Spinning hop, half turns on the axis.
+    P2,
+    /// Spinning sidle, half turns and mirrors across the axis.
+    P2mg,
+    /// Jump, a mirror along the axis.
+    P11m,
+    /// Spinning jump, mirrors along and across the axis.
+    P2mm,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wallpaper {
+    P1,
+    P2,
+    Pm,
+    Pg,
+    Cm,
+    Pmm,
+    Pmg,
+    Pgg,
+    Cmm,
+    P4,
+    P4m,
+    P4g,
+    P3,
+    P3m1,
+    P31m,
+    P6,
+    P6m,
+}
+
+impl Wallpaper {
+    pub const ALL: [Wallpaper; 17] = [
+        Wallpaper::P1,
+        Wallpaper::P2,
+        Wallpaper::Pm,
+        Wallpaper::Pg,
+        Wallpaper::Cm,
+        Wallpaper::Pmm,
+        Wallpaper::Pmg,
+        Wallpaper::Pgg,
+        Wallpaper::Cmm,
+        Wallpaper::P4,
+        Wallpaper::P4m,
+        Wallpaper::P4g,
+        Wallpaper::P3,
+        Wallpaper::P3m1,
+        Wallpaper::P31m,
+        Wallpaper::P6,
+        Wallpaper::P6m,
+    ];
+
+    pub fn is_hexagonal(self) -> bool {
+        use Wallpaper::*;
+        matches!(self, P3 | P3m1 | P31m | P6 | P6m)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Group {
+    /// `n` turns about the origin, at least one.
+    Cyclic(usize),
+    /// `n` turns about the origin and `n` mirrors through it, the first
+    /// along the x axis.
+    Dihedral(usize),
+    Frieze(Frieze),
+    Wallpaper(Wallpaper),
+}
+
+// `(x, y) -> (xx * x + xy * y + x0, yx * x + yy * y + y0)` in lattice
+// coordinates.
+type Op = [f32; 6];
+
+// The turns of p3.
+const THIRDS: [Op; 3] = [
+    [1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+    [0.0, -1.0, 0.0, 1.0, -1.0, 0.0],
+    [-1.0, 1.0, 0.0, -1.0, 0.0, 0.0],
+];
+// Mirrors through every 3-fold centre.
+const P3M1: [Op; 3] = [
+    [0.0, -1.0, 0.0, -1.0, 0.0, 0.0],
+    [-1.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+    [1.0, 0.0, 0.0, 1.0, -1.0, 0.0],
+];
+const P31M: [Op; 3] = [
+    [0.0, 1.0, 0.0, 1.0, 0.0, 0.0],
+    [1.0, -1.0, 0.0, 0.0, -1.0, 0.0],
+    [-1.0, 0.0, 0.0, -1.0, 1.0, 0.0],
+];
+// The half turns that make p3 into p6.
+const SIXTHS: [Op; 3] = [
+    [-1.0, 0.0, 0.0, 0.0, -1.0, 0.0],
+    [0.0, 1.0, 0.0, -1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0, 1.0, 0.0, 0.0],
+];
+
+fn op(o: &Op) -> Affine {
+    Affine {
+        a: o[0],
+        c: o[1],
+        e: o[2],
+        b: o[3],
+        d: o[4],
+        f: o[5],
+    }
+}
+
+// The operations of one cell and the fundamental domain in lattice
+// coordinates. Strips and wedges reach out `far`.
+fn cell(group: Group, far: f32) -> (Vec<Op>, Vec<Point2>) {
+    let p = pt2;
+    match group {
+        Group::Cyclic(_) | Group::Dihedral(_) => (vec![], vec![]),
+        Group::Frieze(f) => {
+            use Frieze::*;
+            let ops: &[Op] = match f {
+                P1 => &[[1.0, 0.0, 0.0, 0.0, 1.0, 0.0]],
+                P11g => &[
+                    [1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                    [1.0, 0.0, 0.5, 0.0, -1.0, 0.0],
+                ],
+                P1m1 => &[
+                    [1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                    [-1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                ],
+                P2 => &[
+                    [1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                    [-1.0, 0.0, 0.0, 0.0, -1.0, 0.0],
+                ],
+                P2mg => &[
+                    [1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                    [-1.0, 0.0, 0.0, 0.0, -1.0, 0.0],
+                    [-1.0, 0.0, 0.5, 0.0, 1.0, 0.0],
+                    [1.0, 0.0, 0.5, 0.0, -1.0, 0.0],
+                ],
+                P11m => &[
+                    [1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                    [1.0, 0.0, 0.0, 0.0, -1.0, 0.0],
+                ],
+                P2mm => &[
+                    [1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                    [-1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                    [1.0, 0.0, 0.0, 0.0, -1.0, 0.0],
+                    [-1.0, 0.0, 0.0, 0.0, -1.0, 0.0],
+                ],
+            };
+            let (w, lo) = match f {
+                P1 => (1.0, -far),
+                P11g | P1m1 | P2 => (0.5, -far),
+                P2mg => (0.25, -far),
+                P11m => (1.0, 0.0),
+                P2mm => (0.5, 0.0),
+            };
+            let domain = vec![p(0.0, lo), p(w, lo), p(w, far), p(0.0, far)];
+            (ops.to_vec(), domain)
+        }
+        Group::Wallpaper(w) => {
+            use Wallpaper::*;
+            let id = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+            let half = [-1.0, 0.0, 0.0, 0.0, -1.0, 0.0];
+            let mx = [-1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+            let my = [1.0, 0.0, 0.0, 0.0, -1.0, 0.0];
+            let quarter = [0.0, -1.0, 0.0, 1.0, 0.0, 0.0];
+            let three = [0.0, 1.0, 0.0, -1.0, 0.0, 0.0];
+            let diagonal = [0.0, 1.0, 0.0, 1.0, 0.0, 0.0];
+            let anti = [0.0, -1.0, 0.0, -1.0, 0.0, 0.0];
+            // `ops` moved by half a cell along both axes.
+            let centred = |ops: &[Op]| -> Vec<Op> {
+                ops.iter()
+                    .map(|o| [o[0], o[1], o[2] + 0.5, o[3], o[4], o[5] + 0.5])
+                    .collect()
+            };
+            let square = |x: f32, y: f32| vec![p(0.0, 0.0), p(x, 0.0), p(x, y), p(0.0, y)];
+            match w {
+                P1 => (vec![id], square(1.0, 1.0)),
+                P2 => (vec![id, half], square(0.5, 1.0)),
+                Pm => (vec![id, mx], square(0.5, 1.0)),
+                Pg => (vec![id, [-1.0, 0.0, 0.0, 0.0, 1.0, 0.5]], square(1.0, 0.5)),
+                Cm => (
+                    [vec![id, mx], centred(&[id, mx])].concat(),
+                    square(0.5, 0.5),
+                ),
+                Pmm => (vec![id, half, mx, my], square(0.5, 0.5)),
+                Pmg => (
+                    vec![
+                        id,
+                        half,
+                        [-1.0, 0.0, 0.5, 0.0, 1.0, 0.0],
+                        [1.0, 0.0, 0.5, 0.0, -1.0, 0.0],
+                    ],
+                    vec![p(0.0, -0.5), p(0.25, -0.5), p(0.25, 0.5), p(0.0, 0.5)],
+                ),
+                Pgg => (
+                    vec![
+                        id,
+                        half,
+                        [-1.0, 0.0, 0.5, 0.0, 1.0, 0.5],
+                        [1.0, 0.0, 0.5, 0.0, -1.0, 0.5],
+                    ],
+                    square(0.5, 0.5),
+                ),
+                Cmm => {
+                    let ops = [id, half, mx, my];
+                    ([ops.to_vec(), centred(&ops)].concat(), square(0.5, 0.25))
+                }
+                P4 => (vec![id, half, quarter, three], square(0.5, 0.5)),
+                P4m => (
+                    vec![id, half, quarter, three, mx, my, diagonal, anti],
+                    vec![p(0.0, 0.0), p(0.5, 0.0), p(0.5, 0.5)],
+                ),
+                P4g => {
+                    let mirrors = [
+                        [-1.0, 0.0, 0.5, 0.0, 1.0, 0.5],
+                        [1.0, 0.0, 0.5, 0.0, -1.0, 0.5],
+                        [0.0, 1.0, 0.5, 1.0, 0.0, 0.5],
+                        [0.0, -1.0, 0.5, -1.0, 0.0, 0.5],
+                    ];
+                    (
+                        [vec![id, half, quarter, three], mirrors.to_vec()].concat(),
+                        vec![p(0.0, 0.0), p(0.5, 0.0), p(0.0, 0.5)],
+                    )
+                }
+                P3 => (
+                    THIRDS.to_vec(),
+                    vec![
+                        p(0.0, 0.0),
+                        p(2.0 / 3.0, 1.0 / 3.0),
+                        p(1.0, 1.0),
+                        p(1.0 / 3.0, 2.0 / 3.0),
+                    ],
+                ),
+                P3m1 => (
+                    [THIRDS, P3M1].concat(),
+                    vec![
+                        p(0.0, 0.0),
+                        p(2.0 / 3.0, 1.0 / 3.0),
+                        p(1.0 / 3.0, 2.0 / 3.0),
+                    ],
+                ),
+                P31m => (
+                    [THIRDS, P31M].concat(),
+                    vec![p(0.0, 0.0), p(1.0, 0.0), p(2.0 / 3.0, 1.0 / 3.0)],
+                ),
+                P6 => (
+                    [THIRDS, SIXTHS].concat(),
+                    vec![p(0.0, 0.0), p(1.0, 0.0), p(2.0 / 3.0, 1.0 / 3.0)],
+                ),
+                P6m => (
+                    [THIRDS, SIXTHS, P3M1, P31M].concat(),
+                    vec![p(0.0, 0.0), p(0.5, 0.0), p(2.0 / 3.0, 1.0 / 3.0)],
+                ),
+            }
+        }
+    }
+}
+
+pub struct Symmetry {
+    pub group: Group,
+    /// Keep each copy to its image of the fundamental domain.
+    pub clip: bool,
+    a: Vector2,
+    b: Vector2,
+}
+
+impl Symmetry {
+    /// The group on a lattice 100 pixels across, hexagonal for the groups
+    /// with 3 and 6-fold turns and square for the rest.
+    pub fn new(group: Group) -> Self {
+        if let Group::Cyclic(n) | Group::Dihedral(n) = group {
+            assert!(n > 0, "a rosette needs at least one turn");
+        }
+        Symmetry {
+            group,
+            clip: false,
+            a: vec2(0.0, 0.0),
+            b: vec2(0.0, 0.0),
+        }
+        .size(100.0)
+    }
+
+    /// The default lattice with cells `size` across.
+    pub fn size(mut self, size: f32) -> Self {
+        let hexagonal = matches!(self.group, Group::Wallpaper(w) if w.is_hexagonal());
+        self.a = vec2(size, 0.0);
+        self.b = if hexagonal {
+            vec2(-0.5, 0.75f32.sqrt()) * size
+        } else {
+            vec2(0.0, size)
+        };
+        self
+    }
+
+    /// Lay the cells on the lattice with sides `a` and `b`. The lattice must
+    /// suit the group: rectangular groups need `a` and `b` at right angles,
+    /// centred, square and hexagonal ones also the same length, and
+    /// hexagonal ones 120 degrees apart. Friezes repeat along `a`.
+    pub fn lattice(mut self, a: Vector2, b: Vector2) -> Self {
+        self.a = a;
+        self.b = b;
+        self
+    }
+
+    pub fn clip(mut self) -> Self {
+        self.clip = true;
+        self
+    }
+
+    // From lattice coordinates to the canvas.
+    fn basis(&self) -> Affine {
+        Affine {
+            a: self.a.x,
+            b: self.a.y,
+            c: self.b.x,
+            d: self.b.y,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    // How far out to reach, in lattice coordinates for lattices and pixels
+    // for rosettes, to cover `bounds`.
+    fn far(&self, bounds: Rect) -> f32 {
+        let corners = [
+            bounds.bottom_left(),
+            bounds.bottom_right(),
+            bounds.top_left(),
+            bounds.top_right(),
+        ];
+        let to_lattice = self.basis().invert();
+        corners
+            .iter()
+            .map(|&p| match self.group {
+                Group::Cyclic(_) | Group::Dihedral(_) => p.magnitude(),
+                _ => {
+                    let q = to_lattice.apply(p);
+                    q.x.abs().max(q.y.abs())
+                }
+            })
+            .fold(0.0, f32::max)
+            * 2.0
+            + 2.0
+    }
+
+    /// The transforms from the motif to each copy that reaches into
+    /// `bounds`, starting with the identity.
+    pub fn transforms(&self, bounds: Rect) -> Vec<Affine> {
+        let reflect = Affine::scale(1.0, -1.0);
+        match self.group {
+            Group::Cyclic(n) => (0..n)
+                .map(|k| Affine::rotate(TAU * k as f32 / n as f32))
+                .collect(),
+            Group::Dihedral(n) => (0..n)
+                .flat_map(|k| {
+                    let turn = Affine::rotate(TAU * k as f32 / n as f32);
+                    vec![turn, reflect.then(&turn)]
+                })
+                .collect(),
+            _ => {
+                let (ops, _) = cell(self.group, 0.0);
+                let basis = self.basis();
+                let to_lattice = basis.invert();
+                // The lattice coordinates of `bounds`, and a cell more
+                // since the operations move the motif up to a cell away.
+                let corners = [
+                    bounds.bottom_left(),
+                    bounds.bottom_right(),
+                    bounds.top_left(),
+                    bounds.top_right(),
+                ];
+                let qs: Vec<Point2> = corners.iter().map(|&p| to_lattice.apply(p)).collect();
+                let range = |f: fn(&Point2) -> f32| {
+                    let lo = qs.iter().map(f).fold(f32::MAX, f32::min).floor() as i32 - 2;
+                    let hi = qs.iter().map(f).fold(f32::MIN, f32::max).ceil() as i32 + 1;
+                    lo..=hi
+                };
+                let rows = match self.group {
+                    Group::Frieze(_) => 0..=0,
+                    _ => range(|q| q.y),
+                };
+                let mut transforms = vec![];
+                for j in rows {
+                    for i in range(|q| q.x) {
+                        let shift = Affine::translate(i as f32, j as f32);
+                        for o in &ops {
+                            let t = to_lattice.then(&op(o)).then(&shift).then(&basis);
+                            transforms.push(t);
+                        }
+                    }
+                }
+                // The motif itself first.
+                if let Some(k) = transforms.iter().position(is_identity) {
+                    transforms.swap(0, k);
+                }
+                transforms
+            }
+        }
+    }
+
+    /// The fundamental domain, reaching far enough past `bounds` where it has
+    /// no end.
+    pub fn domain(&self, bounds: Rect) -> Polygon {
+        let far = self.far(bounds);
+        match self.group {
+            Group::Cyclic(n) | Group::Dihedral(n) => {
+                let angle = match self.group {
+                    Group::Cyclic(_) => TAU / n as f32,
+                    _ => PI / n as f32,
+                };
+                // A wedge, its arc outside the bounds.
+                let steps = (angle / (PI / 8.0)).ceil() as usize;
+                let mut points = vec![pt2(0.0, 0.0)];
+                for k in 0..=steps {
+                    let a = angle * k as f32 / steps as f32;
+                    points.push(vec2(a.cos(), a.sin()) * far / (angle / steps as f32 / 2.0).cos());
+                }
+                Polygon::new(points)
+            }
+            _ => {
+                let (_, domain) = cell(self.group, far);
+                let basis = self.basis();
+                Polygon::new(domain.into_iter().map(|p| basis.apply(p)).collect())
+            }
+        }
+    }
+
+    /// Run `scene` once for each copy that reaches onto the canvas, with its
+    /// transform applied, clipped to the domain if `clip` is set.
+    pub fn draw(&self, canvas: &mut Canvas, scene: impl Fn(&mut Canvas)) {
+        let bounds = canvas.bounds();
+        let domain = if self.clip {
+            Some(self.domain(bounds))
+        } else {
+            None
+        };
+        for t in self.transforms(bounds) {
+            canvas.with_transform(t, |c| match &domain {
+                Some(d) => c.with_mask(Mask::region(d), |c| scene(c)),
+                None => scene(c),
+            });
+        }
+    }
+}
+
+fn is_identity(t: &Affine) -> bool {
+    let i = Affine::IDENTITY;
+    [
+        t.a - i.a,
+        t.b - i.b,
+        t.c - i.c,
+        t.d - i.d,
+        t.e - i.e,
+        t.f - i.f,
+    ]
+    .iter()
+    .all(|d| d.abs() < 1e-4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // How many copies of the domain cover `p`.
+    fn cover(s: &Symmetry, bounds: Rect, p: Point2) -> usize {
+        let domain = s.domain(bounds);
+        s.transforms(bounds)
+            .iter()
+            .filter(|t| domain.contains(t.invert().apply(p)))
+            .count()
+    }
+
+    // Points spread over the middle of `bounds` that miss domain edges.
+    fn probes(bounds: Rect) -> Vec<Point2> {
+        (0..40)
+            .map(|i| {
+                let (u, v) = (
+                    (i as f32 * 0.618_034).fract(),
+                    (i as f32 * 0.414_214 + 0.1).fract(),
+                );
+                pt2(
+                    bounds.left() + bounds.w() * (0.25 + u / 2.0),
+                    bounds.bottom() + bounds.h() * (0.25 + v / 2.0),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn domains_tile_the_plane_once() {
+        let bounds = Rect::from_w_h(400.0, 300.0);
+        let mut groups = vec![Group::Cyclic(5), Group::Dihedral(3), Group::Dihedral(6)];
+        groups.extend(Wallpaper::ALL.iter().map(|&w| Group::Wallpaper(w)));
+        for &f in &[
+            Frieze::P1,
+            Frieze::P11g,
+            Frieze::P1m1,
+            Frieze::P2,
+            Frieze::P2mg,
+            Frieze::P11m,
+            Frieze::P2mm,
+        ] {
+            groups.push(Group::Frieze(f));
+        }
+        for group in groups {
+            let s = Symmetry::new(group).size(70.0);
+            for p in probes(bounds) {
+                assert_eq!(cover(&s, bounds, p), 1, "{:?} at {:?}", group, p);
+            }
+        }
+    }
+
+    #[test]
+    fn copies_are_symmetries() {
+        let bounds = Rect::from_w_h(400.0, 300.0);
+        // Group operations map the set of copies onto itself.
+        let s = Symmetry::new(Group::Wallpaper(Wallpaper::P4g)).size(50.0);
+        let ts = s.transforms(bounds);
+        assert!(is_identity(&ts[0]));
+        let p = pt2(3.0, 7.0);
+        let images: Vec<Point2> = ts.iter().map(|t| t.apply(p)).collect();
+        let g = *ts
+            .iter()
+            .find(|t| !is_identity(t) && t.apply(pt2(0.0, 0.0)).magnitude() < 60.0)
+            .unwrap();
+        for q in images.iter().filter(|q| q.magnitude() < 50.0) {
+            let moved = g.apply(*q);
+            assert!(images.iter().any(|r| r.distance(moved) < 1e-2));
+        }
+        assert_eq!(
+            Symmetry::new(Group::Dihedral(5)).transforms(bounds).len(),
+            10
+        );
+    }
+
+    #[test]
+    fn draw_clips_to_the_domain() {
+        let mut canvas = Canvas::new(200.0, 200.0);
+        Symmetry::new(Group::Cyclic(4))
+            .clip()
+            .draw(&mut canvas, |c| {
+                c.rect(pt2(0.0, 0.0), 100.0, 100.0);
+            });
+        assert_eq!(canvas.shapes().len(), 4);
+        assert_eq!(canvas.masks().len(), 4);
+        let clipped = canvas.clip_masks();
+        let area: f32 = clipped.shapes().iter().map(|s| s.region().area()).sum();
+        assert!((area - 10_000.0).abs() < 1.0, "{}", area);
+    }
+}
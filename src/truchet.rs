@@ -0,0 +1,589 @@
+// Truchet tilings on square and hex grids. A tile is a set of paths between
+// ports on its cell's edges, a tiling places one tile from a tile set in every
+// cell of a grid, and because neighbouring cells share their ports the paths
+// join up into polylines that run across the whole grid.
+
+use nannou::geom::Rect;
+use nannou::noise::NoiseFn;
+use nannou::prelude::*;
+use std::collections::HashMap;
+
+use crate::hexgrid::Layout;
+use crate::rng::random_range;
+
+/// A cell of a grid, `corners` go anti-clockwise and edge `k` runs from corner
+/// `k` to corner `k + 1`. `key` is `(col, row)` for square grids and `(q, r)`
+/// for hex grids.
+#[derive(Debug, Clone)]
+pub struct Cell {
+    pub key: (i32, i32),
+    pub center: Point2,
+    pub corners: Vec<Point2>,
+}
+
+impl Cell {
+    fn port(&self, port: Port) -> Point2 {
+        let n = self.corners.len();
+        let a = self.corners[port.edge % n];
+        let b = self.corners[(port.edge + 1) % n];
+        a + (b - a) * port.at
+    }
+
+    fn edge_direction(&self, edge: usize) -> Vector2 {
+        let n = self.corners.len();
+        (self.corners[(edge + 1) % n] - self.corners[edge % n]).normalize()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    pub sides: usize,
+    pub cells: Vec<Cell>,
+}
+
+impl Grid {
+    /// Squares of side `size` covering `rect`, starting at its bottom left
+    /// corner. Edge 0 is on the right and edge 1 at the top.
+    pub fn square(rect: Rect, size: f32) -> Self {
+        let cols = (rect.w() / size).ceil() as i32;
+        let rows = (rect.h() / size).ceil() as i32;
+        let s = size / 2.0;
+        let mut cells = vec![];
+        for col in 0..cols {
+            for row in 0..rows {
+                let center = pt2(
+                    rect.left() + (col as f32 + 0.5) * size,
+                    rect.bottom() + (row as f32 + 0.5) * size,
+                );
+                let corners = vec![
+                    center + vec2(s, -s),
+                    center + vec2(s, s),
+                    center + vec2(-s, s),
+                    center + vec2(-s, -s),
+                ];
+                cells.push(Cell {
+                    key: (col, row),
+                    center,
+                    corners,
+                });
+            }
+        }
+        Grid { sides: 4, cells }
+    }
+
+    /// The hexes of `layout` that overlap `rect`.
+    pub fn hex(layout: Layout, rect: Rect) -> Self {
+        let cells = layout
+            .hexes_in(rect)
+            .into_iter()
+            .map(|h| Cell {
+                key: (h.q, h.r),
+                center: layout.center(h),
+                corners: layout.corners(h),
+            })
+            .collect();
+        Grid { sides: 6, cells }
+    }
+
+    /// For each cell, the index of the cell across each of its edges, found by
+    /// the edges they share.
+    pub fn neighbors(&self) -> Vec<Vec<Option<usize>>> {
+        let key = |p: Point2| ((p.x * 64.0).round() as i64, (p.y * 64.0).round() as i64);
+        let mut edges: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (i, cell) in self.cells.iter().enumerate() {
+            for e in 0..self.sides {
+                edges
+                    .entry(key(cell.port(Port::mid(e))))
+                    .or_default()
+                    .push(i);
+            }
+        }
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                (0..self.sides)
+                    .map(|e| {
+                        edges[&key(cell.port(Port::mid(e)))]
+                            .iter()
+                            .copied()
+                            .find(|j| *j != i)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// A point `at` of the way along `edge`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Port {
+    pub edge: usize,
+    pub at: f32,
+}
+
+impl Port {
+    pub fn new(edge: usize, at: f32) -> Self {
+        Port { edge, at }
+    }
+
+    pub fn mid(edge: usize) -> Self {
+        Port::new(edge, 0.5)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Path {
+    Line,
+    /// A circular arc leaving its first port straight into the cell.
+    Arc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub from: Port,
+    pub to: Port,
+    pub path: Path,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tile {
+    pub sides: usize,
+    pub segments: Vec<Segment>,
+    /// How many edges the tile was turned anti-clockwise from the one it was
+    /// defined as.
+    pub turns: usize,
+}
+
+impl Tile {
+    pub fn new(sides: usize) -> Self {
+        Tile {
+            sides,
+            segments: vec![],
+            turns: 0,
+        }
+    }
+
+    pub fn segment(mut self, from: Port, to: Port, path: Path) -> Self {
+        assert!(from.edge < self.sides && to.edge < self.sides);
+        self.segments.push(Segment { from, to, path });
+        self
+    }
+
+    /// A straight line between the midpoints of two edges.
+    pub fn line(self, a: usize, b: usize) -> Self {
+        self.segment(Port::mid(a), Port::mid(b), Path::Line)
+    }
+
+    /// An arc between the midpoints of two edges.
+    pub fn arc(self, a: usize, b: usize) -> Self {
+        self.segment(Port::mid(a), Port::mid(b), Path::Arc)
+    }
+
+    pub fn rotated(&self, turns: usize) -> Tile {
+        let turn = |p: Port| Port::new((p.edge + turns) % self.sides, p.at);
+        Tile {
+            sides: self.sides,
+            segments: self
+                .segments
+                .iter()
+                .map(|s| Segment {
+                    from: turn(s.from),
+                    to: turn(s.to),
+                    path: s.path,
+                })
+                .collect(),
+            turns: (self.turns + turns) % self.sides,
+        }
+    }
+
+    // The segments with their ends and then the list in a fixed order, for
+    // spotting rotations that give the same tile.
+    fn canonical(&self) -> Vec<(usize, i32, usize, i32, Path)> {
+        let mut segments: Vec<_> = self
+            .segments
+            .iter()
+            .map(|s| {
+                let a = (s.from.edge, (s.from.at * 1000.0).round() as i32);
+                let b = (s.to.edge, (s.to.at * 1000.0).round() as i32);
+                let (a, b) = if a <= b { (a, b) } else { (b, a) };
+                (a.0, a.1, b.0, b.1, s.path)
+            })
+            .collect();
+        segments.sort_by_key(|s| (s.0, s.1, s.2, s.3));
+        segments
+    }
+
+    /// The tile's paths in `cell`, each as a list of points.
+    pub fn paths(&self, cell: &Cell) -> Vec<Vec<Point2>> {
+        assert_eq!(cell.corners.len(), self.sides, "tile does not fit the cell");
+        self.segments
+            .iter()
+            .map(|s| {
+                let p0 = cell.port(s.from);
+                let p1 = cell.port(s.to);
+                match s.path {
+                    Path::Line => vec![p0, p1],
+                    Path::Arc => arc(p0, cell.edge_direction(s.from.edge), p1),
+                }
+            })
+            .collect()
+    }
+}
+
+// The arc from `p0` to `p1` whose centre is on the edge through `p0` running
+// along `u`, so that it leaves `p0` at right angles to the edge. Points are
+// about 3 degrees apart.
+fn arc(p0: Point2, u: Vector2, p1: Point2) -> Vec<Point2> {
+    let d = p0 - p1;
+    let ud = u.dot(d);
+    if ud.abs() < 1e-6 {
+        return vec![p0, p1];
+    }
+    let c = p0 + u * (-d.magnitude2() / (2.0 * ud));
+    let (v0, v1) = (p0 - c, p1 - c);
+    let (a0, a1) = (v0.y.atan2(v0.x), v1.y.atan2(v1.x));
+    // Inside the cell is to the left of the edge.
+    let inward = vec2(-u.y, u.x);
+    let sweep = if vec2(-v0.y, v0.x).dot(inward) > 0.0 {
+        (a1 - a0).rem_euclid(TAU)
+    } else {
+        -(a0 - a1).rem_euclid(TAU)
+    };
+    let n = (sweep.abs().to_degrees() / 3.0).ceil().max(1.0) as usize;
+    let r = v0.magnitude();
+    let mut points: Vec<Point2> = (0..n)
+        .map(|i| {
+            let a = a0 + sweep * i as f32 / n as f32;
+            c + vec2(a.cos(), a.sin()) * r
+        })
+        .collect();
+    points.push(p1);
+    points
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileSet {
+    pub sides: usize,
+    pub tiles: Vec<Tile>,
+}
+
+impl TileSet {
+    pub fn new(sides: usize) -> Self {
+        TileSet {
+            sides,
+            tiles: vec![],
+        }
+    }
+
+    pub fn tile(mut self, tile: Tile) -> Self {
+        assert_eq!(tile.sides, self.sides, "tile does not fit the tile set");
+        self.tiles.push(tile);
+        self
+    }
+
+    /// Add every distinct rotation of the tiles, each right after the tile it
+    /// was turned from.
+    pub fn with_rotations(self) -> Self {
+        let mut tiles: Vec<Tile> = vec![];
+        for tile in &self.tiles {
+            for k in 0..self.sides {
+                let t = tile.rotated(k);
+                if tiles.iter().all(|u| u.canonical() != t.canonical()) {
+                    tiles.push(t);
+                }
+            }
+        }
+        TileSet { tiles, ..self }
+    }
+
+    pub fn from_name(name: &str) -> Option<TileSet> {
+        TILE_SETS.iter().find(|(n, _)| *n == name).map(|(_, f)| f())
+    }
+}
+
+/// Smith's quarter circles, two arcs around opposite corners.
+pub fn smith() -> TileSet {
+    TileSet::new(4)
+        .tile(Tile::new(4).arc(0, 1).arc(2, 3))
+        .with_rotations()
+}
+
+/// Smith's tiles and a crossing.
+pub fn weave() -> TileSet {
+    let mut set = smith();
+    set.tiles.push(Tile::new(4).line(0, 2).line(1, 3));
+    set
+}
+
+/// Kolmyjec's Hex Variation, a line across and an arc around the corner at
+/// either end of it.
+pub fn kolmyjec() -> TileSet {
+    TileSet::new(6)
+        .tile(Tile::new(6).line(1, 4).arc(2, 3).arc(5, 0))
+        .with_rotations()
+}
+
+/// Three arcs around alternate corners.
+pub fn hex_arcs() -> TileSet {
+    TileSet::new(6)
+        .tile(Tile::new(6).arc(0, 1).arc(2, 3).arc(4, 5))
+        .with_rotations()
+}
+
+/// Empty tiles, straight pipes and bends, for tilings where paths may end.
+pub fn pipes() -> TileSet {
+    TileSet::new(4)
+        .tile(Tile::new(4))
+        .tile(Tile::new(4).arc(0, 1))
+        .tile(Tile::new(4).line(0, 2))
+        .with_rotations()
+}
+
+/// Empty tiles, straight pipes, and tight and wide bends.
+pub fn hex_pipes() -> TileSet {
+    TileSet::new(6)
+        .tile(Tile::new(6))
+        .tile(Tile::new(6).line(0, 3))
+        .tile(Tile::new(6).arc(0, 1))
+        .tile(Tile::new(6).arc(0, 2))
+        .with_rotations()
+}
+
+type Preset = fn() -> TileSet;
+
+pub const TILE_SETS: &[(&str, Preset)] = &[
+    ("smith", smith),
+    ("weave", weave),
+    ("kolmyjec", kolmyjec),
+    ("hex_arcs", hex_arcs),
+    ("pipes", pipes),
+    ("hex_pipes", hex_pipes),
+];
+
+/// The names of the registered tile sets that fit cells with `sides` edges.
+pub fn tile_sets(sides: usize) -> Vec<&'static str> {
+    TILE_SETS
+        .iter()
+        .filter(|(_, f)| f().sides == sides)
+        .map(|(n, _)| *n)
+        .collect()
+}
+
+/// A tile set laid on a grid, `placed[i]` is the tile in `grid.cells[i]`.
+#[derive(Debug, Clone)]
+pub struct Truchet {
+    pub grid: Grid,
+    pub tiles: TileSet,
+    pub placed: Vec<usize>,
+}
+
+impl Truchet {
+    /// The tile picked by `rule` in each cell.
+    pub fn rule(grid: Grid, tiles: TileSet, rule: impl Fn(&Cell) -> usize) -> Self {
+        assert_eq!(grid.sides, tiles.sides, "tile set does not fit the grid");
+        assert!(!tiles.tiles.is_empty(), "empty tile set");
+        let n = tiles.tiles.len();
+        let placed = grid.cells.iter().map(|c| rule(c) % n).collect();
+        Truchet {
+            grid,
+            tiles,
+            placed,
+        }
+    }
+
+    pub fn random(grid: Grid, tiles: TileSet) -> Self {
+        let n = tiles.tiles.len();
+        Truchet::rule(grid, tiles, |_| random_range(0, n))
+    }
+
+    /// Tiles picked by the noise at each cell's centre, low values pick the
+    /// first tiles.
+    pub fn noise<N>(grid: Grid, tiles: TileSet, noise: &N, frequency: f64) -> Self
+    where
+        N: NoiseFn<[f64; 3]>,
+    {
+        let n = tiles.tiles.len();
+        Truchet::rule(grid, tiles, |c| {
+            let p = [
+                frequency * c.center.x as f64,
+                frequency * c.center.y as f64,
+                0.0,
+            ];
+            let v = (noise.get(p) as f32 + 1.0) / 2.0;
+            (v.clamp(0.0, 1.0) * n as f32).min(n as f32 - 1.0) as usize
+        })
+    }
+
+    /// Each cell with the tile placed in it.
+    pub fn cells(&self) -> impl Iterator<Item = (&Cell, &Tile)> {
+        self.grid
+            .cells
+            .iter()
+            .zip(self.placed.iter())
+            .map(move |(c, i)| (c, &self.tiles.tiles[*i]))
+    }
+
+    /// The paths of every tile, joined where they meet. Open polylines, which
+    /// end at the edge of the grid, come first, closed ones end where they
+    /// start.
+    pub fn polylines(&self) -> Vec<Vec<Point2>> {
+        let pieces: Vec<Vec<Point2>> = self.cells().flat_map(|(c, t)| t.paths(c)).collect();
+        let key = |p: Point2| ((p.x * 64.0).round() as i64, (p.y * 64.0).round() as i64);
+        let mut ends: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (i, piece) in pieces.iter().enumerate() {
+            ends.entry(key(piece[0])).or_default().push(i);
+            ends.entry(key(*piece.last().unwrap())).or_default().push(i);
+        }
+        let mut used = vec![false; pieces.len()];
+        let mut lines = vec![];
+        let mut walk = |start: usize, reverse: bool, used: &mut Vec<bool>| {
+            let mut line = pieces[start].clone();
+            if reverse {
+                line.reverse();
+            }
+            used[start] = true;
+            let first = key(line[0]);
+            loop {
+                let k = key(*line.last().unwrap());
+                let next = ends[&k].iter().copied().find(|j| !used[*j]);
+                let j = match next {
+                    Some(j) => j,
+                    None => break,
+                };
+                used[j] = true;
+                let mut piece = pieces[j].clone();
+                if key(piece[0]) != k {
+                    piece.reverse();
+                }
+                line.extend(piece.into_iter().skip(1));
+            }
+            if line.len() > 2 && key(*line.last().unwrap()) == first {
+                *line.last_mut().unwrap() = line[0];
+            }
+            lines.push(line);
+        };
+        let degree = |p: Point2| ends[&key(p)].len();
+        for i in 0..pieces.len() {
+            if used[i] {
+                continue;
+            }
+            if degree(pieces[i][0]) == 1 {
+                walk(i, false, &mut used);
+            } else if degree(*pieces[i].last().unwrap()) == 1 {
+                walk(i, true, &mut used);
+            }
+        }
+        for i in 0..pieces.len() {
+            if !used[i] {
+                walk(i, false, &mut used);
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hexgrid::Orientation;
+    use crate::rng;
+
+    fn length(line: &[Point2]) -> f32 {
+        line.windows(2).map(|w| w[0].distance(w[1])).sum()
+    }
+
+    #[test]
+    fn rotations_are_distinct() {
+        assert_eq!(smith().tiles.len(), 2);
+        assert_eq!(weave().tiles.len(), 3);
+        assert_eq!(kolmyjec().tiles.len(), 3);
+        assert_eq!(hex_arcs().tiles.len(), 2);
+        let turns: Vec<usize> = kolmyjec().tiles.iter().map(|t| t.turns).collect();
+        assert_eq!(turns, vec![0, 1, 2]);
+        assert_eq!(pipes().tiles.len(), 7);
+        assert_eq!(hex_pipes().tiles.len(), 16);
+        assert_eq!(tile_sets(4), vec!["smith", "weave", "pipes"]);
+        assert_eq!(tile_sets(6), vec!["kolmyjec", "hex_arcs", "hex_pipes"]);
+        assert!(TileSet::from_name("penrose").is_none());
+    }
+
+    #[test]
+    fn arcs_go_around_corners() {
+        let grid = Grid::square(Rect::from_w_h(10.0, 10.0), 10.0);
+        let cell = &grid.cells[0];
+        let paths = smith().tiles[0].paths(cell);
+        for (path, corner) in paths.iter().zip([cell.corners[1], cell.corners[3]].iter()) {
+            assert!(path
+                .iter()
+                .all(|p| (p.distance(*corner) - 5.0).abs() < 1e-3));
+            // A quarter circle, not the other three quarters.
+            assert!((length(path) - 2.5 * PI).abs() < 0.05);
+        }
+        let layout = Layout::new(Orientation::Flat, 10.0, pt2(0.0, 0.0));
+        let grid = Grid::hex(layout, Rect::from_w_h(1.0, 1.0));
+        let cell = &grid.cells[0];
+        let paths = kolmyjec().tiles[0].paths(cell);
+        let h = 5.0 * 3f32.sqrt();
+        assert!(paths[0][0].distance(pt2(0.0, h)) < 1e-3);
+        assert!(paths[0][1].distance(pt2(0.0, -h)) < 1e-3);
+        assert!(paths[1]
+            .iter()
+            .all(|p| (p.distance(pt2(-10.0, 0.0)) - 5.0).abs() < 1e-3));
+        assert!(paths[2]
+            .iter()
+            .all(|p| (p.distance(pt2(10.0, 0.0)) - 5.0).abs() < 1e-3));
+    }
+
+    #[test]
+    fn polylines_join_across_tiles() {
+        rng::seed(3);
+        let rect = Rect::from_w_h(200.0, 120.0);
+        let truchet = Truchet::random(Grid::square(rect, 20.0), weave());
+        let lines = truchet.polylines();
+        let pieces: f32 = truchet
+            .cells()
+            .flat_map(|(c, t)| t.paths(c))
+            .map(|p| length(&p))
+            .sum();
+        let total: f32 = lines.iter().map(|l| length(l)).sum();
+        assert!((pieces - total).abs() < 1e-2);
+        assert!(lines.len() < 10 * 6 * 2);
+        let on_border =
+            |p: Point2| (p.x.abs() - 100.0).abs() < 1e-3 || (p.y.abs() - 60.0).abs() < 1e-3;
+        for line in &lines {
+            let (a, b) = (line[0], *line.last().unwrap());
+            assert!(a == b || (on_border(a) && on_border(b)), "{:?} {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn hex_polylines_close() {
+        let layout = Layout::new(Orientation::Flat, 10.0, pt2(0.0, 0.0));
+        // Every cell the same, arcs round alternate corners close into
+        // little circles inside the grid.
+        let grid = Grid::hex(layout, Rect::from_w_h(150.0, 150.0));
+        let cells = grid.cells.len();
+        let truchet = Truchet::rule(grid, hex_arcs(), |_| 0);
+        let lines = truchet.polylines();
+        let closed = lines.iter().filter(|l| l[0] == *l.last().unwrap()).count();
+        assert!(closed > cells / 4, "{} of {}", closed, cells);
+        for l in lines.iter().filter(|l| l[0] == *l.last().unwrap()) {
+            assert!((length(l) - TAU * 5.0).abs() < 0.2);
+        }
+    }
+
+    #[test]
+    fn placement() {
+        let rect = Rect::from_w_h(100.0, 100.0);
+        let nn = nannou::noise::Perlin::new();
+        let a = Truchet::noise(Grid::square(rect, 10.0), weave(), &nn, 0.02);
+        let b = Truchet::noise(Grid::square(rect, 10.0), weave(), &nn, 0.02);
+        assert_eq!(a.placed, b.placed);
+        assert!(a.placed.iter().all(|i| *i < 3));
+        let checks = Truchet::rule(Grid::square(rect, 10.0), smith(), |c| {
+            (c.key.0 + c.key.1) as usize
+        });
+        assert_eq!(checks.placed[..3], [0, 1, 0]);
+    }
+}
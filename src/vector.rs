@@ -0,0 +1,286 @@
+// Export a canvas as an svg document. Masks become svg masks, path masks as
+// paths and image masks as the rendered mask embedded as a png.
+
+use nannou::color::{LinSrgba, Srgba};
+use nannou::image::{DynamicImage, ImageOutputFormat};
+use std::fmt::Write;
+use std::path::Path;
+use svg::node::element::{
+    Definitions, Element, Filter, Group, Image, Mask as SvgMask, Path as SvgPath, Rectangle,
+};
+use svg::{Document, Node};
+
+use crate::canvas::{Canvas, Cap, FillRule, Join, Mask, MaskSource, Segment, Shape};
+use crate::raster::mask_image;
+
+fn hex_color(c: LinSrgba) -> (String, f32) {
+    let c: Srgba = Srgba::from_linear(c);
+    let c: Srgba<u8> = c.into_format();
+    (
+        format!("#{:02X}{:02X}{:02X}", c.red, c.green, c.blue),
+        c.alpha as f32 / 255.0,
+    )
+}
+
+fn svg_rule(rule: FillRule) -> &'static str {
+    match rule {
+        FillRule::NonZero => "nonzero",
+        FillRule::EvenOdd => "evenodd",
+    }
+}
+
+// Svg's y axis points down, nannou's points up.
+fn path_data(segments: &[Segment], canvas: &Canvas) -> String {
+    let (w2, h2) = (canvas.width / 2.0, canvas.height / 2.0);
+    let mut d = String::new();
+    for s in segments {
+        let s = s.map(|p| nannou::geom::pt2(p.x + w2, h2 - p.y));
+        match s {
+            Segment::MoveTo(p) => write!(d, "M{:.3} {:.3}", p.x, p.y),
+            Segment::LineTo(p) => write!(d, "L{:.3} {:.3}", p.x, p.y),
+            Segment::CubicTo(a, b, p) => write!(
+                d,
+                "C{:.3} {:.3} {:.3} {:.3} {:.3} {:.3}",
+                a.x, a.y, b.x, b.y, p.x, p.y
+            ),
+            Segment::Close => write!(d, "Z"),
+        }
+        .unwrap();
+    }
+    d
+}
+
+fn base64(data: &[u8]) -> String {
+    const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(CHARS[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+// The svg mask for the mask scope `i`, and a filter for its feather.
+fn mask_def(canvas: &Canvas, mask: &Mask, i: usize) -> (SvgMask, Option<Filter>) {
+    let id = format!("mask{}", i);
+    let mut def = SvgMask::new()
+        .set("id", id.as_str())
+        .set("maskUnits", "userSpaceOnUse")
+        .set("x", 0)
+        .set("y", 0)
+        .set("width", canvas.width)
+        .set("height", canvas.height);
+    match &mask.source {
+        MaskSource::Path { segments, rule, .. } => {
+            let mut path = SvgPath::new()
+                .set("d", path_data(segments, canvas))
+                .set("fill-rule", svg_rule(*rule));
+            if mask.inverse {
+                def = def.add(
+                    Rectangle::new()
+                        .set("width", "100%")
+                        .set("height", "100%")
+                        .set("fill", "#FFFFFF"),
+                );
+                path = path.set("fill", "#000000");
+            } else {
+                path = path.set("fill", "#FFFFFF");
+            }
+            if mask.feather <= 0.0 {
+                return (def.add(path), None);
+            }
+            let filter_id = format!("feather{}", i);
+            let mut blur = Element::new("feGaussianBlur");
+            blur.assign("stdDeviation", mask.sigma());
+            let filter = Filter::new()
+                .set("id", filter_id.as_str())
+                .set("filterUnits", "userSpaceOnUse")
+                .set("x", 0)
+                .set("y", 0)
+                .set("width", canvas.width)
+                .set("height", canvas.height)
+                .add(blur);
+            path = path.set("filter", format!("url(#{})", filter_id));
+            (def.add(path), Some(filter))
+        }
+        // Svg has no way to sample an image's alpha and feather it, so embed
+        // the mask as the rasterizer sees it.
+        MaskSource::Image { .. } => {
+            let mut png = vec![];
+            DynamicImage::ImageLuma8(mask_image(canvas, mask, 1.0))
+                .write_to(&mut png, ImageOutputFormat::Png)
+                .expect("failed to encode mask");
+            let image = Image::new()
+                .set("width", canvas.width)
+                .set("height", canvas.height)
+                .set("preserveAspectRatio", "none")
+                .set("href", format!("data:image/png;base64,{}", base64(&png)));
+            (def.add(image), None)
+        }
+    }
+}
+
+fn svg_path(shape: &Shape, canvas: &Canvas) -> SvgPath {
+    let mut path = SvgPath::new().set("d", path_data(&shape.segments, canvas));
+    path = match shape.fill {
+        Some(fill) => {
+            let (color, opacity) = hex_color(fill);
+            path.set("fill", color)
+                .set("fill-opacity", opacity)
+                .set("fill-rule", svg_rule(shape.rule))
+        }
+        None => path.set("fill", "none"),
+    };
+    if let Some(stroke) = shape.scaled_stroke() {
+        let (color, opacity) = hex_color(stroke.color);
+        path = path
+            .set("stroke", color)
+            .set("stroke-opacity", opacity)
+            .set("stroke-width", stroke.weight)
+            .set(
+                "stroke-linecap",
+                match stroke.cap {
+                    Cap::Butt => "butt",
+                    Cap::Round => "round",
+                    Cap::Square => "square",
+                },
+            )
+            .set(
+                "stroke-linejoin",
+                match stroke.join {
+                    Join::Miter => "miter",
+                    Join::Round => "round",
+                    Join::Bevel => "bevel",
+                },
+            );
+    }
+    path
+}
+
+// Close the innermost open mask group.
+fn close_group(doc: &mut Document, groups: &mut Vec<(usize, Group)>) {
+    let (_, group) = groups.pop().unwrap();
+    match groups.last_mut() {
+        Some((_, parent)) => parent.append(group),
+        None => doc.append(group),
+    }
+}
+
+/// An svg of the canvas. Plotters ignore masks, export
+/// `canvas.clip_masks()` for them instead.
+pub fn document(canvas: &Canvas) -> Document {
+    let mut doc = Document::new()
+        .set("viewBox", (0, 0, canvas.width, canvas.height))
+        .set("width", canvas.width)
+        .set("height", canvas.height);
+    if let Some(bg) = canvas.background_color() {
+        let (color, opacity) = hex_color(bg);
+        doc = doc.add(
+            Rectangle::new()
+                .set("width", "100%")
+                .set("height", "100%")
+                .set("fill", color)
+                .set("fill-opacity", opacity),
+        );
+    }
+    if !canvas.masks().is_empty() {
+        let mut defs = Definitions::new();
+        for (i, scope) in canvas.masks().iter().enumerate() {
+            let (mask, filter) = mask_def(canvas, &scope.mask, i);
+            if let Some(filter) = filter {
+                defs = defs.add(filter);
+            }
+            defs = defs.add(mask);
+        }
+        doc = doc.add(defs);
+    }
+    // Shapes in a mask scope go in a group masked by it, nested like the
+    // scopes are.
+    let mut groups: Vec<(usize, Group)> = vec![];
+    for shape in canvas.shapes() {
+        let chain = canvas.mask_chain(shape.mask);
+        let keep = groups
+            .iter()
+            .zip(&chain)
+            .take_while(|((i, _), j)| i == *j)
+            .count();
+        while groups.len() > keep {
+            close_group(&mut doc, &mut groups);
+        }
+        for &i in &chain[keep..] {
+            groups.push((i, Group::new().set("mask", format!("url(#mask{})", i))));
+        }
+        let path = svg_path(shape, canvas);
+        match groups.last_mut() {
+            Some((_, group)) => group.append(path),
+            None => doc.append(path),
+        }
+    }
+    while !groups.is_empty() {
+        close_group(&mut doc, &mut groups);
+    }
+    doc
+}
+
+pub fn save_svg<P: AsRef<Path>>(canvas: &Canvas, path: P) {
+    svg::save(path, &document(canvas)).expect("failed to save svg");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nannou::prelude::*;
+
+    #[test]
+    fn flips_y_and_writes_colors() {
+        let mut canvas = Canvas::new(100.0, 50.0);
+        canvas.background(WHITE);
+        canvas
+            .line(pt2(-50.0, 25.0), pt2(50.0, -25.0))
+            .color(RED)
+            .weight(2.0);
+        let svg = document(&canvas).to_string();
+        assert!(svg.contains("M0.000 0.000L100.000 50.000"));
+        assert!(svg.contains("stroke=\"#FF0000\""));
+        assert!(svg.contains("fill=\"#FFFFFF\""));
+    }
+
+    #[test]
+    fn base64_pads() {
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"M"), "TQ==");
+    }
+
+    #[test]
+    fn masks_wrap_shapes_in_groups() {
+        let mut canvas = Canvas::new(100.0, 50.0);
+        canvas.with_mask(Mask::circle(pt2(0.0, 0.0), 10.0).feather(2.0), |c| {
+            c.rect(pt2(0.0, 0.0), 10.0, 10.0);
+            c.with_mask(Mask::rect(pt2(0.0, 0.0), 4.0, 4.0).inverse(), |c| {
+                c.rect(pt2(0.0, 0.0), 10.0, 10.0);
+            });
+        });
+        canvas.rect(pt2(0.0, 0.0), 10.0, 10.0);
+        let svg = document(&canvas).to_string();
+        assert!(svg.contains("<mask height=\"50\" id=\"mask0\""));
+        assert!(svg.contains("filter=\"url(#feather0)\""));
+        assert!(svg.contains("<feGaussianBlur stdDeviation=\"0.8\""));
+        let outer = svg.find("mask=\"url(#mask0)\"").unwrap();
+        let inner = svg.find("mask=\"url(#mask1)\"").unwrap();
+        assert!(outer < inner);
+        assert_eq!(svg.matches("</g>").count(), 2);
+        assert!(svg.rfind("</g>") < svg.rfind("<path"));
+    }
+}
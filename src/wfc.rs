@@ -0,0 +1,409 @@
+// Wave function collapse. Every cell of a grid starts out able to hold any
+// tile, then cell by cell the one with the fewest options left is collapsed to
+// a single tile, picked at random by weight, and the choice is propagated to
+// the neighbours through the adjacency rules. When a cell runs out of options
+// the solver backs up and tries again with the last choice ruled out.
+
+use crate::rng::random_range;
+use crate::truchet::{Grid, TileSet};
+
+/// Which tiles may sit next to each other, `allowed[a][e]` has bit `b` set
+/// when tile `b` may be across edge `e` of tile `a`. Cells meet along opposite
+/// edges, edge `e` of one cell against edge `e + sides / 2` of the other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules {
+    pub sides: usize,
+    pub weights: Vec<f32>,
+    allowed: Vec<Vec<u64>>,
+}
+
+impl Rules {
+    pub fn new(
+        tiles: usize,
+        sides: usize,
+        compatible: impl Fn(usize, usize, usize) -> bool,
+    ) -> Self {
+        assert!(tiles > 0 && tiles <= 64, "between 1 and 64 tiles");
+        let allowed = (0..tiles)
+            .map(|a| {
+                (0..sides)
+                    .map(|e| {
+                        (0..tiles)
+                            .filter(|b| compatible(a, e, *b))
+                            .fold(0, |mask, b| mask | 1 << b)
+                    })
+                    .collect()
+            })
+            .collect();
+        Rules {
+            sides,
+            weights: vec![1.0; tiles],
+            allowed,
+        }
+    }
+
+    /// Each tile has a socket for each of its edges, read anti-clockwise. Two
+    /// edges fit when one socket is the other reversed, so "ab" fits "ba" and
+    /// "a" fits "a".
+    pub fn from_sockets(sockets: &[Vec<&str>]) -> Self {
+        assert!(!sockets.is_empty(), "no tiles to make rules for");
+        let sides = sockets[0].len();
+        Rules::new(sockets.len(), sides, |a, e, b| {
+            let f = (e + sides / 2) % sides;
+            sockets[a][e].chars().eq(sockets[b][f].chars().rev())
+        })
+    }
+
+    /// Truchet tiles fit when their paths meet, every port on one edge has a
+    /// port on the other.
+    pub fn from_tile_set(tiles: &TileSet) -> Self {
+        let sides = tiles.sides;
+        let socket = |t: usize, e: usize, flip: bool| {
+            let mut ports: Vec<i32> = tiles.tiles[t]
+                .segments
+                .iter()
+                .flat_map(|s| vec![s.from, s.to])
+                .filter(|p| p.edge == e)
+                .map(|p| {
+                    let at = if flip { 1.0 - p.at } else { p.at };
+                    (at * 1000.0).round() as i32
+                })
+                .collect();
+            ports.sort_unstable();
+            ports
+        };
+        Rules::new(tiles.tiles.len(), sides, |a, e, b| {
+            socket(a, e, false) == socket(b, (e + sides / 2) % sides, true)
+        })
+    }
+
+    /// Learn the rules from an example on a square grid, `example[row][col]`
+    /// with the first row at the top. Values that are side by side in the
+    /// example may be side by side in the solution, and each value is
+    /// weighted by how often it appears. Returns the values, tile `i` being
+    /// `values[i]`.
+    pub fn learn<T: PartialEq + Clone>(example: &[Vec<T>]) -> (Self, Vec<T>) {
+        let mut values: Vec<T> = vec![];
+        let mut tiles = vec![];
+        for row in example {
+            let mut ts = vec![];
+            for v in row {
+                let t = match values.iter().position(|u| u == v) {
+                    Some(t) => t,
+                    None => {
+                        values.push(v.clone());
+                        values.len() - 1
+                    }
+                };
+                ts.push(t);
+            }
+            tiles.push(ts);
+        }
+        let mut rules = Rules::new(values.len(), 4, |_, _, _| false);
+        rules.weights = vec![0.0; values.len()];
+        for (i, row) in tiles.iter().enumerate() {
+            for (j, &a) in row.iter().enumerate() {
+                rules.weights[a] += 1.0;
+                if let Some(&b) = row.get(j + 1) {
+                    rules.allowed[a][0] |= 1 << b;
+                    rules.allowed[b][2] |= 1 << a;
+                }
+                if let Some(&b) = tiles.get(i + 1).and_then(|r| r.get(j)) {
+                    rules.allowed[a][3] |= 1 << b;
+                    rules.allowed[b][1] |= 1 << a;
+                }
+            }
+        }
+        (rules, values)
+    }
+
+    pub fn weights(mut self, weights: Vec<f32>) -> Self {
+        assert_eq!(weights.len(), self.len(), "one weight per tile");
+        self.weights = weights;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.allowed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty()
+    }
+
+    pub fn allows(&self, a: usize, edge: usize, b: usize) -> bool {
+        self.allowed[a][edge] & 1 << b != 0
+    }
+}
+
+fn tiles(mask: u64) -> impl Iterator<Item = usize> {
+    (0..64).filter(move |t| mask & 1 << t != 0)
+}
+
+pub struct Wfc {
+    rules: Rules,
+    neighbors: Vec<Vec<Option<usize>>>,
+    wave: Vec<u64>,
+    max_backtracks: usize,
+}
+
+impl Wfc {
+    pub fn new(grid: &Grid, rules: Rules) -> Self {
+        assert_eq!(grid.sides, rules.sides, "rules do not fit the grid");
+        let all = u64::MAX >> (64 - rules.len());
+        Wfc {
+            neighbors: grid.neighbors(),
+            wave: vec![all; grid.cells.len()],
+            rules,
+            max_backtracks: 10_000,
+        }
+    }
+
+    /// Give up after backing up this many times.
+    pub fn backtracks(mut self, n: usize) -> Self {
+        self.max_backtracks = n;
+        self
+    }
+
+    /// Place `tile` in `cell` before solving.
+    pub fn fix(&mut self, cell: usize, tile: usize) {
+        assert!(tile < self.rules.len(), "no tile {}", tile);
+        self.wave[cell] &= 1 << tile;
+    }
+
+    /// The tiles `cell` may still hold.
+    pub fn options(&self, cell: usize) -> Vec<usize> {
+        tiles(self.wave[cell]).collect()
+    }
+
+    // Rule out tiles that no longer fit next to the cells in `stack`, false
+    // when a cell is left with nothing.
+    fn propagate(&mut self, mut stack: Vec<usize>) -> bool {
+        while let Some(c) = stack.pop() {
+            for (e, n) in self.neighbors[c].iter().enumerate() {
+                let n = match n {
+                    Some(n) => *n,
+                    None => continue,
+                };
+                let fits = tiles(self.wave[c]).fold(0, |m, a| m | self.rules.allowed[a][e]);
+                let w = self.wave[n] & fits;
+                if w != self.wave[n] {
+                    if w == 0 {
+                        return false;
+                    }
+                    self.wave[n] = w;
+                    stack.push(n);
+                }
+            }
+        }
+        true
+    }
+
+    // The undecided cell with the lowest entropy, ties broken at random.
+    fn observe(&self) -> Option<usize> {
+        let mut best = None;
+        let mut lowest = f32::MAX;
+        for (i, &w) in self.wave.iter().enumerate() {
+            if w.count_ones() < 2 {
+                continue;
+            }
+            let (sum, sum_log) = tiles(w).fold((0.0, 0.0), |(s, l), t| {
+                let x = self.rules.weights[t].max(1e-6);
+                (s + x, l + x * x.ln())
+            });
+            let entropy = sum.ln() - sum_log / sum + random_range(0.0, 1e-4);
+            if entropy < lowest {
+                lowest = entropy;
+                best = Some(i);
+            }
+        }
+        best
+    }
+
+    fn pick(&self, cell: usize) -> usize {
+        let options = self.options(cell);
+        let total: f32 = options.iter().map(|t| self.rules.weights[*t]).sum();
+        let mut x = random_range(0.0, total);
+        for &t in &options {
+            x -= self.rules.weights[t];
+            if x < 0.0 {
+                return t;
+            }
+        }
+        *options.last().unwrap()
+    }
+
+    /// A tile for every cell, or `None` when the rules and fixed tiles can't
+    /// be satisfied or the solver gave up.
+    pub fn solve(&mut self) -> Option<Vec<usize>> {
+        if self.wave.contains(&0) || !self.propagate((0..self.wave.len()).collect()) {
+            return None;
+        }
+        let mut choices: Vec<(Vec<u64>, usize, usize)> = vec![];
+        let mut backtracks = 0;
+        while let Some(cell) = self.observe() {
+            let tile = self.pick(cell);
+            choices.push((self.wave.clone(), cell, tile));
+            self.wave[cell] = 1 << tile;
+            let mut ok = self.propagate(vec![cell]);
+            while !ok {
+                backtracks += 1;
+                if backtracks > self.max_backtracks {
+                    return None;
+                }
+                let (wave, cell, tile) = choices.pop()?;
+                self.wave = wave;
+                self.wave[cell] &= !(1 << tile);
+                ok = self.wave[cell] != 0 && self.propagate(vec![cell]);
+            }
+        }
+        Some(
+            self.wave
+                .iter()
+                .map(|w| w.trailing_zeros() as usize)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hexgrid::{Layout, Orientation};
+    use crate::rng;
+    use crate::truchet::{hex_pipes, pipes, Truchet};
+    use nannou::geom::Rect;
+    use nannou::prelude::*;
+
+    fn fits(grid: &Grid, rules: &Rules, placed: &[usize]) -> bool {
+        grid.neighbors().iter().enumerate().all(|(i, ns)| {
+            ns.iter()
+                .enumerate()
+                .all(|(e, n)| n.is_none_or(|n| rules.allows(placed[i], e, placed[n])))
+        })
+    }
+
+    #[test]
+    fn grid_neighbors() {
+        let grid = Grid::square(Rect::from_w_h(30.0, 20.0), 10.0);
+        let ns = grid.neighbors();
+        // Cells go up the columns, so cell 0 is bottom left with cell 1
+        // above it and cell 2 to its right.
+        assert_eq!(ns[0], vec![Some(2), Some(1), None, None]);
+        let layout = Layout::new(Orientation::Pointy, 10.0, pt2(0.0, 0.0));
+        let grid = Grid::hex(layout, Rect::from_w_h(100.0, 100.0));
+        let ns = grid.neighbors();
+        assert!(ns.iter().any(|n| n.iter().all(|n| n.is_some())));
+        for (i, n) in ns.iter().enumerate() {
+            for (e, j) in n.iter().enumerate() {
+                if let Some(j) = j {
+                    assert_eq!(ns[*j][(e + 3) % 6], Some(i));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sockets() {
+        // A road that may only run left to right.
+        let rules = Rules::from_sockets(&[vec!["g", "g", "g", "g"], vec!["r", "g", "r", "g"]]);
+        assert!(rules.allows(1, 0, 1) && !rules.allows(1, 0, 0) && !rules.allows(0, 0, 1));
+        let asym = Rules::from_sockets(&[vec!["ab", "a", "ab", "a"], vec!["ba", "a", "ba", "a"]]);
+        assert!(asym.allows(0, 0, 1) && !asym.allows(0, 0, 0));
+        rng::seed(2);
+        let grid = Grid::square(Rect::from_w_h(100.0, 100.0), 10.0);
+        let mut wfc = Wfc::new(&grid, rules.clone());
+        wfc.fix(55, 1);
+        let placed = wfc.solve().unwrap();
+        assert!(fits(&grid, &rules, &placed));
+        assert_eq!(placed[55], 1);
+        // The road runs right across its row.
+        for i in (5..100).step_by(10) {
+            assert_eq!(placed[i], 1);
+        }
+    }
+
+    #[test]
+    fn learns_from_example() {
+        let example: Vec<Vec<char>> = ["~~~..", "~~...", "~..##", "..###"]
+            .iter()
+            .map(|r| r.chars().collect())
+            .collect();
+        let (rules, values) = Rules::learn(&example);
+        assert_eq!(values, vec!['~', '.', '#']);
+        assert_eq!(rules.weights, vec![6.0, 9.0, 5.0]);
+        // Water never touches land.
+        for e in 0..4 {
+            assert!(!rules.allows(0, e, 2) && !rules.allows(2, e, 0));
+        }
+        // Sand is below water and above nothing else.
+        assert!(rules.allows(0, 3, 1) && !rules.allows(1, 3, 0));
+        rng::seed(4);
+        let grid = Grid::square(Rect::from_w_h(200.0, 200.0), 10.0);
+        let placed = Wfc::new(&grid, rules.clone()).solve().unwrap();
+        assert!(fits(&grid, &rules, &placed));
+    }
+
+    #[test]
+    fn seeded_runs_repeat() {
+        let grid = Grid::square(Rect::from_w_h(150.0, 150.0), 10.0);
+        let rules = Rules::from_tile_set(&pipes());
+        rng::seed(9);
+        let a = Wfc::new(&grid, rules.clone()).solve();
+        rng::seed(9);
+        let b = Wfc::new(&grid, rules).solve();
+        assert!(a.is_some());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn pipes_close_up() {
+        rng::seed(1);
+        let layout = Layout::new(Orientation::Flat, 10.0, pt2(0.0, 0.0));
+        let grid = Grid::hex(layout, Rect::from_w_h(200.0, 200.0));
+        let tiles = hex_pipes();
+        let rules = Rules::from_tile_set(&tiles).weights((0..16).map(|t| 1.0 + t as f32).collect());
+        let mut wfc = Wfc::new(&grid, rules.clone());
+        // Empty tiles all round the outside, so every pipe joins up.
+        for (i, ns) in grid.neighbors().iter().enumerate() {
+            if ns.contains(&None) {
+                wfc.fix(i, 0);
+            }
+        }
+        let placed = wfc.solve().unwrap();
+        assert!(fits(&grid, &rules, &placed));
+        let truchet = Truchet {
+            grid,
+            tiles,
+            placed,
+        };
+        let lines = truchet.polylines();
+        assert!(!lines.is_empty());
+        assert!(lines.iter().all(|l| l[0] == *l.last().unwrap()));
+    }
+
+    #[test]
+    fn backtracks_or_gives_up() {
+        let layout = Layout::new(Orientation::Flat, 10.0, pt2(0.0, 0.0));
+        let grid = Grid::hex(layout, Rect::from_w_h(120.0, 120.0));
+        // Neighbours differ, hex grids need three colours.
+        let rules = Rules::new(3, 6, |a, _, b| a != b);
+        rng::seed(6);
+        let placed = Wfc::new(&grid, rules.clone()).solve().unwrap();
+        assert!(fits(&grid, &rules, &placed));
+        let two = Rules::new(2, 6, |a, _, b| a != b);
+        assert_eq!(Wfc::new(&grid, two).solve(), None);
+        let mut wfc = Wfc::new(&grid, rules);
+        wfc.fix(0, 1);
+        wfc.fix(
+            grid.neighbors()[0]
+                .iter()
+                .flatten()
+                .next()
+                .copied()
+                .unwrap(),
+            1,
+        );
+        assert_eq!(wfc.solve(), None);
+    }
+}
@@ -0,0 +1,77 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::{cli, gif_path};
+
+const WIDTH: f32 = {{width}}.0;
+const HEIGHT: f32 = {{height}}.0;
+const FRAMES: u64 = 360;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "{{name}}",
+    title: "{{title}}",
+    description: "{{title}}.",
+    credits: None,
+    kind: Kind::Animated,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::app(model).update(update).run();
+}
+
+fn render(rec: &mut Recorder) {
+    let mut model = Model { t: 0.0 };
+    for frame in (0..FRAMES).step_by(4) {
+        step(&mut model, frame);
+        rec.frame(&scene(&model));
+    }
+}
+
+struct Model {
+    t: f32,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(WIDTH as u32, HEIGHT as u32)
+        .view(view)
+        .build()
+        .unwrap();
+    Model { t: 0.0 }
+}
+
+fn step(model: &mut Model, frame: u64) {
+    model.t = (frame % FRAMES) as f32 / FRAMES as f32;
+}
+
+fn update(app: &App, model: &mut Model, _update: Update) {
+    step(model, app.elapsed_frames());
+}
+
+fn scene(model: &Model) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
+    let r = HEIGHT / 4.0;
+    let center = pt2(r * (TAU * model.t).cos(), r * (TAU * model.t).sin());
+    canvas.ellipse(center, r / 2.0, r / 2.0).color(PLUM);
+    canvas
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let png = cli::png();
+
+    let draw = app.draw();
+    scene(model).draw(&draw);
+
+    if png {
+        let file_path = gif_path(app, &frame);
+        app.main_window().capture_frame(file_path);
+        app.set_loop_mode(LoopMode::loop_ntimes(FRAMES as usize));
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
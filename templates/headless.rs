@@ -0,0 +1,38 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::output_path;
+use crate::raster::{self, Recorder};
+use crate::sketch::{Kind, Sketch};
+
+const WIDTH: f32 = {{width}}.0;
+const HEIGHT: f32 = {{height}}.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "{{name}}",
+    title: "{{title}}",
+    description: "{{title}}.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+// Renders straight to `img/{{name}}.png` without opening a window.
+fn run() {
+    let path = output_path(SKETCH.name, "png");
+    raster::save_png(&scene(), &path, 1.0);
+    println!("Saved {}", path.display());
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(WHITE);
+    let d = WIDTH.min(HEIGHT) / 2.0;
+    canvas.ellipse(pt2(0.0, 0.0), d, d).color(STEELBLUE);
+    canvas
+}
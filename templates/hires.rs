@@ -0,0 +1,35 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::hires;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+
+const WIDTH: f32 = {{width}}.0;
+const HEIGHT: f32 = {{height}}.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "{{name}}",
+    title: "{{title}}",
+    description: "{{title}}.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    hires::run(scene);
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(BLACK);
+    let d = WIDTH.min(HEIGHT) / 2.0;
+    canvas.ellipse(pt2(0.0, 0.0), d, d).color(ORANGE);
+    canvas
+}
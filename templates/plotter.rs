@@ -0,0 +1,60 @@
+use nannou::prelude::*;
+
+use crate::canvas::Canvas;
+use crate::raster::Recorder;
+use crate::sketch::{Kind, Sketch};
+use crate::vector::save_svg;
+use crate::{cli, output_path};
+
+const WIDTH: f32 = {{width}}.0;
+const HEIGHT: f32 = {{height}}.0;
+
+pub const SKETCH: Sketch = Sketch {
+    name: "{{name}}",
+    title: "{{title}}",
+    description: "{{title}}.",
+    credits: None,
+    kind: Kind::Static,
+    run,
+    render: Some(render),
+};
+
+fn run() {
+    nannou::sketch(view).size(WIDTH as u32, HEIGHT as u32).run()
+}
+
+fn render(rec: &mut Recorder) {
+    rec.frame(&scene());
+}
+
+// Strokes only, so the svg can go straight to a pen plotter.
+fn scene() -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    canvas.background(WHITE);
+    let r = WIDTH.min(HEIGHT) / 3.0;
+    for i in 0..10 {
+        let d = 2.0 * r * (i + 1) as f32 / 10.0;
+        canvas
+            .ellipse(pt2(0.0, 0.0), d, d)
+            .no_fill()
+            .stroke(BLACK)
+            .weight(1.0);
+    }
+    canvas
+}
+
+// `-p` saves the drawing to `img/{{name}}.svg`.
+fn view(app: &App, frame: Frame) {
+    let png = cli::png();
+
+    app.set_loop_mode(LoopMode::loop_once());
+    let draw = app.draw();
+    let canvas = scene();
+    canvas.draw(&draw);
+
+    if png {
+        save_svg(&canvas, output_path(SKETCH.name, "svg"));
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
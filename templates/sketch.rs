@@ -1,35 +0,0 @@
-use getopts::Options;
-use nannou::app::LoopMode;
-use nannou::prelude::*;
-use std::env;
-
-use sketches::img_path;
-
-const WIDTH: u32 = 900;
-const HEIGHT: u32 = 600;
-
-fn main() {
-    nannou::sketch(view).size(WIDTH, HEIGHT).run()
-}
-
-fn view(app: &App, frame: Frame) {
-    let args: Vec<String> = env::args().collect();
-    let mut opts = Options::new();
-    opts.optflag("p", "png", "save frames to file as png.");
-    let matches = match opts.parse(&args[1..]) {
-        Ok(m) => m,
-        Err(f) => panic!("{}", f),
-    };
-    let png = matches.opt_present("p");
-
-    app.set_loop_mode(LoopMode::loop_once());
-    let draw = app.draw();
-    draw.background().color(PLUM);
-
-    if png {
-        let file_path = img_path(app);
-        app.main_window().capture_frame(file_path);
-    }
-
-    draw.to_frame(app, &frame).unwrap();
-}